@@ -17,40 +17,31 @@
 use crate::{Result, IType, CallServiceClosure};
 use crate::AquamarineVMError;
 use crate::config::AquamarineVMConfig;
+use crate::config::DataStoreConfig;
+use crate::DataStore;
+use crate::FileSystemDataStore;
+use crate::InMemoryDataStore;
+use crate::particle_gc::CleanupStats;
+use crate::particle_gc::ParticleData;
+use crate::particle_gc::ParticleDataSweeper;
 
 use fluence_faas::{FaaSConfig, HostExportedFunc};
 use fluence_faas::FluenceFaaS;
 use fluence_faas::HostImportDescriptor;
 use fluence_faas::IValue;
+use serde::de::IgnoredAny;
 use stepper_interface::StepperOutcome;
 
 use std::path::PathBuf;
-use std::ops::{Deref, DerefMut};
+use std::ops::Deref;
 use std::sync::Arc;
-use parking_lot::{Mutex};
+use std::time::Duration;
+use std::time::SystemTime;
+use parking_lot::{Mutex, MutexGuard, MappedMutexGuard};
 
 const CALL_SERVICE_NAME: &str = "call_service";
 const CURRENT_PEER_ID_ENV_NAME: &str = "CURRENT_PEER_ID";
 
-/// A newtype needed to mark it as `unsafe impl Send`
-struct SendSafeFaaS(FluenceFaaS);
-
-/// Mark runtime as Send, so libp2p on the node (use-site) is happy
-unsafe impl Send for SendSafeFaaS {}
-
-impl Deref for SendSafeFaaS {
-    type Target = FluenceFaaS;
-
-    fn deref(&self) -> &Self::Target {
-        &self.0
-    }
-}
-impl DerefMut for SendSafeFaaS {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
-    }
-}
-
 /// Information about the particle that is being executed by the stepper at the moment
 #[derive(Debug, Default, Clone)]
 pub struct ParticleParameters {
@@ -59,8 +50,12 @@ pub struct ParticleParameters {
 }
 
 pub struct AquamarineVM {
-    faas: SendSafeFaaS,
-    particle_data_store: PathBuf,
+    faas: FluenceFaaS,
+    particle_data: Arc<Mutex<ParticleData>>,
+    /// ttl of particles called without an explicit one
+    particle_ttl: Option<Duration>,
+    /// removes expired particle data in background if enabled
+    _particle_data_sweeper: Option<ParticleDataSweeper>,
     /// file name of the AIR interpreter .wasm
     wasm_filename: String,
     /// information about the particle that is being executed at the moment
@@ -70,8 +65,6 @@ pub struct AquamarineVM {
 impl AquamarineVM {
     /// Create AquamarineVM with provided config.
     pub fn new(config: AquamarineVMConfig) -> Result<Self> {
-        use AquamarineVMError::InvalidDataStorePath;
-
         let current_particle: Arc<Mutex<ParticleParameters>> = <_>::default();
         let call_service = call_service_descriptor(current_particle.clone(), config.call_service);
         let (wasm_dir, wasm_filename) = split_dirname(config.aquamarine_wasm_path)?;
@@ -85,13 +78,23 @@ impl AquamarineVM {
         );
         let faas = FluenceFaaS::with_raw_config(faas_config)?;
 
-        let particle_data_store = config.particle_data_store;
-        std::fs::create_dir_all(&particle_data_store)
-            .map_err(|e| InvalidDataStorePath(e, particle_data_store.clone()))?;
+        let particle_data_store: Box<dyn DataStore> = match config.particle_data_store {
+            DataStoreConfig::FileSystem(data_dir) => Box::new(FileSystemDataStore::new(data_dir)?),
+            DataStoreConfig::InMemory => Box::new(InMemoryDataStore::default()),
+            DataStoreConfig::Custom(data_store) => data_store,
+        };
+        let particle_data = ParticleData::new(particle_data_store, config.particle_ttl)?;
+        let particle_data = Arc::new(Mutex::new(particle_data));
+
+        let particle_data_sweeper = config
+            .particle_cleanup_interval
+            .map(|interval| ParticleDataSweeper::start(particle_data.clone(), interval));
 
         let aqua_vm = Self {
-            faas: SendSafeFaaS(faas),
-            particle_data_store,
+            faas,
+            particle_data,
+            particle_ttl: config.particle_ttl,
+            _particle_data_sweeper: particle_data_sweeper,
             wasm_filename,
             current_particle,
         };
@@ -99,6 +102,7 @@ impl AquamarineVM {
         Ok(aqua_vm)
     }
 
+    /// Executes the particle, its data expires after the ttl from config.
     pub fn call(
         &mut self,
         init_user_id: impl Into<String>,
@@ -106,21 +110,44 @@ impl AquamarineVM {
         data: impl Into<Vec<u8>>,
         particle_id: impl Into<String>,
     ) -> Result<StepperOutcome> {
-        use AquamarineVMError::PersistDataError;
+        let ttl = self.particle_ttl;
+        self.call_(init_user_id, aqua, data, particle_id, ttl)
+    }
+
+    /// Executes the particle, its data expires after the given ttl counted from
+    /// the first call with this particle.
+    pub fn call_with_ttl(
+        &mut self,
+        init_user_id: impl Into<String>,
+        aqua: impl Into<String>,
+        data: impl Into<Vec<u8>>,
+        particle_id: impl Into<String>,
+        ttl: Duration,
+    ) -> Result<StepperOutcome> {
+        self.call_(init_user_id, aqua, data, particle_id, Some(ttl))
+    }
 
+    fn call_(
+        &mut self,
+        init_user_id: impl Into<String>,
+        aqua: impl Into<String>,
+        data: impl Into<Vec<u8>>,
+        particle_id: impl Into<String>,
+        ttl: Option<Duration>,
+    ) -> Result<StepperOutcome> {
         let particle_id = particle_id.into();
         let init_user_id = init_user_id.into();
 
-        let prev_data_path = self.particle_data_store.join(&particle_id);
-        // TODO: check for errors related to invalid file content (such as invalid UTF8 string)
-        let prev_data = std::fs::read_to_string(&prev_data_path)
-            .unwrap_or_default()
-            .into_bytes();
+        // the lock is held during the whole call, so data couldn't be swept while the particle is executed
+        let mut particle_data = self.particle_data.lock();
+        particle_data.record_deadline(&particle_id, ttl)?;
+
+        let prev_data = read_prev_data(particle_data.store.as_ref(), &particle_id)?;
 
         let args = prepare_args(prev_data, data, init_user_id.clone(), aqua);
 
         // Update ParticleParams with the new values so subsequent calls to `call_service` can use them
-        self.update_current_particle(particle_id, init_user_id);
+        self.update_current_particle(particle_id.clone(), init_user_id);
 
         let result =
             self.faas
@@ -130,12 +157,34 @@ impl AquamarineVM {
             .map_err(AquamarineVMError::StepperResultDeError)?;
 
         // persist resulted data
-        std::fs::write(&prev_data_path, &outcome.data)
-            .map_err(|e| PersistDataError(e, prev_data_path))?;
+        particle_data.store.write(&particle_id, &outcome.data)?;
 
         Ok(outcome)
     }
 
+    /// Removes data of the particle, e.g. a completed one, and returns its size in bytes.
+    pub fn remove_particle(&mut self, particle_id: &str) -> Result<u64> {
+        self.particle_data.lock().remove(particle_id)
+    }
+
+    /// Removes data of all particles with passed deadlines, failures are reported in stats.
+    pub fn cleanup_expired(&mut self) -> CleanupStats {
+        self.particle_data.lock().remove_expired(SystemTime::now())
+    }
+
+    /// Returns the moment after which data of the particle is stale,
+    /// None if the particle hasn't been seen or never expires.
+    pub fn particle_deadline(&self, particle_id: &str) -> Option<SystemTime> {
+        self.particle_data.lock().deadline(particle_id)
+    }
+
+    /// Returns the store of particle data, it's locked until the guard is dropped.
+    pub fn data_store(&self) -> MappedMutexGuard<'_, dyn DataStore + 'static> {
+        MutexGuard::map(self.particle_data.lock(), |particle_data| {
+            particle_data.store.as_mut()
+        })
+    }
+
     fn update_current_particle(&self, particle_id: String, init_user_id: String) {
         let mut params = self.current_particle.lock();
         params.particle_id = particle_id;
@@ -143,6 +192,29 @@ impl AquamarineVM {
     }
 }
 
+/// Reads prev data of the particle and checks that it's a JSON as the stepper produces,
+/// empty data is passed for particles without saved data.
+fn read_prev_data(store: &dyn DataStore, particle_id: &str) -> Result<Vec<u8>> {
+    use AquamarineVMError::CorruptedPrevData;
+
+    let prev_data = store.read(particle_id)?.unwrap_or_default();
+    if prev_data.is_empty() {
+        return Ok(prev_data);
+    }
+
+    let prev_data_str = std::str::from_utf8(&prev_data).map_err(|e| CorruptedPrevData {
+        particle_id: particle_id.to_string(),
+        reason: format!("it isn't a valid UTF-8 string: {}", e),
+    })?;
+
+    serde_json::from_str::<IgnoredAny>(prev_data_str).map_err(|e| CorruptedPrevData {
+        particle_id: particle_id.to_string(),
+        reason: format!("it isn't a valid JSON: {}", e),
+    })?;
+
+    Ok(prev_data)
+}
+
 fn prepare_args(
     prev_data: Vec<u8>,
     data: impl Into<Vec<u8>>,
@@ -233,6 +305,11 @@ fn make_faas_config(
         host_imports,
         wasi: None,
         logging_mask,
+        stateless: false,
+        pure_functions: <_>::default(),
+        signature: None,
+        wasm_features: <_>::default(),
+        max_stack_height: None,
     };
 
     let envs = hashmap! {
@@ -243,7 +320,13 @@ fn make_faas_config(
     FaaSConfig {
         modules_dir: Some(aquamarine_wasm_dir),
         modules_config: vec![(String::from(aquamarine_wasm_file), aquamarine_module_config)],
+        module_store_dir: None,
+        module_hashes: <_>::default(),
+        trusted_keys: vec![],
         default_modules_config: None,
+        journal_path: None,
+        memoization_cache_size: None,
+        json_options: <_>::default(),
     }
 }
 
@@ -273,3 +356,48 @@ impl AquamarineVM {
         Ok(outcome)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::read_prev_data;
+    use crate::AquamarineVMError;
+    use crate::DataStore;
+    use crate::InMemoryDataStore;
+
+    fn store_with(particle_id: &str, data: &[u8]) -> InMemoryDataStore {
+        let mut store = InMemoryDataStore::default();
+        store
+            .write(particle_id, data)
+            .unwrap_or_else(|e| panic!("can't write particle data: {:?}", e));
+        store
+    }
+
+    #[test]
+    fn valid_prev_data() {
+        let store = store_with("particle", br#"{"trace":[]}"#);
+        let prev_data = read_prev_data(&store, "particle")
+            .unwrap_or_else(|e| panic!("can't read prev data: {:?}", e));
+        assert_eq!(prev_data, br#"{"trace":[]}"#.to_vec());
+
+        let prev_data = read_prev_data(&store, "missing")
+            .unwrap_or_else(|e| panic!("can't read prev data: {:?}", e));
+        assert!(prev_data.is_empty());
+    }
+
+    #[test]
+    fn corrupted_prev_data() {
+        for data in [&b"\xff\xfe{}"[..], &br#"{"trace":["#[..]].iter() {
+            let store = store_with("particle", data);
+            let result = read_prev_data(&store, "particle");
+            assert!(
+                matches!(
+                    result,
+                    Err(AquamarineVMError::CorruptedPrevData { ref particle_id, .. }) if particle_id == "particle"
+                ),
+                "{:?} should be reported as corrupted, got {:?}",
+                data,
+                result
+            );
+        }
+    }
+}
@@ -15,12 +15,14 @@
  */
 
 use std::path::PathBuf;
+use std::time::Duration;
 
 use crate::aquamarine_stepper_vm::ParticleParameters;
+use crate::DataStore;
 use crate::IValue;
 
 pub type CallServiceClosure =
-    Box<dyn Fn(ParticleParameters, Vec<IValue>) -> Option<IValue> + 'static>;
+    Box<dyn Fn(ParticleParameters, Vec<IValue>) -> Option<IValue> + Send + 'static>;
 
 /// Describes behaviour of the Aquamarine VM stepper.
 pub struct AquamarineVMConfig {
@@ -33,10 +35,35 @@ pub struct AquamarineVMConfig {
     /// Current peer id.
     pub current_peer_id: String,
 
-    /// Path to a folder contains prev data.
+    /// Storage of prev data, a path to a folder could be converted to the default one.
     /// AquamarineVM uses it to store data obtained after stepper execution, and load it as a prev_data by particle_id.
-    pub particle_data_store: PathBuf,
+    pub particle_data_store: DataStoreConfig,
+
+    /// Time for which data of a particle is kept after the particle is first seen,
+    /// None means that data is kept until it's removed explicitly.
+    pub particle_ttl: Option<Duration>,
+
+    /// Interval of removing expired particle data in background, None disables it.
+    pub particle_cleanup_interval: Option<Duration>,
 
     /// Mask used to filter logs, for details see `log_utf8_string` in fluence-faas.
     pub logging_mask: i32,
 }
+
+/// Describes where AquamarineVM keeps particle data.
+pub enum DataStoreConfig {
+    /// Data of each particle is kept in a file in the given folder, it's the default store.
+    FileSystem(PathBuf),
+
+    /// Data is kept in memory and lost with AquamarineVM.
+    InMemory,
+
+    /// A custom store, e.g. backed by a database.
+    Custom(Box<dyn DataStore>),
+}
+
+impl From<PathBuf> for DataStoreConfig {
+    fn from(data_dir: PathBuf) -> Self {
+        DataStoreConfig::FileSystem(data_dir)
+    }
+}
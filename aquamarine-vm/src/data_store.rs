@@ -0,0 +1,289 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::AquamarineVMError;
+use crate::Result;
+
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+/// Temp files are kept in a hidden subdir, particle ids can't start with a dot,
+/// so they never clash with it.
+const TMP_DIR_NAME: &str = ".tmp";
+/// Deadlines of particles are kept in files named by particle ids in this subdir.
+const DEADLINES_DIR_NAME: &str = ".deadlines";
+
+/// Storage of particle data: data produced by the stepper is saved by particle id
+/// and passed back to it as prev_data on the next call with the same particle.
+pub trait DataStore: Send {
+    /// Returns data of the particle or None if nothing has been saved for it.
+    fn read(&self, particle_id: &str) -> Result<Option<Vec<u8>>>;
+
+    /// Saves data of the particle replacing the previous one.
+    fn write(&mut self, particle_id: &str, data: &[u8]) -> Result<()>;
+
+    /// Removes data and the deadline of the particle and returns the data size in bytes,
+    /// removing of a missing particle isn't an error and frees 0 bytes.
+    fn remove(&mut self, particle_id: &str) -> Result<u64>;
+
+    /// Returns ids of all particles with saved data.
+    fn list(&self) -> Result<Vec<String>>;
+
+    /// Saves the moment after which data of the particle is stale.
+    fn write_deadline(&mut self, particle_id: &str, deadline: SystemTime) -> Result<()>;
+
+    /// Returns saved deadlines of particles, including ones without data.
+    fn read_deadlines(&self) -> Result<HashMap<String, SystemTime>>;
+}
+
+/// Keeps data of each particle in a file named by the particle id, and its deadline
+/// in a file with the same name in a hidden subdir. Files are replaced atomically,
+/// so a crash during writing leaves either the old or the new data.
+/// Particle ids should be plain file names that don't start with a dot.
+pub struct FileSystemDataStore {
+    data_dir: PathBuf,
+}
+
+impl FileSystemDataStore {
+    /// Creates a store in the given dir, the dir is created if it doesn't exist.
+    pub fn new(data_dir: PathBuf) -> Result<Self> {
+        let tmp_dirs = [
+            data_dir.join(TMP_DIR_NAME),
+            data_dir.join(DEADLINES_DIR_NAME).join(TMP_DIR_NAME),
+        ];
+        for tmp_dir in tmp_dirs.iter() {
+            std::fs::create_dir_all(tmp_dir)
+                .map_err(|e| AquamarineVMError::InvalidDataStorePath(e, data_dir.clone()))?;
+        }
+
+        Ok(Self { data_dir })
+    }
+
+    fn data_path(&self, particle_id: &str) -> Result<PathBuf> {
+        check_particle_id(particle_id)?;
+        Ok(self.data_dir.join(particle_id))
+    }
+}
+
+impl DataStore for FileSystemDataStore {
+    fn read(&self, particle_id: &str) -> Result<Option<Vec<u8>>> {
+        let data_path = self.data_path(particle_id)?;
+        match std::fs::read(&data_path) {
+            Ok(data) => Ok(Some(data)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(AquamarineVMError::ReadDataError(e, data_path)),
+        }
+    }
+
+    fn write(&mut self, particle_id: &str, data: &[u8]) -> Result<()> {
+        let data_path = self.data_path(particle_id)?;
+        let tmp_path = self.data_dir.join(TMP_DIR_NAME).join(particle_id);
+
+        write_atomically(&tmp_path, &data_path, data).map_err(|e| {
+            // the temp file is useless after a failure, and the error of its removal is less important
+            let _ = std::fs::remove_file(&tmp_path);
+            AquamarineVMError::PersistDataError(e, data_path)
+        })
+    }
+
+    fn remove(&mut self, particle_id: &str) -> Result<u64> {
+        let data_path = self.data_path(particle_id)?;
+        let data_size = match std::fs::metadata(&data_path) {
+            Ok(metadata) => metadata.len(),
+            Err(e) if e.kind() == ErrorKind::NotFound => 0,
+            Err(e) => return Err(AquamarineVMError::RemoveDataError(e, data_path)),
+        };
+
+        // the deadline is removed last, so data that failed to be removed still expires
+        remove_file(&data_path)?;
+        remove_file(&self.data_dir.join(DEADLINES_DIR_NAME).join(particle_id))?;
+
+        Ok(data_size)
+    }
+
+    fn list(&self) -> Result<Vec<String>> {
+        let read_dir_error = |e| AquamarineVMError::ReadDataError(e, self.data_dir.clone());
+
+        let mut particle_ids = vec![];
+        for entry in std::fs::read_dir(&self.data_dir).map_err(read_dir_error)? {
+            let entry = entry.map_err(read_dir_error)?;
+            if entry.file_type().map_err(read_dir_error)?.is_dir() {
+                continue;
+            }
+
+            // files with other names couldn't be created for any particle
+            match entry.file_name().into_string() {
+                Ok(file_name) if check_particle_id(&file_name).is_ok() => {
+                    particle_ids.push(file_name)
+                }
+                _ => {}
+            }
+        }
+        particle_ids.sort();
+
+        Ok(particle_ids)
+    }
+
+    fn write_deadline(&mut self, particle_id: &str, deadline: SystemTime) -> Result<()> {
+        check_particle_id(particle_id)?;
+        let deadlines_dir = self.data_dir.join(DEADLINES_DIR_NAME);
+        let deadline_path = deadlines_dir.join(particle_id);
+        let tmp_path = deadlines_dir.join(TMP_DIR_NAME).join(particle_id);
+
+        // deadlines before the epoch are stale anyway
+        let deadline = deadline.duration_since(UNIX_EPOCH).unwrap_or_default();
+        let deadline = format!("{}.{:09}", deadline.as_secs(), deadline.subsec_nanos());
+
+        write_atomically(&tmp_path, &deadline_path, deadline.as_bytes()).map_err(|e| {
+            let _ = std::fs::remove_file(&tmp_path);
+            AquamarineVMError::PersistDataError(e, deadline_path)
+        })
+    }
+
+    fn read_deadlines(&self) -> Result<HashMap<String, SystemTime>> {
+        let deadlines_dir = self.data_dir.join(DEADLINES_DIR_NAME);
+        let read_dir_error = |e| AquamarineVMError::ReadDataError(e, deadlines_dir.clone());
+
+        let mut deadlines = HashMap::new();
+        for entry in std::fs::read_dir(&deadlines_dir).map_err(read_dir_error)? {
+            let entry = entry.map_err(read_dir_error)?;
+            let particle_id = match entry.file_name().into_string() {
+                Ok(file_name) if check_particle_id(&file_name).is_ok() => file_name,
+                _ => continue,
+            };
+
+            let deadline_path = entry.path();
+            let deadline = std::fs::read_to_string(&deadline_path)
+                .map_err(|e| AquamarineVMError::ReadDataError(e, deadline_path.clone()))?;
+            match parse_deadline(&deadline) {
+                Some(deadline) => {
+                    deadlines.insert(particle_id, deadline);
+                }
+                // the particle gets the default ttl as if its deadline hasn't been saved
+                None => log::warn!(
+                    "deadline {:?} of particle {} is malformed and ignored",
+                    deadline,
+                    particle_id
+                ),
+            }
+        }
+
+        Ok(deadlines)
+    }
+}
+
+fn parse_deadline(deadline: &str) -> Option<SystemTime> {
+    let mut parts = deadline.splitn(2, '.');
+    let secs = parts.next()?.parse().ok()?;
+    let nanos = parts.next()?.parse().ok()?;
+
+    UNIX_EPOCH.checked_add(Duration::new(secs, nanos))
+}
+
+fn remove_file(path: &Path) -> Result<()> {
+    match std::fs::remove_file(path) {
+        Err(e) if e.kind() != ErrorKind::NotFound => {
+            Err(AquamarineVMError::RemoveDataError(e, path.to_path_buf()))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Writes data to a temp file, flushes it to disk and renames it to the target path.
+fn write_atomically(tmp_path: &Path, path: &Path, data: &[u8]) -> std::io::Result<()> {
+    let mut tmp_file = std::fs::File::create(tmp_path)?;
+    tmp_file.write_all(data)?;
+    tmp_file.sync_all()?;
+
+    std::fs::rename(tmp_path, path)?;
+
+    // the rename itself is durable only after the dir is synced
+    match path.parent() {
+        Some(dir) => sync_dir(dir),
+        None => Ok(()),
+    }
+}
+
+#[cfg(unix)]
+fn sync_dir(dir: &Path) -> std::io::Result<()> {
+    std::fs::File::open(dir)?.sync_all()
+}
+
+#[cfg(not(unix))]
+fn sync_dir(_dir: &Path) -> std::io::Result<()> {
+    // directories can't be opened as files here
+    Ok(())
+}
+
+/// Particle ids are used as file names, so they can't address other files.
+fn check_particle_id(particle_id: &str) -> Result<()> {
+    let is_invalid = particle_id.is_empty()
+        || particle_id.starts_with('.')
+        || particle_id.contains(&['/', '\\', '\0'][..]);
+    if is_invalid {
+        return Err(AquamarineVMError::InvalidParticleId(
+            particle_id.to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Keeps particle data in memory, so it's lost with AquamarineVM. Intended mostly for tests.
+#[derive(Debug, Default, Clone)]
+pub struct InMemoryDataStore {
+    particles: HashMap<String, Vec<u8>>,
+    deadlines: HashMap<String, SystemTime>,
+}
+
+impl DataStore for InMemoryDataStore {
+    fn read(&self, particle_id: &str) -> Result<Option<Vec<u8>>> {
+        Ok(self.particles.get(particle_id).cloned())
+    }
+
+    fn write(&mut self, particle_id: &str, data: &[u8]) -> Result<()> {
+        self.particles
+            .insert(particle_id.to_string(), data.to_vec());
+        Ok(())
+    }
+
+    fn remove(&mut self, particle_id: &str) -> Result<u64> {
+        self.deadlines.remove(particle_id);
+        let data = self.particles.remove(particle_id);
+        Ok(data.map_or(0, |data| data.len() as u64))
+    }
+
+    fn list(&self) -> Result<Vec<String>> {
+        let mut particle_ids = self.particles.keys().cloned().collect::<Vec<_>>();
+        particle_ids.sort();
+
+        Ok(particle_ids)
+    }
+    fn write_deadline(&mut self, particle_id: &str, deadline: SystemTime) -> Result<()> {
+        self.deadlines.insert(particle_id.to_string(), deadline);
+        Ok(())
+    }
+
+    fn read_deadlines(&self) -> Result<HashMap<String, SystemTime>> {
+        Ok(self.deadlines.clone())
+    }
+}
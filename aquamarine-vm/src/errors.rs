@@ -31,6 +31,21 @@ pub enum AquamarineVMError {
     /// I/O errors while persisting resulted data.
     PersistDataError(IOError, PathBuf),
 
+    /// I/O errors while reading prev data.
+    ReadDataError(IOError, PathBuf),
+
+    /// I/O errors while removing particle data.
+    RemoveDataError(IOError, PathBuf),
+
+    /// Errors of custom data stores.
+    DataStoreError(String),
+
+    /// A particle id can't be used by the data store, e.g. it isn't a plain file name.
+    InvalidParticleId(String),
+
+    /// Saved prev data of the particle isn't a valid UTF-8 JSON produced by the stepper.
+    CorruptedPrevData { particle_id: String, reason: String },
+
     /// Errors related to particle_data_store path from supplied config.
     InvalidDataStorePath(IOError, PathBuf),
 
@@ -54,6 +69,30 @@ impl std::fmt::Display for AquamarineVMError {
                 "an error occurred while saving prev data {:?} by {:?} path",
                 err, path
             ),
+            AquamarineVMError::ReadDataError(err, path) => write!(
+                f,
+                "an error occurred while reading prev data {:?} by {:?} path",
+                err, path
+            ),
+            AquamarineVMError::RemoveDataError(err, path) => write!(
+                f,
+                "an error occurred while removing particle data {:?} by {:?} path",
+                err, path
+            ),
+            AquamarineVMError::DataStoreError(err_msg) => write!(f, "{}", err_msg),
+            AquamarineVMError::InvalidParticleId(particle_id) => write!(
+                f,
+                "particle id {:?} isn't a plain file name or starts with a dot",
+                particle_id
+            ),
+            AquamarineVMError::CorruptedPrevData {
+                particle_id,
+                reason,
+            } => write!(
+                f,
+                "prev data of particle {} is corrupted: {}",
+                particle_id, reason
+            ),
             AquamarineVMError::InvalidDataStorePath(err, path) => write!(
                 f,
                 "an error occurred while creating data storage {:?} by {:?} path",
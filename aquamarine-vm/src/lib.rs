@@ -26,13 +26,20 @@
 
 mod aquamarine_stepper_vm;
 mod config;
+mod data_store;
 mod errors;
+mod particle_gc;
 
 pub use aquamarine_stepper_vm::AquamarineVM;
 pub use aquamarine_stepper_vm::ParticleParameters;
 pub use config::CallServiceClosure;
 pub use config::AquamarineVMConfig;
+pub use config::DataStoreConfig;
+pub use data_store::DataStore;
+pub use data_store::FileSystemDataStore;
+pub use data_store::InMemoryDataStore;
 pub use errors::AquamarineVMError;
+pub use particle_gc::CleanupStats;
 
 // Re-exports
 pub use fluence_faas::HostExportedFunc;
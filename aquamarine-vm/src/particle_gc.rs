@@ -0,0 +1,369 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::AquamarineVMError;
+use crate::DataStore;
+use crate::Result;
+
+use parking_lot::Mutex;
+
+use std::collections::HashMap;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+use std::time::SystemTime;
+
+/// Describes particle data removed by a cleanup.
+#[derive(Debug, Default)]
+pub struct CleanupStats {
+    /// Ids of particles whose data has been removed.
+    pub removed_particles: Vec<String>,
+
+    /// Total size of removed data in bytes.
+    pub reclaimed_bytes: u64,
+
+    /// Expired particles whose data couldn't be removed, they're retried by the next cleanup.
+    pub failed_particles: Vec<(String, AquamarineVMError)>,
+}
+
+/// Particle data store together with deadlines after which data of particles is stale.
+pub(crate) struct ParticleData {
+    pub(crate) store: Box<dyn DataStore>,
+    deadlines: HashMap<String, SystemTime>,
+}
+
+impl ParticleData {
+    /// Restores deadlines saved in the store, particles present in the store without
+    /// a saved deadline get the default ttl counted from now.
+    pub(crate) fn new(store: Box<dyn DataStore>, default_ttl: Option<Duration>) -> Result<Self> {
+        let deadlines = store.read_deadlines()?;
+        let mut particle_data = Self { store, deadlines };
+
+        if default_ttl.is_some() {
+            for particle_id in particle_data.store.list()? {
+                particle_data.record_deadline(&particle_id, default_ttl)?;
+            }
+        }
+
+        Ok(particle_data)
+    }
+
+    /// Records the deadline of a particle when it's seen for the first time,
+    /// ttls passed with subsequent calls don't prolong it.
+    pub(crate) fn record_deadline(
+        &mut self,
+        particle_id: &str,
+        ttl: Option<Duration>,
+    ) -> Result<()> {
+        if self.deadlines.contains_key(particle_id) {
+            return Ok(());
+        }
+
+        if let Some(deadline) = ttl.and_then(deadline_after) {
+            self.store.write_deadline(particle_id, deadline)?;
+            self.deadlines.insert(particle_id.to_string(), deadline);
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn deadline(&self, particle_id: &str) -> Option<SystemTime> {
+        self.deadlines.get(particle_id).copied()
+    }
+
+    /// Removes data and the deadline of the particle, returns the size of removed data.
+    pub(crate) fn remove(&mut self, particle_id: &str) -> Result<u64> {
+        let data_size = self.store.remove(particle_id)?;
+        self.deadlines.remove(particle_id);
+
+        Ok(data_size)
+    }
+
+    /// Removes data of all particles with deadlines not later than now, particles
+    /// that failed to be removed are reported in stats and keep their deadlines.
+    pub(crate) fn remove_expired(&mut self, now: SystemTime) -> CleanupStats {
+        let mut expired_particles = self
+            .deadlines
+            .iter()
+            .filter(|(_, deadline)| **deadline <= now)
+            .map(|(particle_id, _)| particle_id.clone())
+            .collect::<Vec<_>>();
+        expired_particles.sort();
+
+        let mut stats = CleanupStats::default();
+        for particle_id in expired_particles {
+            match self.remove(&particle_id) {
+                Ok(data_size) => {
+                    stats.reclaimed_bytes += data_size;
+                    stats.removed_particles.push(particle_id);
+                }
+                Err(e) => stats.failed_particles.push((particle_id, e)),
+            }
+        }
+
+        stats
+    }
+}
+
+/// Periodically removes expired particle data in a separate thread, it's stopped on drop.
+pub(crate) struct ParticleDataSweeper {
+    stop_sender: mpsc::Sender<()>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl ParticleDataSweeper {
+    pub(crate) fn start(particle_data: Arc<Mutex<ParticleData>>, interval: Duration) -> Self {
+        let (stop_sender, stop_receiver) = mpsc::channel();
+
+        let thread = std::thread::spawn(move || {
+            // both a stop message and a dropped sender stop the sweeping
+            while let Err(mpsc::RecvTimeoutError::Timeout) = stop_receiver.recv_timeout(interval) {
+                let stats = particle_data.lock().remove_expired(SystemTime::now());
+                if !stats.removed_particles.is_empty() {
+                    log::info!(
+                        "data of {} expired particles has been removed, {} bytes reclaimed",
+                        stats.removed_particles.len(),
+                        stats.reclaimed_bytes
+                    );
+                }
+                for (particle_id, e) in stats.failed_particles {
+                    log::warn!(
+                        "failed to remove data of expired particle {}: {}",
+                        particle_id,
+                        e
+                    );
+                }
+            }
+        });
+
+        Self {
+            stop_sender,
+            thread: Some(thread),
+        }
+    }
+}
+
+impl Drop for ParticleDataSweeper {
+    fn drop(&mut self) {
+        // the thread could have already finished only if it panicked
+        let _ = self.stop_sender.send(());
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn deadline_after(ttl: Duration) -> Option<SystemTime> {
+    // too big ttls mean that data never expires
+    SystemTime::now().checked_add(ttl)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InMemoryDataStore;
+
+    /// Fails to remove the particle with id "broken".
+    #[derive(Default)]
+    struct FailingDataStore {
+        store: InMemoryDataStore,
+    }
+
+    impl DataStore for FailingDataStore {
+        fn read(&self, particle_id: &str) -> Result<Option<Vec<u8>>> {
+            self.store.read(particle_id)
+        }
+
+        fn write(&mut self, particle_id: &str, data: &[u8]) -> Result<()> {
+            self.store.write(particle_id, data)
+        }
+
+        fn remove(&mut self, particle_id: &str) -> Result<u64> {
+            if particle_id == "broken" {
+                return Err(AquamarineVMError::DataStoreError(String::from(
+                    "can't remove",
+                )));
+            }
+            self.store.remove(particle_id)
+        }
+
+        fn list(&self) -> Result<Vec<String>> {
+            self.store.list()
+        }
+
+        fn write_deadline(&mut self, particle_id: &str, deadline: SystemTime) -> Result<()> {
+            self.store.write_deadline(particle_id, deadline)
+        }
+
+        fn read_deadlines(&self) -> Result<HashMap<String, SystemTime>> {
+            self.store.read_deadlines()
+        }
+    }
+
+    /// Does the same with particle data as a call of AquamarineVM.
+    fn call_with_ttl(
+        particle_data: &mut ParticleData,
+        particle_id: &str,
+        data: &[u8],
+        ttl: Duration,
+    ) {
+        particle_data
+            .record_deadline(particle_id, Some(ttl))
+            .unwrap_or_else(|e| panic!("can't record deadline: {:?}", e));
+        particle_data
+            .store
+            .write(particle_id, data)
+            .unwrap_or_else(|e| panic!("can't write particle data: {:?}", e));
+    }
+
+    fn particle_data(store: impl DataStore + 'static) -> ParticleData {
+        ParticleData::new(Box::new(store), None)
+            .unwrap_or_else(|e| panic!("can't create particle data: {:?}", e))
+    }
+
+    #[test]
+    fn cleanup_expired() {
+        let mut particle_data = particle_data(InMemoryDataStore::default());
+        let hour = Duration::from_secs(3600);
+
+        call_with_ttl(&mut particle_data, "short", b"data", Duration::from_secs(1));
+        call_with_ttl(&mut particle_data, "long", b"long data", hour);
+        // subsequent calls don't prolong the deadline
+        call_with_ttl(&mut particle_data, "short", b"new data", hour);
+
+        let short_deadline = particle_data
+            .deadline("short")
+            .expect("deadline should be recorded");
+        let stats = particle_data.remove_expired(short_deadline);
+        assert_eq!(stats.removed_particles, vec!["short"]);
+        assert_eq!(stats.reclaimed_bytes, b"new data".len() as u64);
+        assert!(stats.failed_particles.is_empty());
+
+        assert_eq!(particle_data.deadline("short"), None);
+        assert!(particle_data.deadline("long").is_some());
+        let particle_ids = particle_data
+            .store
+            .list()
+            .unwrap_or_else(|e| panic!("can't list particles: {:?}", e));
+        assert_eq!(particle_ids, vec!["long"]);
+    }
+
+    #[test]
+    fn remove_particle() {
+        let mut particle_data = particle_data(InMemoryDataStore::default());
+        call_with_ttl(
+            &mut particle_data,
+            "particle",
+            b"data",
+            Duration::from_secs(1),
+        );
+
+        let removed_size = particle_data
+            .remove("particle")
+            .unwrap_or_else(|e| panic!("can't remove particle: {:?}", e));
+        assert_eq!(removed_size, b"data".len() as u64);
+        assert_eq!(particle_data.deadline("particle"), None);
+
+        let deadlines = particle_data
+            .store
+            .read_deadlines()
+            .unwrap_or_else(|e| panic!("can't read deadlines: {:?}", e));
+        assert!(deadlines.is_empty());
+
+        let removed_size = particle_data
+            .remove("particle")
+            .unwrap_or_else(|e| panic!("can't remove particle: {:?}", e));
+        assert_eq!(removed_size, 0);
+    }
+
+    #[test]
+    fn failed_removals_are_reported() {
+        let mut particle_data = particle_data(FailingDataStore::default());
+        let ttl = Duration::from_secs(1);
+        call_with_ttl(&mut particle_data, "broken", b"broken data", ttl);
+        call_with_ttl(&mut particle_data, "particle", b"data", ttl);
+
+        let stats = particle_data.remove_expired(SystemTime::now() + ttl);
+        assert_eq!(stats.removed_particles, vec!["particle"]);
+        assert_eq!(stats.reclaimed_bytes, b"data".len() as u64);
+        assert_eq!(stats.failed_particles.len(), 1);
+        assert_eq!(stats.failed_particles[0].0, "broken");
+
+        // the particle is retried by the next cleanup
+        assert!(particle_data.deadline("broken").is_some());
+    }
+
+    #[test]
+    fn deadlines_are_restored() {
+        let deadline = SystemTime::now() + Duration::from_secs(3600);
+        let mut store = InMemoryDataStore::default();
+        for particle_id in ["old", "particle"].iter() {
+            store
+                .write(particle_id, b"data")
+                .unwrap_or_else(|e| panic!("can't write particle data: {:?}", e));
+        }
+        store
+            .write_deadline("particle", deadline)
+            .unwrap_or_else(|e| panic!("can't write deadline: {:?}", e));
+
+        let default_ttl = Duration::from_secs(60);
+        let particle_data = ParticleData::new(Box::new(store), Some(default_ttl))
+            .unwrap_or_else(|e| panic!("can't create particle data: {:?}", e));
+
+        assert_eq!(particle_data.deadline("particle"), Some(deadline));
+        let old_deadline = particle_data
+            .deadline("old")
+            .expect("particles without deadlines should get the default ttl");
+        assert!(old_deadline <= SystemTime::now() + default_ttl);
+
+        // the default deadline is saved, so it isn't prolonged by the next restart
+        let deadlines = particle_data
+            .store
+            .read_deadlines()
+            .unwrap_or_else(|e| panic!("can't read deadlines: {:?}", e));
+        assert_eq!(deadlines.get("old"), Some(&old_deadline));
+    }
+
+    #[test]
+    fn sweeper_removes_expired_data() {
+        let particle_data = Arc::new(Mutex::new(particle_data(InMemoryDataStore::default())));
+        call_with_ttl(
+            &mut particle_data.lock(),
+            "particle",
+            b"data",
+            Duration::from_secs(0),
+        );
+
+        let sweeper = ParticleDataSweeper::start(particle_data.clone(), Duration::from_millis(10));
+        let started = std::time::Instant::now();
+        while particle_data.lock().deadline("particle").is_some() {
+            assert!(
+                started.elapsed() < Duration::from_secs(5),
+                "expired data should be swept"
+            );
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        drop(sweeper);
+
+        let particle_ids = particle_data
+            .lock()
+            .store
+            .list()
+            .unwrap_or_else(|e| panic!("can't list particles: {:?}", e));
+        assert!(particle_ids.is_empty());
+    }
+}
@@ -0,0 +1,197 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use aquamarine_vm::AquamarineVMError;
+use aquamarine_vm::DataStore;
+use aquamarine_vm::FileSystemDataStore;
+use aquamarine_vm::InMemoryDataStore;
+
+use std::time::Duration;
+use std::time::SystemTime;
+
+fn check_data_store(data_store: &mut dyn DataStore) {
+    let read_data = |data_store: &dyn DataStore, particle_id: &str| {
+        data_store
+            .read(particle_id)
+            .unwrap_or_else(|e| panic!("can't read particle data: {:?}", e))
+    };
+
+    assert_eq!(read_data(data_store, "particle_1"), None);
+
+    data_store
+        .write("particle_1", b"data_1")
+        .unwrap_or_else(|e| panic!("can't write particle data: {:?}", e));
+    data_store
+        .write("particle_2", b"data_2")
+        .unwrap_or_else(|e| panic!("can't write particle data: {:?}", e));
+    data_store
+        .write("particle_1", b"new_data_1")
+        .unwrap_or_else(|e| panic!("can't write particle data: {:?}", e));
+
+    assert_eq!(
+        read_data(data_store, "particle_1"),
+        Some(b"new_data_1".to_vec())
+    );
+    let particle_ids = data_store
+        .list()
+        .unwrap_or_else(|e| panic!("can't list particles: {:?}", e));
+    assert_eq!(particle_ids, vec!["particle_1", "particle_2"]);
+
+    let removed_size = data_store
+        .remove("particle_1")
+        .unwrap_or_else(|e| panic!("can't remove particle data: {:?}", e));
+    assert_eq!(removed_size, b"new_data_1".len() as u64);
+    let removed_size = data_store
+        .remove("particle_1")
+        .unwrap_or_else(|e| panic!("removing a missing particle shouldn't fail: {:?}", e));
+    assert_eq!(removed_size, 0);
+
+    assert_eq!(read_data(data_store, "particle_1"), None);
+
+    let deadline = SystemTime::now() + Duration::from_secs(60);
+    data_store
+        .write_deadline("particle_2", deadline)
+        .unwrap_or_else(|e| panic!("can't write deadline: {:?}", e));
+    let deadlines = data_store
+        .read_deadlines()
+        .unwrap_or_else(|e| panic!("can't read deadlines: {:?}", e));
+    assert_eq!(deadlines.get("particle_2"), Some(&deadline));
+
+    let particle_ids = data_store
+        .list()
+        .unwrap_or_else(|e| panic!("can't list particles: {:?}", e));
+    assert_eq!(particle_ids, vec!["particle_2"]);
+}
+
+#[test]
+pub fn file_system_data_store() {
+    let data_dir = std::env::temp_dir().join("aquamarine_vm_file_system_data_store");
+    let _ = std::fs::remove_dir_all(&data_dir);
+
+    let mut data_store = FileSystemDataStore::new(data_dir)
+        .unwrap_or_else(|e| panic!("can't create data store: {:?}", e));
+    check_data_store(&mut data_store);
+}
+
+#[test]
+pub fn in_memory_data_store() {
+    let mut data_store = InMemoryDataStore::default();
+    check_data_store(&mut data_store);
+}
+
+#[test]
+pub fn file_system_data_store_leaves_no_temp_files() {
+    let data_dir = std::env::temp_dir().join("aquamarine_vm_file_system_data_store_temp_files");
+    let _ = std::fs::remove_dir_all(&data_dir);
+
+    let mut data_store = FileSystemDataStore::new(data_dir.clone())
+        .unwrap_or_else(|e| panic!("can't create data store: {:?}", e));
+    data_store
+        .write("particle", b"data")
+        .unwrap_or_else(|e| panic!("can't write particle data: {:?}", e));
+
+    // a temp file left by an interrupted write
+    std::fs::write(data_dir.join(".tmp").join("particle"), b"trunc")
+        .unwrap_or_else(|e| panic!("can't write a temp file: {:?}", e));
+
+    let particle_ids = data_store
+        .list()
+        .unwrap_or_else(|e| panic!("can't list particles: {:?}", e));
+    assert_eq!(particle_ids, vec!["particle"]);
+
+    data_store
+        .write("particle", b"new_data")
+        .unwrap_or_else(|e| panic!("can't write particle data: {:?}", e));
+    assert!(!data_dir.join(".tmp").join("particle").exists());
+    assert_eq!(
+        std::fs::read(data_dir.join("particle")).ok(),
+        Some(b"new_data".to_vec())
+    );
+}
+
+#[test]
+pub fn file_system_data_store_rejects_invalid_ids() {
+    let data_dir = std::env::temp_dir().join("aquamarine_vm_file_system_data_store_invalid_ids");
+    let _ = std::fs::remove_dir_all(&data_dir);
+
+    let mut data_store = FileSystemDataStore::new(data_dir.clone())
+        .unwrap_or_else(|e| panic!("can't create data store: {:?}", e));
+
+    for particle_id in &[
+        "",
+        ".tmp",
+        ".hidden",
+        "../escaped",
+        "dir/particle",
+        "dir\\particle",
+    ] {
+        let is_invalid_id = |result: Result<_, AquamarineVMError>| {
+            matches!(result, Err(AquamarineVMError::InvalidParticleId(_)))
+        };
+
+        assert!(is_invalid_id(
+            data_store.write(particle_id, b"data").map(|_| ())
+        ));
+        assert!(is_invalid_id(data_store.read(particle_id).map(|_| ())));
+        assert!(is_invalid_id(data_store.remove(particle_id).map(|_| ())));
+    }
+    assert!(!data_dir.with_file_name("escaped").exists());
+
+    // ids that only look like the temp dir are fine
+    data_store
+        .write("tmp", b"data")
+        .unwrap_or_else(|e| panic!("can't write particle data: {:?}", e));
+    let particle_ids = data_store
+        .list()
+        .unwrap_or_else(|e| panic!("can't list particles: {:?}", e));
+    assert_eq!(particle_ids, vec!["tmp"]);
+}
+
+#[test]
+pub fn file_system_data_store_persists_deadlines() {
+    let data_dir = std::env::temp_dir().join("aquamarine_vm_file_system_data_store_deadlines");
+    let _ = std::fs::remove_dir_all(&data_dir);
+
+    let deadline = SystemTime::now() + Duration::from_secs(60);
+    let mut data_store = FileSystemDataStore::new(data_dir.clone())
+        .unwrap_or_else(|e| panic!("can't create data store: {:?}", e));
+    data_store
+        .write("particle", b"data")
+        .unwrap_or_else(|e| panic!("can't write particle data: {:?}", e));
+    data_store
+        .write_deadline("particle", deadline)
+        .unwrap_or_else(|e| panic!("can't write deadline: {:?}", e));
+    drop(data_store);
+
+    let mut data_store = FileSystemDataStore::new(data_dir)
+        .unwrap_or_else(|e| panic!("can't create data store: {:?}", e));
+    let deadlines = data_store
+        .read_deadlines()
+        .unwrap_or_else(|e| panic!("can't read deadlines: {:?}", e));
+    assert_eq!(deadlines.get("particle"), Some(&deadline));
+    let particle_ids = data_store
+        .list()
+        .unwrap_or_else(|e| panic!("can't list particles: {:?}", e));
+    assert_eq!(particle_ids, vec!["particle"]);
+
+    data_store
+        .remove("particle")
+        .unwrap_or_else(|e| panic!("can't remove particle data: {:?}", e));
+    let deadlines = data_store
+        .read_deadlines()
+        .unwrap_or_else(|e| panic!("can't read deadlines: {:?}", e));
+    assert!(deadlines.is_empty());
+}
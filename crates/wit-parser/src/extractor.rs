@@ -32,3 +32,15 @@ pub fn module_interface(module_path: PathBuf) -> Result<ServiceInterface> {
 
     get_interface(&fce_interface)
 }
+
+/// Returns an interface of a module with IT types as they are, without converting
+/// them to a text view.
+pub fn module_raw_interface(module_path: PathBuf) -> Result<FCEModuleInterface> {
+    use fce_wit_interfaces::FCEWITInterfaces;
+
+    let wit_section_bytes = extract_wit_section_bytes(module_path)?;
+    let wit = extract_wit_with_fn(&wit_section_bytes)?;
+    let fce_interface = FCEWITInterfaces::new(wit);
+
+    get_raw_interface(&fce_interface)
+}
@@ -43,6 +43,13 @@ pub struct FCEModuleInterface {
 }
 
 pub fn get_interface(wit: &FCEWITInterfaces<'_>) -> Result<ServiceInterface> {
+    let fce_interface = get_raw_interface(wit)?;
+    let service_interface = into_service_interface(fce_interface);
+
+    Ok(service_interface)
+}
+
+pub fn get_raw_interface(wit: &FCEWITInterfaces<'_>) -> Result<FCEModuleInterface> {
     let function_signatures = get_exports(wit)?;
     let record_types = extract_record_types(wit);
 
@@ -51,9 +58,7 @@ pub fn get_interface(wit: &FCEWITInterfaces<'_>) -> Result<ServiceInterface> {
         function_signatures,
     };
 
-    let service_interface = into_service_interface(fce_interface);
-
-    Ok(service_interface)
+    Ok(fce_interface)
 }
 
 fn get_exports(wit: &FCEWITInterfaces<'_>) -> Result<Vec<FCEFunctionSignature>> {
@@ -39,5 +39,10 @@ pub use embedder::embed_text_wit;
 pub use extractor::extract_wit;
 pub use extractor::extract_text_wit;
 pub use extractor::module_interface;
+pub use extractor::module_raw_interface;
+pub use extractor::FCEModuleInterface;
+pub use extractor::FCEFunctionSignature;
+pub use extractor::RecordTypes;
+pub use extractor::ServiceInterface;
 
 pub(crate) type Result<T> = std::result::Result<T, WITParserError>;
@@ -0,0 +1,91 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::FCEError;
+use crate::IValue;
+
+use parking_lot::RwLock;
+
+use std::sync::Arc;
+
+/// Describes a call of an export function of some module.
+#[derive(Debug, Clone, Copy)]
+pub struct FCECallContext<'c> {
+    pub module_name: &'c str,
+    pub function_name: &'c str,
+    pub arguments: &'c [IValue],
+}
+
+/// Observes calls of export functions, both called from the host and from other modules.
+pub trait FCECallInterceptor: Send + Sync {
+    /// Invoked before the call, an error rejects the call with FCEError::CallRejected.
+    fn before_call(&self, context: &FCECallContext<'_>) -> std::result::Result<(), String>;
+
+    /// Invoked after the call (or its rejection) with its result,
+    /// only if before_call of this interceptor has been invoked.
+    fn after_call(
+        &self,
+        context: &FCECallContext<'_>,
+        result: std::result::Result<&[IValue], &FCEError>,
+    );
+}
+
+/// Interceptors shared between FCE and all its modules, so interceptors registered
+/// after a module has been loaded also see its calls.
+#[derive(Clone, Default)]
+pub(crate) struct Interceptors(Arc<RwLock<Vec<Arc<dyn FCECallInterceptor>>>>);
+
+impl Interceptors {
+    pub(crate) fn add(&self, interceptor: Arc<dyn FCECallInterceptor>) {
+        self.0.write().push(interceptor);
+    }
+
+    /// Runs the call surrounded by all registered interceptors.
+    pub(crate) fn intercept(
+        &self,
+        context: FCECallContext<'_>,
+        call: impl FnOnce() -> crate::Result<Vec<IValue>>,
+    ) -> crate::Result<Vec<IValue>> {
+        // interceptors are copied out to not hold the lock during the call,
+        // because nested calls to other modules will need it as well
+        let interceptors = self.0.read().clone();
+        if interceptors.is_empty() {
+            return call();
+        }
+
+        let mut invoked_count = 0;
+        let before_result = interceptors
+            .iter()
+            .try_for_each(|interceptor| {
+                invoked_count += 1;
+                interceptor.before_call(&context)
+            })
+            .map_err(|reason| FCEError::CallRejected {
+                module_name: context.module_name.to_string(),
+                function_name: context.function_name.to_string(),
+                reason,
+            });
+
+        let result = before_result.and_then(|_| call());
+
+        // in the reverse order to let interceptors be nested into each other
+        for interceptor in interceptors[..invoked_count].iter().rev() {
+            interceptor.after_call(&context, result.as_ref().map(Vec::as_slice));
+        }
+
+        result
+    }
+}
@@ -17,6 +17,7 @@
 use super::IValue;
 use super::IType;
 use crate::HostImportError;
+use crate::ModuleSignatureConfig;
 
 use wasmer_wasi::WasiVersion;
 use wasmer_runtime::ImportObject;
@@ -26,7 +27,7 @@ use std::path::PathBuf;
 use std::collections::HashMap;
 use std::collections::HashSet;
 
-pub type HostExportedFunc = Box<dyn Fn(&mut Ctx, Vec<IValue>) -> Option<IValue> + 'static>;
+pub type HostExportedFunc = Box<dyn Fn(&mut Ctx, Vec<IValue>) -> Option<IValue> + Send + 'static>;
 
 pub struct HostImportDescriptor {
     /// This closure will be invoked for corresponding import.
@@ -38,9 +39,10 @@ pub struct HostImportDescriptor {
     /// Types of output of the closure.
     pub output_type: Option<IType>,
 
-    /// If Some, this closure is called with error when errors is encountered while lifting.
-    /// If None, panic will occur.
-    pub error_handler: Option<Box<dyn Fn(&HostImportError) -> Option<IValue> + 'static>>,
+    /// If Some, this closure is called with error when errors is encountered while lifting
+    /// or when a call is aborted without executing the import.
+    /// If None, panic will occur on lifting errors and aborted calls return no value.
+    pub error_handler: Option<Box<dyn Fn(&HostImportError) -> Option<IValue> + Send + 'static>>,
 }
 
 pub struct FCEModuleConfig {
@@ -65,6 +67,55 @@ pub struct FCEModuleConfig {
 
     /// Mapping between paths.
     pub wasi_mapped_dirs: HashMap<String, PathBuf>,
+
+    /// If set, a module is loaded only if its signature is made by a trusted key.
+    pub signature_verification: Option<ModuleSignatureConfig>,
+
+    /// Wasm features the module is allowed to use.
+    pub wasm_features: WasmFeatures,
+
+    /// If set, the module is instrumented to trap instead of exhausting the native stack
+    /// when its stack height exceeds this limit. The height is measured in values
+    /// of locals and the operand stack of functions in the current call chain.
+    pub max_stack_height: Option<u32>,
+}
+
+/// Describes which Wasm features a module could use, a module using other ones
+/// is rejected on loading. Allowed SIMD, threads and bulk memory operations pass
+/// the validation, but they still aren't supported by the module parser.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WasmFeatures {
+    /// Allow float types and instructions, they could behave nondeterministically
+    /// on different platforms because of NaN representation.
+    pub floats: bool,
+
+    /// Maximum count of globals, including imported ones.
+    pub max_globals: Option<u32>,
+
+    /// Maximum count of tables, including imported ones.
+    pub max_tables: Option<u32>,
+
+    /// Allow SIMD instructions and the v128 type.
+    pub simd: bool,
+
+    /// Allow atomic instructions and shared memories.
+    pub threads: bool,
+
+    /// Allow bulk memory instructions and passive segments.
+    pub bulk_memory: bool,
+}
+
+impl Default for WasmFeatures {
+    fn default() -> Self {
+        Self {
+            floats: true,
+            max_globals: None,
+            max_tables: None,
+            simd: false,
+            threads: false,
+            bulk_memory: false,
+        }
+    }
 }
 
 impl Default for FCEModuleConfig {
@@ -79,6 +130,9 @@ impl Default for FCEModuleConfig {
             wasi_envs: HashMap::new(),
             wasi_preopened_files: HashSet::new(),
             wasi_mapped_dirs: HashMap::new(),
+            signature_verification: None,
+            wasm_features: WasmFeatures::default(),
+            max_stack_height: None,
         }
     }
 }
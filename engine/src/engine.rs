@@ -15,14 +15,16 @@
  */
 
 use super::*;
+use crate::call_interceptor::Interceptors;
 use crate::module::FCEModule;
 use crate::module::RecordTypes;
 
 use serde::Serialize;
 
+use std::borrow::Cow;
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
-use std::rc::Rc;
+use std::sync::Arc;
 
 /// Represent FCE module interface.
 #[derive(PartialEq, Eq, Debug, Clone, Serialize)]
@@ -31,16 +33,37 @@ pub struct FCEModuleInterface<'a> {
     pub function_signatures: Vec<FCEFunctionSignature>,
 }
 
+/// A module created by FCE, but not loaded into it.
+pub struct FCEPreparedModule {
+    name: String,
+    module: FCEModule,
+}
+
+impl FCEPreparedModule {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Return function signatures and record types exported by the module.
+    pub fn interface(&self) -> FCEModuleInterface<'_> {
+        FCE::get_module_interface(&self.module)
+    }
+}
+
 /// The base struct of the Fluence Compute Engine.
 pub struct FCE {
     // set of modules registered inside FCE
     modules: HashMap<String, FCEModule>,
+
+    // interceptors of all calls of module export functions
+    interceptors: Interceptors,
 }
 
 impl FCE {
     pub fn new() -> Self {
         Self {
             modules: HashMap::new(),
+            interceptors: Interceptors::default(),
         }
     }
 
@@ -62,6 +85,24 @@ impl FCE {
         )
     }
 
+    /// Runs a call answered without executing the module (e.g. from a cache) surrounded
+    /// by registered interceptors, so they see it like any other call of the function.
+    pub fn intercept_call(
+        &self,
+        module_name: &str,
+        func_name: &str,
+        arguments: &[IValue],
+        call: impl FnOnce() -> Result<Vec<IValue>>,
+    ) -> Result<Vec<IValue>> {
+        let context = FCECallContext {
+            module_name,
+            function_name: func_name,
+            arguments,
+        };
+
+        self.interceptors.intercept(context, call)
+    }
+
     /// Load a new module inside FCE.
     pub fn load_module<S: Into<String>>(
         &mut self,
@@ -69,25 +110,73 @@ impl FCE {
         wasm_bytes: &[u8],
         config: FCEModuleConfig,
     ) -> Result<()> {
-        self.load_module_(name.into(), wasm_bytes, config)
+        let module = self.prepare_module(name, wasm_bytes, config)?;
+
+        match self.modules.entry(module.name) {
+            Entry::Vacant(entry) => {
+                entry.insert(module.module);
+                Ok(())
+            }
+            Entry::Occupied(entry) => Err(FCEError::NonUniqueModuleName(entry.key().clone())),
+        }
     }
 
-    fn load_module_(
-        &mut self,
+    /// Create a module without loading it, so it could be checked before it replaces
+    /// a loaded module with the same name.
+    pub fn prepare_module<S: Into<String>>(
+        &self,
+        name: S,
+        wasm_bytes: &[u8],
+        config: FCEModuleConfig,
+    ) -> Result<FCEPreparedModule> {
+        self.prepare_module_(name.into(), wasm_bytes, config)
+    }
+
+    fn prepare_module_(
+        &self,
         name: String,
         wasm_bytes: &[u8],
         config: FCEModuleConfig,
-    ) -> Result<()> {
-        let _prepared_wasm_bytes = crate::misc::prepare_module(wasm_bytes, config.mem_pages_count)?;
+    ) -> Result<FCEPreparedModule> {
+        // modules are verified before any processing of their bytes
+        if let Some(signature_config) = &config.signature_verification {
+            crate::misc::verify_module_signature(wasm_bytes, signature_config)?;
+        }
 
-        let module = FCEModule::new(&wasm_bytes, config, &self.modules)?;
+        let _prepared_wasm_bytes =
+            crate::misc::prepare_module(wasm_bytes, config.mem_pages_count, &config.wasm_features)?;
 
-        match self.modules.entry(name) {
-            Entry::Vacant(entry) => {
-                entry.insert(module);
+        // only instrumented modules are compiled from modified bytes
+        let wasm_bytes = match config.max_stack_height {
+            Some(max_stack_height) => Cow::Owned(crate::misc::limit_stack_height(
+                wasm_bytes,
+                max_stack_height,
+            )?),
+            None => Cow::Borrowed(wasm_bytes),
+        };
+
+        let module = FCEModule::new(
+            &name,
+            &wasm_bytes,
+            config,
+            &self.modules,
+            self.interceptors.clone(),
+        )?;
+
+        Ok(FCEPreparedModule { name, module })
+    }
+
+    /// Replace a loaded module with a prepared module with the same name.
+    pub fn replace_module(&mut self, module: FCEPreparedModule) -> Result<()> {
+        match self.modules.get_mut(&module.name) {
+            Some(loaded_module) => {
+                *loaded_module = module.module;
                 Ok(())
             }
-            Entry::Occupied(entry) => Err(FCEError::NonUniqueModuleName(entry.key().clone())),
+            None => Err(FCEError::NoSuchModule(format!(
+                "trying to replace module with name {} that is not loaded",
+                module.name
+            ))),
         }
     }
 
@@ -105,6 +194,12 @@ impl FCE {
             })
     }
 
+    /// Register an interceptor of calls of all export functions, including calls made
+    /// by modules to each other.
+    pub fn add_interceptor(&mut self, interceptor: Arc<dyn FCECallInterceptor>) {
+        self.interceptors.add(interceptor);
+    }
+
     pub fn module_wasi_state<S: AsRef<str>>(
         &mut self,
         module_name: S,
@@ -143,12 +238,29 @@ impl FCE {
         &self,
         module_name: S,
         record_id: u64,
-    ) -> Option<&Rc<IRecordType>> {
+    ) -> Option<&Arc<IRecordType>> {
         self.modules
             .get(module_name.as_ref())
             .and_then(|module| module.export_record_type_by_id(record_id))
     }
 
+    /// Return stats of data passed through host imports of module with given name.
+    pub fn module_host_imports_stats<S: AsRef<str>>(
+        &self,
+        module_name: S,
+    ) -> Option<HostImportsStats> {
+        self.modules
+            .get(module_name.as_ref())
+            .map(|module| module.host_imports_stats())
+    }
+
+    /// Return the current memory size in Wasm pages of module with given name.
+    pub fn module_memory_pages<S: AsRef<str>>(&self, module_name: S) -> Option<u32> {
+        self.modules
+            .get(module_name.as_ref())
+            .map(|module| module.memory_pages())
+    }
+
     fn get_module_interface(module: &FCEModule) -> FCEModuleInterface<'_> {
         let record_types = module.export_record_types();
 
@@ -17,6 +17,7 @@
 use fce_wit_interfaces::FCEWITInterfacesError;
 use fce_wit_parser::WITParserError;
 use crate::HostImportError;
+use crate::misc::FeatureViolation;
 
 use wasmer_wit::errors::InstructionError;
 use wasmer_runtime::error::{
@@ -42,6 +43,12 @@ pub enum FCEError {
     /// Error that raises on the preparation step.
     PrepareError(String),
 
+    /// A module isn't signed by a trusted key, or its signature is malformed.
+    ModuleSignatureError(String),
+
+    /// A module uses Wasm features that aren't allowed by its config.
+    FeatureViolations(Vec<FeatureViolation>),
+
     /// Indicates that there is already a module with such name.
     NonUniqueModuleName(String),
 
@@ -59,6 +66,20 @@ pub enum FCEError {
 
     /// Incorrect WIT section.
     IncorrectWIT(String),
+
+    /// A call has trapped because the module has exceeded its stack height limit.
+    StackOverflow {
+        module_name: String,
+        function_name: String,
+        max_stack_height: u32,
+    },
+
+    /// A call has been rejected by one of the registered interceptors.
+    CallRejected {
+        module_name: String,
+        function_name: String,
+        reason: String,
+    },
 }
 
 impl Error for FCEError {}
@@ -73,6 +94,16 @@ impl std::fmt::Display for FCEError {
             FCEError::PrepareError(msg) => {
                 write!(f, "Prepare error: {}, probably module is malformed", msg)
             }
+            FCEError::ModuleSignatureError(msg) => {
+                write!(f, "Module signature verification failed: {}", msg)
+            }
+            FCEError::FeatureViolations(violations) => {
+                write!(f, "Module uses Wasm features that aren't allowed:")?;
+                for violation in violations {
+                    write!(f, "\n    {}", violation)?;
+                }
+                Ok(())
+            }
             FCEError::NonUniqueModuleName(module_name) => {
                 write!(f, r#"FCE already has module with name "{}""#, module_name)
             }
@@ -85,6 +116,24 @@ impl std::fmt::Display for FCEError {
             FCEError::HostImportError(host_import_error) => write!(f, "{}", host_import_error),
             FCEError::WITParseError(err) => write!(f, "{}", err),
             FCEError::IncorrectWIT(err_msg) => write!(f, "{}", err_msg),
+            FCEError::StackOverflow {
+                module_name,
+                function_name,
+                max_stack_height,
+            } => write!(
+                f,
+                "call of {}.{} has exceeded the stack height limit of {}",
+                module_name, function_name, max_stack_height
+            ),
+            FCEError::CallRejected {
+                module_name,
+                function_name,
+                reason,
+            } => write!(
+                f,
+                "call of {}.{} has been rejected: {}",
+                module_name, function_name, reason
+            ),
         }
     }
 }
@@ -38,6 +38,10 @@ pub enum HostImportError {
 
     /// An error related to not found record in module record types.
     RecordTypeNotFound(u64),
+
+    /// A host import call has been aborted without executing the import,
+    /// e.g. because a replay of recorded calls has diverged.
+    CallAborted(String),
 }
 
 impl Error for HostImportError {}
@@ -64,6 +68,9 @@ impl std::fmt::Display for HostImportError {
             HostImportError::RecordTypeNotFound(record_type_id) => {
                 write!(f, "Record with type id {} not found", record_type_id)
             }
+            HostImportError::CallAborted(reason) => {
+                write!(f, "Host import call has been aborted: {}", reason)
+            }
         }
     }
 }
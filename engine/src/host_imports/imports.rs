@@ -32,11 +32,12 @@ use wasmer_core::types::Value as WValue;
 use wasmer_core::types::FuncSig;
 
 use std::cell::RefCell;
-use std::rc::Rc;
+use std::sync::Arc;
 
 pub(crate) fn create_host_import_func(
     descriptor: HostImportDescriptor,
-    record_types: Rc<RecordTypes>,
+    record_types: Arc<RecordTypes>,
+    stats: Arc<HostImportsStatsCollector>,
 ) -> DynamicFunc<'static> {
     let allocate_func: AllocateFunc = Box::new(RefCell::new(None));
     let set_result_ptr_func: SetResultPtrFunc = Box::new(RefCell::new(None));
@@ -61,7 +62,10 @@ pub(crate) fn create_host_import_func(
         init_wasm_func_once!(allocate_func, ctx, i32, i32, ALLOCATE_FUNC_NAME, 2);
 
         let result = match wvalues_to_ivalues(ctx, inputs, &argument_types, &record_types) {
-            Ok(ivalues) => host_exported_func(ctx, ivalues),
+            Ok(ivalues) => {
+                stats.add_lifted(&ivalues);
+                host_exported_func(ctx, ivalues)
+            }
             Err(e) => {
                 log::error!("error occurred while lifting values in host import: {}", e);
                 error_handler
@@ -69,6 +73,9 @@ pub(crate) fn create_host_import_func(
                     .map_or_else(|| default_error_handler(&e), |h| h(&e))
             }
         };
+        if let Some(result) = &result {
+            stats.add_lowered(result);
+        }
         let wvalues = ivalue_to_wvalues(ctx, result, &allocate_func);
 
         // TODO: refactor this when multi-value is supported
@@ -101,10 +108,7 @@ pub(crate) fn create_host_import_func(
         }
     };
 
-    DynamicFunc::new(
-        std::sync::Arc::new(FuncSig::new(raw_args, raw_output)),
-        func,
-    )
+    DynamicFunc::new(Arc::new(FuncSig::new(raw_args, raw_output)), func)
 }
 
 fn default_error_handler(err: &HostImportError) -> Option<crate::IValue> {
@@ -18,13 +18,16 @@ mod errors;
 mod imports;
 mod ivalues_lifting;
 mod ivalues_lowering;
+mod stats;
 mod utils;
 
 use std::cell::RefCell;
 use wasmer_core::Func;
 
 pub use errors::HostImportError;
+pub use stats::HostImportsStats;
 pub(crate) use imports::create_host_import_func;
+pub(crate) use stats::HostImportsStatsCollector;
 
 pub(self) use wasmer_core::types::Value as WValue;
 pub(self) use wasmer_core::types::Type as WType;
@@ -0,0 +1,70 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::IValue;
+
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
+/// Amount of data passed through host imports of a module.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct HostImportsStats {
+    /// Bytes lifted from the Wasm memory as arguments of host imports.
+    pub lifted_bytes: u64,
+
+    /// Bytes lowered to the Wasm memory as results of host imports.
+    pub lowered_bytes: u64,
+}
+
+/// Collects stats of all host imports of one module.
+#[derive(Debug, Default)]
+pub(crate) struct HostImportsStatsCollector {
+    lifted_bytes: AtomicU64,
+    lowered_bytes: AtomicU64,
+}
+
+impl HostImportsStatsCollector {
+    pub(super) fn add_lifted(&self, ivalues: &[IValue]) {
+        let size = ivalues.iter().map(ivalue_size).sum::<u64>();
+        self.lifted_bytes.fetch_add(size, Ordering::Relaxed);
+    }
+
+    pub(super) fn add_lowered(&self, ivalue: &IValue) {
+        self.lowered_bytes
+            .fetch_add(ivalue_size(ivalue), Ordering::Relaxed);
+    }
+
+    pub(crate) fn stats(&self) -> HostImportsStats {
+        HostImportsStats {
+            lifted_bytes: self.lifted_bytes.load(Ordering::Relaxed),
+            lowered_bytes: self.lowered_bytes.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Returns size of the payload of the value, without taking into account pointers and sizes
+/// required to pass it to Wasm.
+fn ivalue_size(ivalue: &IValue) -> u64 {
+    match ivalue {
+        IValue::S8(_) | IValue::U8(_) => 1,
+        IValue::S16(_) | IValue::U16(_) => 2,
+        IValue::S32(_) | IValue::U32(_) | IValue::I32(_) | IValue::F32(_) => 4,
+        IValue::S64(_) | IValue::U64(_) | IValue::I64(_) | IValue::F64(_) => 8,
+        IValue::String(value) => value.len() as u64,
+        IValue::Array(values) => values.iter().map(ivalue_size).sum(),
+        IValue::Record(values) => values.iter().map(ivalue_size).sum(),
+    }
+}
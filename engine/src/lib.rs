@@ -27,6 +27,7 @@
     unreachable_patterns
 )]
 
+mod call_interceptor;
 mod config;
 mod engine;
 mod errors;
@@ -34,13 +35,25 @@ mod host_imports;
 mod misc;
 mod module;
 
+pub use call_interceptor::FCECallContext;
+pub use call_interceptor::FCECallInterceptor;
 pub use config::FCEModuleConfig;
+pub use config::WasmFeatures;
 pub use config::HostExportedFunc;
 pub use config::HostImportDescriptor;
 pub use engine::FCE;
 pub use engine::FCEModuleInterface;
+pub use engine::FCEPreparedModule;
 pub use errors::FCEError;
+pub use misc::module_signature;
+pub use misc::FeatureViolation;
+pub use misc::sign_module;
+pub use misc::signing_public_key;
+pub use misc::verify_module_signature;
+pub use misc::ModuleSignatureConfig;
+pub use misc::SIGNATURE_SECTION_NAME;
 pub use host_imports::HostImportError;
+pub use host_imports::HostImportsStats;
 pub use module::IValue;
 pub use module::IRecordType;
 pub use module::IFunctionArg;
@@ -15,5 +15,16 @@
  */
 
 mod prepare;
+mod signature;
+mod wasm_proposals;
 
 pub(crate) use prepare::prepare_module;
+pub(crate) use prepare::limit_stack_height;
+pub(crate) use prepare::STACK_HEIGHT_GLOBAL_NAME;
+pub use prepare::FeatureViolation;
+pub use signature::module_signature;
+pub use signature::sign_module;
+pub use signature::signing_public_key;
+pub use signature::verify_module_signature;
+pub use signature::ModuleSignatureConfig;
+pub use signature::SIGNATURE_SECTION_NAME;
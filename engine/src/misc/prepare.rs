@@ -18,13 +18,37 @@
 // https://github.com/paritytech/substrate/blob/master/srml/contracts/src/wasm/prepare.rs
 // https://github.com/nearprotocol/nearcore/blob/master/runtime/near-vm-runner/src/prepare.rs
 
+use super::wasm_proposals::find_proposal_usages;
+use super::wasm_proposals::ProposalUsage;
+use crate::FCEError;
 use crate::Result;
+use crate::WasmFeatures;
 
 use parity_wasm::{
     builder, elements,
-    elements::{MemorySection, MemoryType},
+    elements::{
+        BlockType, ExportEntry, External, ImportCountType, Instruction, Internal, MemorySection,
+        MemoryType, Type, ValueType,
+    },
 };
 
+use std::collections::HashSet;
+
+/// Name under which the stack height counter is exported from an instrumented module.
+pub(crate) const STACK_HEIGHT_GLOBAL_NAME: &str = "__fce_stack_height";
+
+/// A usage of a Wasm feature that isn't allowed by the module config.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeatureViolation {
+    /// A function with the violation, None for module-level items like globals and tables.
+    pub function: Option<String>,
+
+    /// An offending instruction, None for violations in types, locals and module-level items.
+    pub instruction: Option<String>,
+
+    pub reason: String,
+}
+
 struct ModuleBootstrapper {
     module: elements::Module,
 }
@@ -66,15 +90,398 @@ impl<'a> ModuleBootstrapper {
         }
     }
 
+    /// Checks the module against features, violations found before parsing
+    /// are reported along with the found ones.
+    fn validate_features(
+        self,
+        features: &WasmFeatures,
+        mut violations: Vec<FeatureViolation>,
+    ) -> Result<Self> {
+        if let Some(max_globals) = features.max_globals {
+            let globals_count = self.module.import_count(ImportCountType::Global)
+                + self
+                    .module
+                    .global_section()
+                    .map_or(0, |s| s.entries().len());
+            if globals_count > max_globals as usize {
+                violations.push(FeatureViolation::module_level(format!(
+                    "module has {} globals, but at most {} are allowed",
+                    globals_count, max_globals
+                )));
+            }
+        }
+
+        if let Some(max_tables) = features.max_tables {
+            let tables_count = self.module.import_count(ImportCountType::Table)
+                + self.module.table_section().map_or(0, |s| s.entries().len());
+            if tables_count > max_tables as usize {
+                violations.push(FeatureViolation::module_level(format!(
+                    "module has {} tables, but at most {} are allowed",
+                    tables_count, max_tables
+                )));
+            }
+        }
+
+        if !features.floats {
+            self.collect_float_violations(&mut violations);
+        }
+
+        if violations.is_empty() {
+            Ok(self)
+        } else {
+            Err(FCEError::FeatureViolations(violations))
+        }
+    }
+
+    fn collect_float_violations(&self, violations: &mut Vec<FeatureViolation>) {
+        const FLOATS_REASON: &str = "floats aren't allowed";
+
+        let globals = self
+            .module
+            .global_section()
+            .map_or(&[][..], |s| s.entries());
+        for (global_id, global) in globals.iter().enumerate() {
+            if is_float(global.global_type().content_type()) {
+                violations.push(FeatureViolation::module_level(format!(
+                    "global #{} has a float type, {}",
+                    global_id, FLOATS_REASON
+                )));
+            }
+        }
+
+        let types = self.module.type_section().map_or(&[][..], |s| s.types());
+        let type_has_floats = |type_ref: u32| match types.get(type_ref as usize) {
+            Some(Type::Function(function_type)) => {
+                function_type.params().iter().any(|t| is_float(*t))
+                    || function_type.return_type().map_or(false, is_float)
+            }
+            None => false,
+        };
+        // float types that are reported along with functions using them
+        let mut reported_types = HashSet::new();
+
+        let imports = self
+            .module
+            .import_section()
+            .map_or(&[][..], |s| s.entries());
+        let mut imported_function_id = 0;
+        for import in imports {
+            match import.external() {
+                External::Function(type_ref) => {
+                    if type_has_floats(*type_ref) {
+                        reported_types.insert(*type_ref);
+                        violations.push(FeatureViolation {
+                            function: Some(format!(
+                                "{}.{} (#{})",
+                                import.module(),
+                                import.field(),
+                                imported_function_id
+                            )),
+                            instruction: None,
+                            reason: format!(
+                                "signature of the imported function has float types, {}",
+                                FLOATS_REASON
+                            ),
+                        });
+                    }
+                    imported_function_id += 1;
+                }
+                External::Global(global_type) if is_float(global_type.content_type()) => {
+                    violations.push(FeatureViolation::module_level(format!(
+                        "imported global {}.{} has a float type, {}",
+                        import.module(),
+                        import.field(),
+                        FLOATS_REASON
+                    )));
+                }
+                _ => {}
+            }
+        }
+
+        let functions = self
+            .module
+            .function_section()
+            .map_or(&[][..], |s| s.entries());
+        let bodies = self.module.code_section().map_or(&[][..], |s| s.bodies());
+        // functions defined in the module are indexed after imported ones
+        let imported_functions_count = self.module.import_count(ImportCountType::Function);
+
+        for (local_id, (function, body)) in functions.iter().zip(bodies.iter()).enumerate() {
+            let function_name = self.function_name((imported_functions_count + local_id) as u32);
+            let violation = |instruction: Option<String>, reason: String| FeatureViolation {
+                function: Some(function_name.clone()),
+                instruction,
+                reason,
+            };
+
+            if type_has_floats(function.type_ref()) {
+                reported_types.insert(function.type_ref());
+                violations.push(violation(
+                    None,
+                    format!("signature has float types, {}", FLOATS_REASON),
+                ));
+            }
+
+            if body
+                .locals()
+                .iter()
+                .any(|local| is_float(local.value_type()))
+            {
+                violations.push(violation(
+                    None,
+                    format!("locals have float types, {}", FLOATS_REASON),
+                ));
+            }
+
+            for instruction in body.code().elements() {
+                let uses_floats = match instruction {
+                    Instruction::CallIndirect(type_ref, _) if type_has_floats(*type_ref) => {
+                        reported_types.insert(*type_ref);
+                        true
+                    }
+                    Instruction::Block(BlockType::Value(value_type))
+                    | Instruction::Loop(BlockType::Value(value_type))
+                    | Instruction::If(BlockType::Value(value_type)) => is_float(*value_type),
+                    instruction => is_float_instruction(instruction),
+                };
+                if uses_floats {
+                    violations.push(violation(
+                        Some(instruction.to_string()),
+                        FLOATS_REASON.to_string(),
+                    ));
+                }
+            }
+        }
+
+        // types could be used only by indirect calls of other modules through tables
+        for type_id in 0..types.len() as u32 {
+            if type_has_floats(type_id) && !reported_types.contains(&type_id) {
+                violations.push(FeatureViolation::module_level(format!(
+                    "type #{} has float types, {}",
+                    type_id, FLOATS_REASON
+                )));
+            }
+        }
+    }
+
+    /// Returns an export name of the function along with its index if it's exported.
+    fn function_name(&self, function_id: u32) -> String {
+        let exports = self
+            .module
+            .export_section()
+            .map_or(&[][..], |s| s.entries());
+        let export_name = exports
+            .iter()
+            .find(|export| export.internal() == &Internal::Function(function_id))
+            .map(|export| export.field());
+
+        display_function_name(export_name, function_id)
+    }
+
+    fn inject_stack_limiter(self, max_stack_height: u32) -> Result<Self> {
+        // the limiter rebuilds the module dropping custom sections, including the IT one
+        let custom_sections = self.module.custom_sections().cloned().collect::<Vec<_>>();
+
+        let mut module = pwasm_utils::stack_height::inject_limiter(self.module, max_stack_height)
+            .map_err(|e| {
+            FCEError::PrepareError(format!("can't inject stack height limiter: {:?}", e))
+        })?;
+
+        for custom_section in custom_sections {
+            let name = custom_section.name().to_string();
+            let payload = custom_section.payload().to_vec();
+            module.set_custom_section(name, payload);
+        }
+
+        // the counter is added as the last global, it's exported to detect overflows after traps
+        let stack_height_global_id = module.import_count(ImportCountType::Global)
+            + module.global_section().map_or(0, |s| s.entries().len())
+            - 1;
+        let export_entry = ExportEntry::new(
+            STACK_HEIGHT_GLOBAL_NAME.to_string(),
+            Internal::Global(stack_height_global_id as u32),
+        );
+        match module.export_section_mut() {
+            Some(export_section) => export_section.entries_mut().push(export_entry),
+            None => {
+                return Err(FCEError::PrepareError(String::from(
+                    "module without exports can't be instrumented",
+                )))
+            }
+        }
+
+        Ok(Self { module })
+    }
+
     fn into_wasm(self) -> Result<Vec<u8>> {
         elements::serialize(self.module).map_err(Into::into)
     }
 }
 
 /// Prepares a Wasm module:
+///   - check that it uses only allowed features
 ///   - set memory page count
-pub(crate) fn prepare_module(module: &[u8], mem_pages_count: u32) -> Result<Vec<u8>> {
-    ModuleBootstrapper::init(module)?
+pub(crate) fn prepare_module(
+    module: &[u8],
+    mem_pages_count: u32,
+    features: &WasmFeatures,
+) -> Result<Vec<u8>> {
+    // the module parser can't read most modules with these proposals, so they're checked first
+    let (allowed_usages, violations): (Vec<_>, Vec<_>) = find_proposal_usages(module)
+        .into_iter()
+        .partition(|usage| usage.proposal.is_allowed(features));
+    let violations = violations
+        .into_iter()
+        .map(|usage| usage.violation)
+        .collect::<Vec<_>>();
+
+    let bootstrapper = match ModuleBootstrapper::init(module) {
+        Ok(bootstrapper) => bootstrapper,
+        // other violations can't be found without parsing, so only these ones are reported
+        Err(_) if !violations.is_empty() => return Err(FCEError::FeatureViolations(violations)),
+        Err(e) => return Err(unsupported_proposals_error(&allowed_usages, e)),
+    };
+
+    bootstrapper
+        .validate_features(features, violations)?
         .set_mem_pages_count(mem_pages_count)
         .into_wasm()
 }
+
+/// Explains a parsing error of a module by proposals allowed by the config, if it uses any.
+fn unsupported_proposals_error(allowed_usages: &[ProposalUsage], error: FCEError) -> FCEError {
+    let mut allowed_proposals = allowed_usages
+        .iter()
+        .map(|usage| usage.proposal.to_string())
+        .collect::<Vec<_>>();
+    allowed_proposals.sort();
+    allowed_proposals.dedup();
+    if allowed_proposals.is_empty() {
+        return error;
+    }
+
+    FCEError::PrepareError(format!(
+        "module uses {} allowed by the config, but the module parser doesn't support it: {}",
+        allowed_proposals.join(", "),
+        error
+    ))
+}
+
+/// Instruments a Wasm module to trap when its stack height exceeds the limit.
+pub(crate) fn limit_stack_height(module: &[u8], max_stack_height: u32) -> Result<Vec<u8>> {
+    ModuleBootstrapper::init(module)?
+        .inject_stack_limiter(max_stack_height)?
+        .into_wasm()
+}
+
+/// Returns a function name used in violations: its export name along with its index.
+pub(super) fn display_function_name(export_name: Option<&str>, function_id: u32) -> String {
+    match export_name {
+        Some(export_name) => format!("{} (#{})", export_name, function_id),
+        None => format!("#{}", function_id),
+    }
+}
+
+impl FeatureViolation {
+    pub(crate) fn module_level(reason: String) -> Self {
+        Self {
+            function: None,
+            instruction: None,
+            reason,
+        }
+    }
+}
+
+impl std::fmt::Display for FeatureViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.function, &self.instruction) {
+            (Some(function), Some(instruction)) => write!(
+                f,
+                "function {}, instruction {}: {}",
+                function, instruction, self.reason
+            ),
+            (Some(function), None) => write!(f, "function {}: {}", function, self.reason),
+            _ => write!(f, "{}", self.reason),
+        }
+    }
+}
+
+fn is_float(value_type: ValueType) -> bool {
+    matches!(value_type, ValueType::F32 | ValueType::F64)
+}
+
+fn is_float_instruction(instruction: &Instruction) -> bool {
+    use Instruction::*;
+
+    matches!(
+        instruction,
+        F32Load(..)
+            | F64Load(..)
+            | F32Store(..)
+            | F64Store(..)
+            | F32Const(_)
+            | F64Const(_)
+            | F32Eq
+            | F32Ne
+            | F32Lt
+            | F32Gt
+            | F32Le
+            | F32Ge
+            | F64Eq
+            | F64Ne
+            | F64Lt
+            | F64Gt
+            | F64Le
+            | F64Ge
+            | F32Abs
+            | F32Neg
+            | F32Ceil
+            | F32Floor
+            | F32Trunc
+            | F32Nearest
+            | F32Sqrt
+            | F32Add
+            | F32Sub
+            | F32Mul
+            | F32Div
+            | F32Min
+            | F32Max
+            | F32Copysign
+            | F64Abs
+            | F64Neg
+            | F64Ceil
+            | F64Floor
+            | F64Trunc
+            | F64Nearest
+            | F64Sqrt
+            | F64Add
+            | F64Sub
+            | F64Mul
+            | F64Div
+            | F64Min
+            | F64Max
+            | F64Copysign
+            | I32TruncSF32
+            | I32TruncUF32
+            | I32TruncSF64
+            | I32TruncUF64
+            | I64TruncSF32
+            | I64TruncUF32
+            | I64TruncSF64
+            | I64TruncUF64
+            | F32ConvertSI32
+            | F32ConvertUI32
+            | F32ConvertSI64
+            | F32ConvertUI64
+            | F32DemoteF64
+            | F64ConvertSI32
+            | F64ConvertUI32
+            | F64ConvertSI64
+            | F64ConvertUI64
+            | F64PromoteF32
+            | I32ReinterpretF32
+            | I64ReinterpretF64
+            | F32ReinterpretI32
+            | F64ReinterpretI64
+    )
+}
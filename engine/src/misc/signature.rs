@@ -0,0 +1,133 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::FCEError;
+use crate::Result;
+
+use ed25519_dalek::Keypair;
+use ed25519_dalek::PublicKey;
+use ed25519_dalek::SecretKey;
+use ed25519_dalek::Signature;
+use ed25519_dalek::Signer;
+use ed25519_dalek::Verifier;
+
+use std::convert::TryFrom;
+
+/// Name of a custom section with an embedded signature of a module.
+pub const SIGNATURE_SECTION_NAME: &str = "fce-signature";
+
+const SIGNATURE_LEN: usize = 64;
+
+/// Describes how a module signature is checked before compilation.
+#[derive(Debug, Clone, Default)]
+pub struct ModuleSignatureConfig {
+    /// Ed25519 public keys, a module is loaded only if it's signed by one of them.
+    pub trusted_keys: Vec<[u8; 32]>,
+
+    /// A signature of the whole module, if it isn't set, the embedded one is checked.
+    pub detached_signature: Option<Vec<u8>>,
+}
+
+/// Returns a detached ed25519 signature of the module made by the given secret key.
+pub fn module_signature(wasm: &[u8], secret_key: &[u8]) -> Result<Vec<u8>> {
+    let keypair = keypair(secret_key)?;
+    Ok(keypair.sign(wasm).to_bytes().to_vec())
+}
+
+/// Signs the module and embeds the signature to it as the last custom section,
+/// the signature covers all bytes of the module before this section.
+/// A previously embedded signature is replaced.
+pub fn sign_module(wasm: &[u8], secret_key: &[u8]) -> Result<Vec<u8>> {
+    let unsigned_wasm = match split_embedded_signature(wasm) {
+        Some((unsigned_wasm, _)) => unsigned_wasm,
+        None => wasm,
+    };
+
+    let signature = module_signature(unsigned_wasm, secret_key)?;
+
+    let mut signed_wasm = unsigned_wasm.to_vec();
+    signed_wasm.extend(signature_section_header());
+    signed_wasm.extend(signature);
+
+    Ok(signed_wasm)
+}
+
+/// Returns an ed25519 public key corresponding to the given secret key.
+pub fn signing_public_key(secret_key: &[u8]) -> Result<[u8; 32]> {
+    Ok(keypair(secret_key)?.public.to_bytes())
+}
+
+/// Checks that the module is signed by one of the trusted keys.
+pub fn verify_module_signature(wasm: &[u8], config: &ModuleSignatureConfig) -> Result<()> {
+    let (message, signature) = match &config.detached_signature {
+        Some(signature) => (wasm, signature.as_slice()),
+        None => split_embedded_signature(wasm).ok_or_else(|| {
+            FCEError::ModuleSignatureError(String::from("module doesn't have a signature"))
+        })?,
+    };
+
+    let signature = Signature::try_from(signature).map_err(|e| {
+        FCEError::ModuleSignatureError(format!("module signature is malformed: {}", e))
+    })?;
+
+    let is_trusted = config.trusted_keys.iter().any(|key| {
+        PublicKey::from_bytes(key).map_or(false, |key| key.verify(message, &signature).is_ok())
+    });
+    if !is_trusted {
+        return Err(FCEError::ModuleSignatureError(String::from(
+            "module isn't signed by any of the trusted keys",
+        )));
+    }
+
+    Ok(())
+}
+
+/// Returns the module without the embedded signature section and the signature itself.
+fn split_embedded_signature(wasm: &[u8]) -> Option<(&[u8], &[u8])> {
+    let header = signature_section_header();
+    let section_len = header.len() + SIGNATURE_LEN;
+    if wasm.len() < section_len {
+        return None;
+    }
+
+    let (unsigned_wasm, section) = wasm.split_at(wasm.len() - section_len);
+    let (section_header, signature) = section.split_at(header.len());
+    if section_header != header.as_slice() {
+        return None;
+    }
+
+    Some((unsigned_wasm, signature))
+}
+
+fn signature_section_header() -> Vec<u8> {
+    let name = SIGNATURE_SECTION_NAME.as_bytes();
+    let payload_len = 1 + name.len() + SIGNATURE_LEN;
+
+    // custom section id, its size and the name length are less than 128,
+    // so each of them is encoded to a single LEB128 byte
+    let mut header = vec![0u8, payload_len as u8, name.len() as u8];
+    header.extend_from_slice(name);
+
+    header
+}
+
+fn keypair(secret_key: &[u8]) -> Result<Keypair> {
+    let secret = SecretKey::from_bytes(secret_key)
+        .map_err(|e| FCEError::ModuleSignatureError(format!("secret key is malformed: {}", e)))?;
+    let public = PublicKey::from(&secret);
+
+    Ok(Keypair { secret, public })
+}
@@ -0,0 +1,499 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::prepare::display_function_name;
+use super::FeatureViolation;
+use crate::WasmFeatures;
+
+use wasmparser::DataKind;
+use wasmparser::ElementKind;
+use wasmparser::ExternalKind;
+use wasmparser::ImportSectionEntryType;
+use wasmparser::ModuleReader;
+use wasmparser::Operator;
+use wasmparser::SectionCode;
+use wasmparser::Type;
+
+use std::collections::HashMap;
+
+/// Wasm proposals that the module parser doesn't support, so they're found
+/// by a separate pass before parsing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum WasmProposal {
+    Simd,
+    Threads,
+    BulkMemory,
+}
+
+/// A usage of a proposal found in a module.
+pub(crate) struct ProposalUsage {
+    pub(crate) proposal: WasmProposal,
+    pub(crate) violation: FeatureViolation,
+}
+
+impl WasmProposal {
+    pub(crate) fn is_allowed(self, features: &WasmFeatures) -> bool {
+        match self {
+            WasmProposal::Simd => features.simd,
+            WasmProposal::Threads => features.threads,
+            WasmProposal::BulkMemory => features.bulk_memory,
+        }
+    }
+
+    fn reason(self) -> String {
+        format!("{} isn't allowed", self)
+    }
+}
+
+/// Returns usages of SIMD, threads and bulk memory proposals in a module. A module that
+/// can't be read is reported later by the module parser, so here it's scanned only
+/// up to the first error.
+pub(crate) fn find_proposal_usages(module: &[u8]) -> Vec<ProposalUsage> {
+    let mut usages = vec![];
+    let _ = collect_proposal_usages(module, &mut usages);
+
+    usages
+}
+
+fn collect_proposal_usages(
+    module: &[u8],
+    usages: &mut Vec<ProposalUsage>,
+) -> wasmparser::Result<()> {
+    use WasmProposal::*;
+
+    let mut module_level = |proposal: WasmProposal, item: String| {
+        usages.push(ProposalUsage {
+            proposal,
+            violation: FeatureViolation::module_level(format!("{}, {}", item, proposal.reason())),
+        })
+    };
+
+    let mut reader = ModuleReader::new(module)?;
+    let mut imported_functions_count = 0;
+    let mut export_names = HashMap::new();
+    let mut function_violations = vec![];
+
+    while !reader.eof() {
+        let section = reader.read()?;
+        match section.code {
+            SectionCode::Type => {
+                let types = section.get_type_section_reader()?;
+                for (type_id, func_type) in types.into_iter().enumerate() {
+                    let func_type = func_type?;
+                    let mut value_types = func_type.params.iter().chain(func_type.returns.iter());
+                    if value_types.any(|t| *t == Type::V128) {
+                        module_level(Simd, format!("type #{} has v128 types", type_id));
+                    }
+                }
+            }
+            SectionCode::Import => {
+                for import in section.get_import_section_reader()? {
+                    let import = import?;
+                    match import.ty {
+                        ImportSectionEntryType::Function(_) => imported_functions_count += 1,
+                        ImportSectionEntryType::Memory(memory) if memory.shared => module_level(
+                            Threads,
+                            format!(
+                                "imported memory {}.{} is shared",
+                                import.module, import.field
+                            ),
+                        ),
+                        ImportSectionEntryType::Global(global)
+                            if global.content_type == Type::V128 =>
+                        {
+                            module_level(
+                                Simd,
+                                format!(
+                                    "imported global {}.{} has the v128 type",
+                                    import.module, import.field
+                                ),
+                            )
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            SectionCode::Memory => {
+                let memories = section.get_memory_section_reader()?;
+                for (memory_id, memory) in memories.into_iter().enumerate() {
+                    if memory?.shared {
+                        module_level(Threads, format!("memory #{} is shared", memory_id));
+                    }
+                }
+            }
+            SectionCode::Global => {
+                let globals = section.get_global_section_reader()?;
+                for (global_id, global) in globals.into_iter().enumerate() {
+                    if global?.ty.content_type == Type::V128 {
+                        module_level(Simd, format!("global #{} has the v128 type", global_id));
+                    }
+                }
+            }
+            SectionCode::Export => {
+                for export in section.get_export_section_reader()? {
+                    let export = export?;
+                    if let ExternalKind::Function = export.kind {
+                        export_names.insert(export.index, export.field.to_string());
+                    }
+                }
+            }
+            SectionCode::Element => {
+                let elements = section.get_element_section_reader()?;
+                for (element_id, element) in elements.into_iter().enumerate() {
+                    if let ElementKind::Passive = element?.kind {
+                        module_level(
+                            BulkMemory,
+                            format!("element segment #{} is passive", element_id),
+                        );
+                    }
+                }
+            }
+            SectionCode::Data => {
+                let data_segments = section.get_data_section_reader()?;
+                for (data_id, data) in data_segments.into_iter().enumerate() {
+                    if let DataKind::Passive = data?.kind {
+                        module_level(BulkMemory, format!("data segment #{} is passive", data_id));
+                    }
+                }
+            }
+            SectionCode::DataCount => {
+                module_level(BulkMemory, String::from("module has a data count section"))
+            }
+            SectionCode::Code => {
+                let bodies = section.get_code_section_reader()?;
+                for (local_id, body) in bodies.into_iter().enumerate() {
+                    let body = body?;
+                    let function_id = imported_functions_count + local_id as u32;
+                    let function_name = display_function_name(
+                        export_names.get(&function_id).map(String::as_str),
+                        function_id,
+                    );
+                    let mut violation = |proposal: WasmProposal, instruction, reason| {
+                        function_violations.push(ProposalUsage {
+                            proposal,
+                            violation: FeatureViolation {
+                                function: Some(function_name.clone()),
+                                instruction,
+                                reason,
+                            },
+                        })
+                    };
+
+                    for local in body.get_locals_reader()? {
+                        if local?.1 == Type::V128 {
+                            violation(
+                                Simd,
+                                None,
+                                format!("locals have v128 types, {}", Simd.reason()),
+                            );
+                            break;
+                        }
+                    }
+
+                    let mut operators = body.get_operators_reader()?;
+                    while !operators.eof() {
+                        let operator = operators.read()?;
+                        if let Some(proposal) = operator_proposal(&operator) {
+                            violation(proposal, Some(operator_name(&operator)), proposal.reason());
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    usages.append(&mut function_violations);
+    Ok(())
+}
+
+/// Returns the proposal an operator belongs to, if it's one of the checked proposals.
+fn operator_proposal(operator: &Operator<'_>) -> Option<WasmProposal> {
+    use Operator::*;
+
+    match operator {
+        MemoryInit { .. }
+        | DataDrop { .. }
+        | MemoryCopy
+        | MemoryFill
+        | TableInit { .. }
+        | ElemDrop { .. }
+        | TableCopy { .. } => Some(WasmProposal::BulkMemory),
+        AtomicNotify { .. }
+        | I32AtomicWait { .. }
+        | I64AtomicWait { .. }
+        | AtomicFence { .. }
+        | I32AtomicLoad { .. }
+        | I64AtomicLoad { .. }
+        | I32AtomicLoad8U { .. }
+        | I32AtomicLoad16U { .. }
+        | I64AtomicLoad8U { .. }
+        | I64AtomicLoad16U { .. }
+        | I64AtomicLoad32U { .. }
+        | I32AtomicStore { .. }
+        | I64AtomicStore { .. }
+        | I32AtomicStore8 { .. }
+        | I32AtomicStore16 { .. }
+        | I64AtomicStore8 { .. }
+        | I64AtomicStore16 { .. }
+        | I64AtomicStore32 { .. }
+        | I32AtomicRmwAdd { .. }
+        | I64AtomicRmwAdd { .. }
+        | I32AtomicRmw8AddU { .. }
+        | I32AtomicRmw16AddU { .. }
+        | I64AtomicRmw8AddU { .. }
+        | I64AtomicRmw16AddU { .. }
+        | I64AtomicRmw32AddU { .. }
+        | I32AtomicRmwSub { .. }
+        | I64AtomicRmwSub { .. }
+        | I32AtomicRmw8SubU { .. }
+        | I32AtomicRmw16SubU { .. }
+        | I64AtomicRmw8SubU { .. }
+        | I64AtomicRmw16SubU { .. }
+        | I64AtomicRmw32SubU { .. }
+        | I32AtomicRmwAnd { .. }
+        | I64AtomicRmwAnd { .. }
+        | I32AtomicRmw8AndU { .. }
+        | I32AtomicRmw16AndU { .. }
+        | I64AtomicRmw8AndU { .. }
+        | I64AtomicRmw16AndU { .. }
+        | I64AtomicRmw32AndU { .. }
+        | I32AtomicRmwOr { .. }
+        | I64AtomicRmwOr { .. }
+        | I32AtomicRmw8OrU { .. }
+        | I32AtomicRmw16OrU { .. }
+        | I64AtomicRmw8OrU { .. }
+        | I64AtomicRmw16OrU { .. }
+        | I64AtomicRmw32OrU { .. }
+        | I32AtomicRmwXor { .. }
+        | I64AtomicRmwXor { .. }
+        | I32AtomicRmw8XorU { .. }
+        | I32AtomicRmw16XorU { .. }
+        | I64AtomicRmw8XorU { .. }
+        | I64AtomicRmw16XorU { .. }
+        | I64AtomicRmw32XorU { .. }
+        | I32AtomicRmwXchg { .. }
+        | I64AtomicRmwXchg { .. }
+        | I32AtomicRmw8XchgU { .. }
+        | I32AtomicRmw16XchgU { .. }
+        | I64AtomicRmw8XchgU { .. }
+        | I64AtomicRmw16XchgU { .. }
+        | I64AtomicRmw32XchgU { .. }
+        | I32AtomicRmwCmpxchg { .. }
+        | I64AtomicRmwCmpxchg { .. }
+        | I32AtomicRmw8CmpxchgU { .. }
+        | I32AtomicRmw16CmpxchgU { .. }
+        | I64AtomicRmw8CmpxchgU { .. }
+        | I64AtomicRmw16CmpxchgU { .. }
+        | I64AtomicRmw32CmpxchgU { .. } => Some(WasmProposal::Threads),
+        V128Load { .. }
+        | V128Store { .. }
+        | V128Const { .. }
+        | I8x16Splat
+        | I8x16ExtractLaneS { .. }
+        | I8x16ExtractLaneU { .. }
+        | I8x16ReplaceLane { .. }
+        | I16x8Splat
+        | I16x8ExtractLaneS { .. }
+        | I16x8ExtractLaneU { .. }
+        | I16x8ReplaceLane { .. }
+        | I32x4Splat
+        | I32x4ExtractLane { .. }
+        | I32x4ReplaceLane { .. }
+        | I64x2Splat
+        | I64x2ExtractLane { .. }
+        | I64x2ReplaceLane { .. }
+        | F32x4Splat
+        | F32x4ExtractLane { .. }
+        | F32x4ReplaceLane { .. }
+        | F64x2Splat
+        | F64x2ExtractLane { .. }
+        | F64x2ReplaceLane { .. }
+        | I8x16Eq
+        | I8x16Ne
+        | I8x16LtS
+        | I8x16LtU
+        | I8x16GtS
+        | I8x16GtU
+        | I8x16LeS
+        | I8x16LeU
+        | I8x16GeS
+        | I8x16GeU
+        | I16x8Eq
+        | I16x8Ne
+        | I16x8LtS
+        | I16x8LtU
+        | I16x8GtS
+        | I16x8GtU
+        | I16x8LeS
+        | I16x8LeU
+        | I16x8GeS
+        | I16x8GeU
+        | I32x4Eq
+        | I32x4Ne
+        | I32x4LtS
+        | I32x4LtU
+        | I32x4GtS
+        | I32x4GtU
+        | I32x4LeS
+        | I32x4LeU
+        | I32x4GeS
+        | I32x4GeU
+        | F32x4Eq
+        | F32x4Ne
+        | F32x4Lt
+        | F32x4Gt
+        | F32x4Le
+        | F32x4Ge
+        | F64x2Eq
+        | F64x2Ne
+        | F64x2Lt
+        | F64x2Gt
+        | F64x2Le
+        | F64x2Ge
+        | V128Not
+        | V128And
+        | V128AndNot
+        | V128Or
+        | V128Xor
+        | V128Bitselect
+        | I8x16Abs
+        | I8x16Neg
+        | I8x16AnyTrue
+        | I8x16AllTrue
+        | I8x16Shl
+        | I8x16ShrS
+        | I8x16ShrU
+        | I8x16Add
+        | I8x16AddSaturateS
+        | I8x16AddSaturateU
+        | I8x16Sub
+        | I8x16SubSaturateS
+        | I8x16SubSaturateU
+        | I8x16MinS
+        | I8x16MinU
+        | I8x16MaxS
+        | I8x16MaxU
+        | I16x8Abs
+        | I16x8Neg
+        | I16x8AnyTrue
+        | I16x8AllTrue
+        | I16x8Shl
+        | I16x8ShrS
+        | I16x8ShrU
+        | I16x8Add
+        | I16x8AddSaturateS
+        | I16x8AddSaturateU
+        | I16x8Sub
+        | I16x8SubSaturateS
+        | I16x8SubSaturateU
+        | I16x8Mul
+        | I16x8MinS
+        | I16x8MinU
+        | I16x8MaxS
+        | I16x8MaxU
+        | I32x4Abs
+        | I32x4Neg
+        | I32x4AnyTrue
+        | I32x4AllTrue
+        | I32x4Shl
+        | I32x4ShrS
+        | I32x4ShrU
+        | I32x4Add
+        | I32x4Sub
+        | I32x4Mul
+        | I32x4MinS
+        | I32x4MinU
+        | I32x4MaxS
+        | I32x4MaxU
+        | I64x2Neg
+        | I64x2Shl
+        | I64x2ShrS
+        | I64x2ShrU
+        | I64x2Add
+        | I64x2Sub
+        | I64x2Mul
+        | F32x4Abs
+        | F32x4Neg
+        | F32x4Sqrt
+        | F32x4Add
+        | F32x4Sub
+        | F32x4Mul
+        | F32x4Div
+        | F32x4Min
+        | F32x4Max
+        | F64x2Abs
+        | F64x2Neg
+        | F64x2Sqrt
+        | F64x2Add
+        | F64x2Sub
+        | F64x2Mul
+        | F64x2Div
+        | F64x2Min
+        | F64x2Max
+        | I32x4TruncSatF32x4S
+        | I32x4TruncSatF32x4U
+        | F32x4ConvertI32x4S
+        | F32x4ConvertI32x4U
+        | V8x16Swizzle
+        | V8x16Shuffle { .. }
+        | V8x16LoadSplat { .. }
+        | V16x8LoadSplat { .. }
+        | V32x4LoadSplat { .. }
+        | V64x2LoadSplat { .. }
+        | I8x16NarrowI16x8S
+        | I8x16NarrowI16x8U
+        | I16x8NarrowI32x4S
+        | I16x8NarrowI32x4U
+        | I16x8WidenLowI8x16S
+        | I16x8WidenHighI8x16S
+        | I16x8WidenLowI8x16U
+        | I16x8WidenHighI8x16U
+        | I32x4WidenLowI16x8S
+        | I32x4WidenHighI16x8S
+        | I32x4WidenLowI16x8U
+        | I32x4WidenHighI16x8U
+        | I16x8Load8x8S { .. }
+        | I16x8Load8x8U { .. }
+        | I32x4Load16x4S { .. }
+        | I32x4Load16x4U { .. }
+        | I64x2Load32x2S { .. }
+        | I64x2Load32x2U { .. }
+        | I8x16RoundingAverageU
+        | I16x8RoundingAverageU => Some(WasmProposal::Simd),
+        _ => None,
+    }
+}
+
+/// Returns the name of an operator used in violations, it's taken from the debug
+/// representation without operator immediates.
+fn operator_name(operator: &Operator<'_>) -> String {
+    let operator = format!("{:?}", operator);
+    match operator.find(|c: char| !c.is_ascii_alphanumeric()) {
+        Some(name_end) => operator[..name_end].to_string(),
+        None => operator,
+    }
+}
+
+impl std::fmt::Display for WasmProposal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WasmProposal::Simd => write!(f, "SIMD"),
+            WasmProposal::Threads => write!(f, "threads"),
+            WasmProposal::BulkMemory => write!(f, "bulk memory"),
+        }
+    }
+}
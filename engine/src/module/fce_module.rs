@@ -17,8 +17,14 @@
 use super::wit_prelude::*;
 use super::{IType, IRecordType, IFunctionArg, IValue, WValue};
 use super::RecordTypes;
+use super::stack_limiter::take_nested_overflow;
+use super::stack_limiter::StackLimiter;
 use crate::Result;
 use crate::FCEModuleConfig;
+use crate::call_interceptor::FCECallContext;
+use crate::call_interceptor::Interceptors;
+use crate::host_imports::HostImportsStats;
+use crate::host_imports::HostImportsStatsCollector;
 
 use fce_wit_interfaces::FCEWITInterfaces;
 use fce_wit_parser::extract_wit;
@@ -36,48 +42,79 @@ use std::collections::HashMap;
 use std::convert::TryInto;
 use std::mem::MaybeUninit;
 use std::sync::Arc;
-use std::rc::Rc;
 
-type WITInterpreter =
+pub(super) type WITInterpreter =
     Interpreter<WITInstance, WITExport, WITFunction, WITMemory, WITMemoryView<'static>>;
 
 #[derive(Clone)]
 pub(super) struct WITModuleFunc {
-    interpreter: Arc<WITInterpreter>,
-    pub(super) arguments: Rc<Vec<IFunctionArg>>,
-    pub(super) output_types: Rc<Vec<IType>>,
+    interpreter: Arc<ModuleLocal<WITInterpreter>>,
+    pub(super) arguments: Arc<Vec<IFunctionArg>>,
+    pub(super) output_types: Arc<Vec<IType>>,
 }
 
 /// Represent a function type inside FCE module.
 #[derive(PartialEq, Eq, Debug, Clone, Hash, Serialize, Deserialize)]
 pub struct FCEFunctionSignature {
-    pub name: Rc<String>,
-    pub arguments: Rc<Vec<IFunctionArg>>,
-    pub outputs: Rc<Vec<IType>>,
+    pub name: Arc<String>,
+    pub arguments: Arc<Vec<IFunctionArg>>,
+    pub outputs: Arc<Vec<IType>>,
 }
 
 #[derive(Clone)]
 pub(super) struct Callable {
     pub(super) wit_instance: Arc<WITInstance>,
     pub(super) wit_module_func: WITModuleFunc,
+    pub(super) module_name: Arc<String>,
+    pub(super) function_name: SharedString,
+    pub(super) interceptors: Interceptors,
+    pub(super) stack_limiter: Option<StackLimiter>,
 }
 
 impl Callable {
     pub fn call(&mut self, args: &[IValue]) -> Result<Vec<IValue>> {
         use wasmer_wit::interpreter::stack::Stackable;
 
-        let result = self
-            .wit_module_func
-            .interpreter
-            .run(args, Arc::make_mut(&mut self.wit_instance))?
-            .as_slice()
-            .to_owned();
+        let context = FCECallContext {
+            module_name: self.module_name.as_str(),
+            function_name: self.function_name.0.as_str(),
+            arguments: args,
+        };
+
+        let wit_module_func = &self.wit_module_func;
+        let wit_instance = &mut self.wit_instance;
+        let result = self.interceptors.intercept(context, || {
+            let result = wit_module_func
+                .interpreter
+                .run(args, Arc::make_mut(wit_instance))?
+                .as_slice()
+                .to_owned();
 
-        Ok(result)
+            Ok(result)
+        });
+
+        // an overflow in an imported module makes the result meaningless,
+        // even if the failed import hasn't trapped this module
+        let nested_overflow = take_nested_overflow();
+
+        if let (Err(_), Some(stack_limiter)) = (&result, &self.stack_limiter) {
+            if stack_limiter.reset_after_trap() {
+                return Err(FCEError::StackOverflow {
+                    module_name: self.module_name.to_string(),
+                    function_name: self.function_name.0.to_string(),
+                    max_stack_height: stack_limiter.max_stack_height(),
+                });
+            }
+        }
+
+        match nested_overflow {
+            Some(nested_overflow) => Err(nested_overflow),
+            None => result,
+        }
     }
 }
 
-type ExportFunctions = HashMap<SharedString, Rc<Callable>>;
+type ExportFunctions = HashMap<SharedString, Arc<Callable>>;
 
 pub(crate) struct FCEModule {
     // wasmer_instance is needed because WITInstance contains dynamic functions
@@ -106,13 +143,18 @@ pub(crate) struct FCEModule {
     // TODO: save refs instead copying of a record types HashMap.
     /// Record types used in exported functions as arguments or return values.
     export_record_types: RecordTypes,
+
+    /// Stats of data passed through host imports of this module.
+    host_imports_stats: Arc<HostImportsStatsCollector>,
 }
 
 impl FCEModule {
     pub(crate) fn new(
+        module_name: &str,
         wasm_bytes: &[u8],
         config: FCEModuleConfig,
         modules: &HashMap<String, FCEModule>,
+        interceptors: Interceptors,
     ) -> Result<Self> {
         let wasmer_module = compile(&wasm_bytes)?;
         let wit = extract_wit(&wasmer_module)?;
@@ -121,25 +163,53 @@ impl FCEModule {
         let mut wit_instance = Arc::new_uninit();
         let wit_import_object = Self::adjust_wit_imports(&fce_wit, wit_instance.clone())?;
         let raw_imports = config.raw_imports.clone();
-        let (wasi_import_object, host_closures_import_object) =
-            Self::create_import_objects(config, &fce_wit, wit_import_object.clone())?;
+        let max_stack_height = config.max_stack_height;
+        let host_imports_stats = Arc::new(HostImportsStatsCollector::default());
+        let (wasi_import_object, host_closures_import_object) = Self::create_import_objects(
+            config,
+            &fce_wit,
+            wit_import_object.clone(),
+            host_imports_stats.clone(),
+        )?;
 
         let wasmer_instance = wasmer_module.instantiate(&wasi_import_object)?;
         let wit_instance = unsafe {
-            // get_mut_unchecked here is safe because currently only this modules have reference to
-            // it and the environment is single-threaded
+            // get_mut_unchecked here is safe because currently only this module has a reference to
+            // it and the module isn't shared with other threads until it's fully constructed
             *Arc::get_mut_unchecked(&mut wit_instance) =
                 MaybeUninit::new(WITInstance::new(&wasmer_instance, &fce_wit, modules)?);
             std::mem::transmute::<_, Arc<WITInstance>>(wit_instance)
         };
 
-        let export_funcs = Self::instantiate_wit_exports(&wit_instance, &fce_wit)?;
+        let stack_limiter = max_stack_height
+            .map(|max_stack_height| StackLimiter::new(&wasmer_instance, max_stack_height))
+            .transpose()?;
+        let export_funcs = Self::instantiate_wit_exports(
+            module_name,
+            &wit_instance,
+            &fce_wit,
+            interceptors,
+            stack_limiter.clone(),
+        )?;
         let export_record_types = Self::extract_export_record_types(&export_funcs, &wit_instance)?;
 
         // call _start to populate the WASI state of the module
         #[rustfmt::skip]
         if let Ok(start_func) = wasmer_instance.exports.get::<wasmer_runtime::Func<'_, (), ()>>("_start") {
-            start_func.call()?;
+            let result = start_func.call();
+            if let Some(nested_overflow) = take_nested_overflow() {
+                return Err(nested_overflow);
+            }
+            if let Err(e) = result {
+                return Err(match &stack_limiter {
+                    Some(stack_limiter) if stack_limiter.reset_after_trap() => FCEError::StackOverflow {
+                        module_name: module_name.to_string(),
+                        function_name: String::from("_start"),
+                        max_stack_height: stack_limiter.max_stack_height(),
+                    },
+                    _ => e.into(),
+                });
+            }
         }
 
         Ok(Self {
@@ -149,6 +219,7 @@ impl FCEModule {
             host_closures_import_object,
             export_funcs,
             export_record_types,
+            host_imports_stats,
         })
     }
 
@@ -160,7 +231,7 @@ impl FCEModule {
                     function_name
                 )))
             },
-            |func| Rc::make_mut(func).call(args),
+            |func| Arc::make_mut(func).call(args),
         )
     }
 
@@ -178,16 +249,33 @@ impl FCEModule {
         &self.export_record_types
     }
 
-    pub(crate) fn export_record_type_by_id(&self, record_type: u64) -> Option<&Rc<IRecordType>> {
+    pub(crate) fn export_record_type_by_id(&self, record_type: u64) -> Option<&Arc<IRecordType>> {
         self.export_record_types.get(&record_type)
     }
 
+    pub(crate) fn host_imports_stats(&self) -> HostImportsStats {
+        self.host_imports_stats.stats()
+    }
+
+    /// Returns the current size of the module memory in Wasm pages.
+    pub(crate) fn memory_pages(&self) -> u32 {
+        use wasmer_core::export::Export;
+
+        self.wasmer_instance
+            .exports()
+            .find_map(|(_, export)| match export {
+                Export::Memory(memory) => Some(memory.size().0),
+                _ => None,
+            })
+            .unwrap_or(0)
+    }
+
     pub(crate) fn get_wasi_state(&mut self) -> &wasmer_wasi::state::WasiState {
         unsafe { wasmer_wasi::state::get_wasi_state(self.wasmer_instance.context_mut()) }
     }
 
     // TODO: change the cloning Callable behaviour after changes of Wasmer API
-    pub(super) fn get_callable(&self, function_name: &str) -> Result<Rc<Callable>> {
+    pub(super) fn get_callable(&self, function_name: &str) -> Result<Arc<Callable>> {
         match self.export_funcs.get(function_name) {
             Some(func) => Ok(func.clone()),
             None => Err(FCEError::NoSuchFunction(format!(
@@ -201,6 +289,7 @@ impl FCEModule {
         config: FCEModuleConfig,
         fce_wit: &FCEWITInterfaces<'_>,
         wit_import_object: ImportObject,
+        host_imports_stats: Arc<HostImportsStatsCollector>,
     ) -> Result<(ImportObject, ImportObject)> {
         use crate::host_imports::create_host_import_func;
 
@@ -228,12 +317,16 @@ impl FCEModule {
         let mut host_closures_namespace = Namespace::new();
         let record_types = fce_wit
             .record_types()
-            .map(|(id, r)| (id, r.clone()))
+            .map(|(id, r)| (id, Arc::new(r.as_ref().clone())))
             .collect::<HashMap<_, _>>();
-        let record_types = Rc::new(record_types);
+        let record_types = Arc::new(record_types);
 
         for (import_name, descriptor) in config.host_imports {
-            let host_import = create_host_import_func(descriptor, record_types.clone());
+            let host_import = create_host_import_func(
+                descriptor,
+                record_types.clone(),
+                host_imports_stats.clone(),
+            );
             host_closures_namespace.insert(import_name, host_import);
         }
         let mut host_closures_import_object = ImportObject::new();
@@ -247,11 +340,16 @@ impl FCEModule {
     }
 
     fn instantiate_wit_exports(
+        module_name: &str,
         wit_instance: &Arc<WITInstance>,
         wit: &FCEWITInterfaces<'_>,
+        interceptors: Interceptors,
+        stack_limiter: Option<StackLimiter>,
     ) -> Result<ExportFunctions> {
         use fce_wit_interfaces::WITAstType;
 
+        let module_name = Arc::new(module_name.to_string());
+
         wit.implementations()
             .filter_map(|(adapter_function_type, core_function_type)| {
                 match wit.exports_by_type(*core_function_type) {
@@ -280,15 +378,20 @@ impl FCEModule {
                         let interpreter: WITInterpreter =
                             adapter_instructions.clone().try_into()?;
                         let wit_module_func = WITModuleFunc {
-                            interpreter: Arc::new(interpreter),
-                            arguments: arguments.clone(),
-                            output_types: output_types.clone(),
+                            interpreter: Arc::new(ModuleLocal::new(interpreter)),
+                            arguments: Arc::new(arguments.as_ref().clone()),
+                            output_types: Arc::new(output_types.as_ref().clone()),
                         };
 
-                        let shared_string = SharedString(Rc::new(export_function_name.to_string()));
-                        let callable = Rc::new(Callable {
+                        let shared_string =
+                            SharedString(Arc::new(export_function_name.to_string()));
+                        let callable = Arc::new(Callable {
                             wit_instance: wit_instance.clone(),
                             wit_module_func,
+                            module_name: module_name.clone(),
+                            function_name: shared_string.clone(),
+                            interceptors: interceptors.clone(),
+                            stack_limiter: stack_limiter.clone(),
                         });
 
                         Ok((shared_string, callable))
@@ -476,7 +579,7 @@ impl FCEModule {
                                 record_type_id
                             ))
                         })?;
-                export_record_types.insert(record_type_id, record_type.clone());
+                export_record_types.insert(record_type_id, Arc::new(record_type.as_ref().clone()));
 
                 for field in record_type.fields.iter() {
                     handle_itype(
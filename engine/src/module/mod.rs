@@ -20,6 +20,8 @@ mod wit_function;
 mod wit_instance;
 mod type_converters;
 mod fce_module;
+mod module_local;
+mod stack_limiter;
 
 pub use wit_instance::RecordTypes;
 
@@ -43,4 +45,5 @@ pub(self) mod wit_prelude {
     pub(super) use super::wit_function::WITFunction;
     pub(super) use super::memory::WITMemoryView;
     pub(super) use super::memory::WITMemory;
+    pub(super) use super::module_local::ModuleLocal;
 }
@@ -0,0 +1,63 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::fce_module::FCEModule;
+use super::fce_module::WITInterpreter;
+use super::wit_instance::WITRecordTypes;
+
+use wasmer_core::instance::DynFunc;
+
+use std::ops::Deref;
+
+/// Holds a value of a module that isn't Send or Sync only because it keeps raw pointers
+/// into the Wasmer instance of the module or Rc-based record types required by the interface
+/// types interpreter. Only the types listed below could be held.
+///
+/// Such values are created while the module is instantiated, are reachable only from the module
+/// and modules importing its functions, and are used only by calls, which require the owning
+/// FCE to be borrowed mutably. So they are moved between threads together with the whole FCE
+/// and are never accessed from several threads at once. Sync is needed only to keep them in Arc,
+/// a module itself isn't Sync because of its Wasmer instance.
+#[derive(Clone)]
+pub(super) struct ModuleLocal<T>(T);
+
+impl<T> ModuleLocal<T> {
+    pub(super) fn new(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> Deref for ModuleLocal<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+unsafe impl Send for ModuleLocal<WITRecordTypes> {}
+unsafe impl Sync for ModuleLocal<WITRecordTypes> {}
+unsafe impl Send for ModuleLocal<DynFunc<'static>> {}
+unsafe impl Sync for ModuleLocal<DynFunc<'static>> {}
+unsafe impl Send for ModuleLocal<WITInterpreter> {}
+unsafe impl Sync for ModuleLocal<WITInterpreter> {}
+
+// fails to compile if a module gets state that isn't Send and isn't held by ModuleLocal
+#[allow(dead_code)]
+fn assert_send() {
+    fn assert_send<T: Send>() {}
+    assert_send::<FCEModule>();
+}
@@ -0,0 +1,87 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::WValue;
+use crate::FCEError;
+use crate::Result;
+use crate::misc::STACK_HEIGHT_GLOBAL_NAME;
+
+use wasmer_core::export::Export;
+use wasmer_core::global::Global;
+use wasmer_core::Instance as WasmerInstance;
+
+use std::cell::RefCell;
+
+thread_local! {
+    /// An overflow in a module called through an import, the importing module sees
+    /// only a failed import, so the overflow is reported by its own call.
+    static NESTED_OVERFLOW: RefCell<Option<FCEError>> = RefCell::new(None);
+}
+
+/// Keeps the stack height counter injected to a module on the preparation step.
+#[derive(Clone)]
+pub(super) struct StackLimiter {
+    stack_height: Global,
+    max_stack_height: u32,
+}
+
+impl StackLimiter {
+    pub(super) fn new(wasmer_instance: &WasmerInstance, max_stack_height: u32) -> Result<Self> {
+        let stack_height = wasmer_instance
+            .exports()
+            .find_map(|(name, export)| match export {
+                Export::Global(global) if name == STACK_HEIGHT_GLOBAL_NAME => Some(global),
+                _ => None,
+            })
+            .ok_or_else(|| {
+                FCEError::PrepareError(String::from(
+                    "stack height counter isn't exported by an instrumented module",
+                ))
+            })?;
+
+        Ok(Self {
+            stack_height,
+            max_stack_height,
+        })
+    }
+
+    pub(super) fn max_stack_height(&self) -> u32 {
+        self.max_stack_height
+    }
+
+    /// Resets the counter after a failed call, because a trap leaves it as is,
+    /// and returns true if the call has trapped on exceeding the limit.
+    pub(super) fn reset_after_trap(&self) -> bool {
+        let stack_height = match self.stack_height.get() {
+            WValue::I32(stack_height) => stack_height as u32,
+            _ => 0,
+        };
+        self.stack_height.set(WValue::I32(0));
+
+        // the limiter increases the counter before checking it, so it stays above the limit
+        stack_height > self.max_stack_height
+    }
+}
+
+/// Keeps an overflow of an imported function until the call of the importing module ends.
+pub(super) fn set_nested_overflow(error: FCEError) {
+    NESTED_OVERFLOW.with(|overflow| *overflow.borrow_mut() = Some(error));
+}
+
+/// Returns an overflow that happened in imported functions since the last check.
+pub(super) fn take_nested_overflow() -> Option<FCEError> {
+    NESTED_OVERFLOW.with(|overflow| overflow.borrow_mut().take())
+}
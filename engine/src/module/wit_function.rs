@@ -17,22 +17,24 @@
 use super::fce_module::FCEModule;
 use super::{IType, IFunctionArg, IValue, WValue};
 use super::fce_module::Callable;
+use super::module_local::ModuleLocal;
+use super::stack_limiter::set_nested_overflow;
+use crate::FCEError;
 use crate::Result;
 
 use wasmer_wit::interpreter::wasm;
 use wasmer_core::instance::DynFunc;
 
-// use std::sync::Arc;
-use std::rc::Rc;
+use std::sync::Arc;
 
 #[derive(Clone)]
 enum WITFunctionInner {
     Export {
-        func: Rc<DynFunc<'static>>,
+        func: Arc<ModuleLocal<DynFunc<'static>>>,
     },
     Import {
         // TODO: use dyn Callable here
-        callable: Rc<Callable>,
+        callable: Arc<Callable>,
     },
 }
 
@@ -40,8 +42,8 @@ enum WITFunctionInner {
 #[derive(Clone)]
 pub(super) struct WITFunction {
     name: String,
-    arguments: Rc<Vec<IFunctionArg>>,
-    outputs: Rc<Vec<IType>>,
+    arguments: Arc<Vec<IFunctionArg>>,
+    outputs: Arc<Vec<IType>>,
     inner: WITFunctionInner,
 }
 
@@ -67,11 +69,11 @@ impl WITFunction {
             .collect::<Vec<_>>();
 
         let inner = WITFunctionInner::Export {
-            func: Rc::new(dyn_func),
+            func: Arc::new(ModuleLocal::new(dyn_func)),
         };
 
-        let arguments = Rc::new(arguments);
-        let outputs = Rc::new(outputs);
+        let arguments = Arc::new(arguments);
+        let outputs = Arc::new(outputs);
 
         Ok(Self {
             name,
@@ -85,8 +87,8 @@ impl WITFunction {
     pub(super) fn from_import(
         wit_module: &FCEModule,
         function_name: &str,
-        arguments: Rc<Vec<IFunctionArg>>,
-        outputs: Rc<Vec<IType>>,
+        arguments: Arc<Vec<IFunctionArg>>,
+        outputs: Arc<Vec<IType>>,
     ) -> Result<Self> {
         let callable = wit_module.get_callable(function_name)?;
 
@@ -133,9 +135,23 @@ impl wasm::structures::LocalImport for WITFunction {
                 .call(&arguments.iter().map(ival_to_wval).collect::<Vec<WValue>>())
                 .map(|result| result.iter().map(wval_to_ival).collect())
                 .map_err(|_| ()),
-            WITFunctionInner::Import { callable, .. } => Rc::make_mut(&mut callable.clone())
-                .call(arguments)
-                .map_err(|_| ()),
+            WITFunctionInner::Import { callable, .. } => {
+                let span = tracing::info_span!(
+                    "import_call",
+                    module = callable.module_name.as_str(),
+                    function = self.name.as_str()
+                );
+                let _entered = span.enter();
+
+                // the interpreter drops the error, so an overflow is passed to the outer call
+                Arc::make_mut(&mut callable.clone())
+                    .call(arguments)
+                    .map_err(|e| {
+                        if let FCEError::StackOverflow { .. } = e {
+                            set_nested_overflow(e);
+                        }
+                    })
+            }
         }
     }
 }
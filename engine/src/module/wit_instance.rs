@@ -27,8 +27,14 @@ use wasmer_core::Instance as WasmerInstance;
 
 use std::collections::HashMap;
 use std::rc::Rc;
+use std::sync::Arc;
 
-pub type RecordTypes = HashMap<u64, Rc<IRecordType>>;
+/// Record types exposed by FCE to its users, they could be freely shared between threads.
+pub type RecordTypes = HashMap<u64, Arc<IRecordType>>;
+
+/// Record types in the form required by the interface types interpreter,
+/// they never leave the owning module.
+pub(super) type WITRecordTypes = HashMap<u64, Rc<IRecordType>>;
 
 /// Contains all import and export functions that could be called from WIT context by call-core.
 #[derive(Clone)]
@@ -40,7 +46,7 @@ pub(super) struct WITInstance {
     memories: Vec<WITMemory>,
 
     /// All record types that instance contains.
-    record_types_by_id: RecordTypes,
+    record_types_by_id: ModuleLocal<WITRecordTypes>,
 }
 
 impl WITInstance {
@@ -56,7 +62,7 @@ impl WITInstance {
         exports.extend(imports);
         let funcs = exports;
 
-        let record_types_by_id = Self::extract_record_types(wit);
+        let record_types_by_id = ModuleLocal::new(Self::extract_record_types(wit));
 
         Ok(Self {
             funcs,
@@ -119,6 +125,8 @@ impl WITInstance {
                             }
                         };
 
+                    let arguments = Arc::new(arguments.as_ref().clone());
+                    let output_types = Arc::new(output_types.as_ref().clone());
                     let func =
                         WITFunction::from_import(module, import.name, arguments, output_types)?;
 
@@ -153,7 +161,7 @@ impl WITInstance {
         memories
     }
 
-    fn extract_record_types(wit: &FCEWITInterfaces<'_>) -> RecordTypes {
+    fn extract_record_types(wit: &FCEWITInterfaces<'_>) -> WITRecordTypes {
         let (record_types_by_id, _) = wit.types().fold(
             (HashMap::new(), 0u64),
             |(mut record_types_by_id, id), ty| {
@@ -0,0 +1,117 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use fce::FCE;
+use fce::FCEError;
+use fce::FCEModuleConfig;
+use fce::IValue;
+use fce::ModuleSignatureConfig;
+
+use once_cell::sync::Lazy;
+
+static GREETING_WASM_BYTES: Lazy<Vec<u8>> = Lazy::new(|| {
+    std::fs::read("../examples/greeting/artifacts/greeting.wasm")
+        .expect("../examples/greeting/artifacts/greeting.wasm should presence")
+});
+
+const TRUSTED_SECRET_KEY: [u8; 32] = [1; 32];
+const UNTRUSTED_SECRET_KEY: [u8; 32] = [2; 32];
+
+fn signature_config(detached_signature: Option<Vec<u8>>) -> FCEModuleConfig {
+    let trusted_key = fce::signing_public_key(&TRUSTED_SECRET_KEY)
+        .unwrap_or_else(|e| panic!("can't derive public key: {:?}", e));
+
+    FCEModuleConfig {
+        signature_verification: Some(ModuleSignatureConfig {
+            trusted_keys: vec![trusted_key],
+            detached_signature,
+        }),
+        ..<_>::default()
+    }
+}
+
+#[test]
+pub fn embedded_signature() {
+    let signed_wasm = fce::sign_module(&*GREETING_WASM_BYTES, &TRUSTED_SECRET_KEY)
+        .unwrap_or_else(|e| panic!("can't sign module: {:?}", e));
+
+    // signing again replaces the embedded signature instead of adding one more
+    let resigned_wasm = fce::sign_module(&signed_wasm, &TRUSTED_SECRET_KEY)
+        .unwrap_or_else(|e| panic!("can't sign module: {:?}", e));
+    assert_eq!(signed_wasm, resigned_wasm);
+
+    let mut fce = FCE::new();
+    fce.load_module("greeting", &signed_wasm, signature_config(None))
+        .unwrap_or_else(|e| panic!("can't load a signed module into FCE: {:?}", e));
+
+    let result = fce
+        .call(
+            "greeting",
+            "greeting",
+            &[IValue::String(String::from("Fluence"))],
+        )
+        .unwrap_or_else(|e| panic!("can't invoke greeting: {:?}", e));
+    assert_eq!(result, vec![IValue::String(String::from("Hi, Fluence"))]);
+
+    let load_result = fce.load_module(
+        "unsigned_greeting",
+        &*GREETING_WASM_BYTES,
+        signature_config(None),
+    );
+    assert!(matches!(
+        load_result,
+        Err(FCEError::ModuleSignatureError(_))
+    ));
+
+    let untrusted_wasm = fce::sign_module(&*GREETING_WASM_BYTES, &UNTRUSTED_SECRET_KEY)
+        .unwrap_or_else(|e| panic!("can't sign module: {:?}", e));
+    let load_result = fce.load_module(
+        "untrusted_greeting",
+        &untrusted_wasm,
+        signature_config(None),
+    );
+    assert!(matches!(
+        load_result,
+        Err(FCEError::ModuleSignatureError(_))
+    ));
+}
+
+#[test]
+pub fn detached_signature() {
+    let signature = fce::module_signature(&*GREETING_WASM_BYTES, &TRUSTED_SECRET_KEY)
+        .unwrap_or_else(|e| panic!("can't sign module: {:?}", e));
+
+    let mut fce = FCE::new();
+    fce.load_module(
+        "greeting",
+        &*GREETING_WASM_BYTES,
+        signature_config(Some(signature.clone())),
+    )
+    .unwrap_or_else(|e| panic!("can't load a signed module into FCE: {:?}", e));
+
+    let mut tampered_wasm = GREETING_WASM_BYTES.clone();
+    let last_byte = tampered_wasm.len() - 1;
+    tampered_wasm[last_byte] ^= 0xFF;
+    let load_result = fce.load_module(
+        "tampered_greeting",
+        &tampered_wasm,
+        signature_config(Some(signature)),
+    );
+    assert!(matches!(
+        load_result,
+        Err(FCEError::ModuleSignatureError(_))
+    ));
+}
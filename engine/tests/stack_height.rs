@@ -0,0 +1,237 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use fce::FCE;
+use fce::FCEError;
+use fce::FCEModuleConfig;
+use fce::IValue;
+
+use once_cell::sync::Lazy;
+use parity_wasm::builder;
+use parity_wasm::elements::BlockType;
+use parity_wasm::elements::Instruction;
+use parity_wasm::elements::Instructions;
+use parity_wasm::elements::ValueType;
+
+static GREETING_WASM_BYTES: Lazy<Vec<u8>> = Lazy::new(|| {
+    std::fs::read("../examples/greeting/artifacts/greeting.wasm")
+        .expect("../examples/greeting/artifacts/greeting.wasm should presence")
+});
+
+static EFFECTOR_WASM_BYTES: Lazy<Vec<u8>> = Lazy::new(|| {
+    std::fs::read("../fluence-faas/tests/wasm_tests/arguments_passing/artifacts/arguments_passing_effector.wasm")
+        .expect("arguments_passing_effector.wasm should presence")
+});
+
+static PURE_WASM_BYTES: Lazy<Vec<u8>> = Lazy::new(|| {
+    std::fs::read(
+        "../fluence-faas/tests/wasm_tests/arguments_passing/artifacts/arguments_passing_pure.wasm",
+    )
+    .expect("arguments_passing_pure.wasm should presence")
+});
+
+/// IT of a module with one export recurse(depth: s32) -> s32.
+const RECURSIVE_WIT: &str = r#"
+(@interface type (func (param $depth: s32) (result s32)))   ;; 0
+(@interface type (func (param $depth: s32) (result s32)))   ;; 1
+
+(@interface func (type 0)
+  arg.get 0
+  i32.from_s32
+  call-core 0
+  s32.from_i32)
+
+(@interface export "recurse" (func 1))
+
+(@interface implement (func 1) (func 0))
+"#;
+
+/// Builds a module with a function that calls itself depth times and returns depth.
+fn recursive_wasm() -> Vec<u8> {
+    use Instruction::*;
+
+    let instructions = vec![
+        GetLocal(0),
+        I32Eqz,
+        If(BlockType::Value(ValueType::I32)),
+        I32Const(0),
+        Else,
+        GetLocal(0),
+        I32Const(1),
+        I32Sub,
+        Call(0),
+        I32Const(1),
+        I32Add,
+        End,
+        End,
+    ];
+
+    let mut module = builder::module()
+        .function()
+        .signature()
+        .with_param(ValueType::I32)
+        .with_return_type(Some(ValueType::I32))
+        .build()
+        .body()
+        .with_instructions(Instructions::new(instructions))
+        .build()
+        .build()
+        .memory()
+        .with_min(1)
+        .build()
+        .export()
+        .field("recurse")
+        .internal()
+        .func(0)
+        .build()
+        .export()
+        .field("memory")
+        .internal()
+        .memory(0)
+        .build()
+        .build();
+
+    let buffer = wasmer_wit::decoders::wat::Buffer::new(RECURSIVE_WIT)
+        .unwrap_or_else(|e| panic!("IT of the recursive module should be valid: {:?}", e));
+    let interfaces = wasmer_wit::decoders::wat::parse(&buffer)
+        .unwrap_or_else(|e| panic!("IT of the recursive module should be valid: {:?}", e));
+    let mut wit_bytes = vec![];
+    wasmer_wit::ToBytes::to_bytes(&interfaces, &mut wit_bytes)
+        .expect("IT of the recursive module should be serializable");
+    module.set_custom_section("interface-types", wit_bytes);
+
+    parity_wasm::serialize(module).expect("recursive module should be serializable")
+}
+
+fn call_recurse(fce: &mut FCE, depth: i32) -> Result<Vec<IValue>, FCEError> {
+    fce.call("recursive", "recurse", &[IValue::S32(depth)])
+}
+
+fn stack_height_config(max_stack_height: u32) -> FCEModuleConfig {
+    FCEModuleConfig {
+        max_stack_height: Some(max_stack_height),
+        ..<_>::default()
+    }
+}
+
+fn call_greeting(fce: &mut FCE) -> Result<Vec<IValue>, FCEError> {
+    fce.call(
+        "greeting",
+        "greeting",
+        &[IValue::String(String::from("Fluence"))],
+    )
+}
+
+#[test]
+pub fn stack_height_is_limited() {
+    let mut fce = FCE::new();
+
+    // any function with locals exceeds such limit, so the module traps
+    // either in _start on loading or in the call
+    let result = fce
+        .load_module("greeting", &*GREETING_WASM_BYTES, stack_height_config(1))
+        .and_then(|_| call_greeting(&mut fce));
+    assert!(matches!(
+        result,
+        Err(FCEError::StackOverflow {
+            max_stack_height: 1,
+            ..
+        })
+    ));
+}
+
+#[test]
+pub fn instrumented_module_works() {
+    let mut fce = FCE::new();
+    fce.load_module(
+        "greeting",
+        &*GREETING_WASM_BYTES,
+        stack_height_config(1024 * 1024),
+    )
+    .unwrap_or_else(|e| panic!("can't load a module into FCE: {:?}", e));
+
+    for _ in 0..3 {
+        let result =
+            call_greeting(&mut fce).unwrap_or_else(|e| panic!("can't invoke greeting: {:?}", e));
+        assert_eq!(result, vec![IValue::String(String::from("Hi, Fluence"))]);
+    }
+}
+
+#[test]
+pub fn deep_recursion_overflows() {
+    let mut fce = FCE::new();
+    fce.load_module("recursive", &recursive_wasm(), stack_height_config(1024))
+        .unwrap_or_else(|e| panic!("can't load a module into FCE: {:?}", e));
+
+    let result =
+        call_recurse(&mut fce, 100).unwrap_or_else(|e| panic!("can't invoke recurse: {:?}", e));
+    assert_eq!(result, vec![IValue::S32(100)]);
+
+    let result = call_recurse(&mut fce, 100_000);
+    assert!(matches!(
+        result,
+        Err(FCEError::StackOverflow {
+            max_stack_height: 1024,
+            ..
+        })
+    ));
+}
+
+#[test]
+pub fn instance_works_after_overflow() {
+    let mut fce = FCE::new();
+    fce.load_module("recursive", &recursive_wasm(), stack_height_config(1024))
+        .unwrap_or_else(|e| panic!("can't load a module into FCE: {:?}", e));
+
+    for _ in 0..3 {
+        let result = call_recurse(&mut fce, 100_000);
+        assert!(matches!(result, Err(FCEError::StackOverflow { .. })));
+
+        // the counter is reset after the trap, so a call close to the limit still succeeds
+        let result = call_recurse(&mut fce, 200)
+            .unwrap_or_else(|e| panic!("can't invoke recurse after an overflow: {:?}", e));
+        assert_eq!(result, vec![IValue::S32(200)]);
+    }
+}
+
+#[test]
+pub fn nested_overflow_is_reported() {
+    let mut fce = FCE::new();
+    // such limit is enough for _start of the effector, but not for its string_type
+    fce.load_module(
+        "arguments_passing_effector",
+        &*EFFECTOR_WASM_BYTES,
+        stack_height_config(40),
+    )
+    .unwrap_or_else(|e| panic!("can't load a module into FCE: {:?}", e));
+    fce.load_module("arguments_passing_pure", &*PURE_WASM_BYTES, <_>::default())
+        .unwrap_or_else(|e| panic!("can't load a module into FCE: {:?}", e));
+
+    // the effector overflows, and the pure module only sees a failed import
+    let result = fce.call(
+        "arguments_passing_pure",
+        "string_type",
+        &[IValue::String(String::from("Fluence"))],
+    );
+    match result {
+        Err(FCEError::StackOverflow {
+            module_name,
+            max_stack_height: 40,
+            ..
+        }) => assert_eq!(module_name, "arguments_passing_effector"),
+        result => panic!("nested overflow should be reported, got {:?}", result),
+    }
+}
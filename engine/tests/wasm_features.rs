@@ -0,0 +1,230 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use fce::FCE;
+use fce::FCEError;
+use fce::FCEModuleConfig;
+use fce::WasmFeatures;
+
+use once_cell::sync::Lazy;
+
+static GREETING_WASM_BYTES: Lazy<Vec<u8>> = Lazy::new(|| {
+    std::fs::read("../examples/greeting/artifacts/greeting.wasm")
+        .expect("../examples/greeting/artifacts/greeting.wasm should presence")
+});
+
+// (module (func (export "f") (result f32) f32.const 1.0))
+const FLOAT_WASM_BYTES: &[u8] = &[
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // header
+    0x01, 0x05, 0x01, 0x60, 0x00, 0x01, 0x7d, // type section
+    0x03, 0x02, 0x01, 0x00, // function section
+    0x07, 0x05, 0x01, 0x01, 0x66, 0x00, 0x00, // export section
+    0x0a, 0x09, 0x01, 0x07, 0x00, 0x43, 0x00, 0x00, 0x80, 0x3f, 0x0b, // code section
+];
+
+// (module
+//   (type (func (param f32)))
+//   (type (func))
+//   (type (func (result f64)))
+//   (import "env" "g" (func (type 0)))
+//   (func (export "f") (type 1)))
+const FLOAT_TYPES_WASM_BYTES: &[u8] = &[
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // header
+    0x01, 0x0c, 0x03, 0x60, 0x01, 0x7d, 0x00, // type section
+    0x60, 0x00, 0x00, 0x60, 0x00, 0x01, 0x7c, // type section
+    0x02, 0x09, 0x01, 0x03, 0x65, 0x6e, 0x76, 0x01, 0x67, 0x00, 0x00, // import section
+    0x03, 0x02, 0x01, 0x01, // function section
+    0x07, 0x05, 0x01, 0x01, 0x66, 0x00, 0x01, // export section
+    0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b, // code section
+];
+
+// (module (func (export "f") (param i32) local.get 0 i32x4.splat drop))
+const SIMD_WASM_BYTES: &[u8] = &[
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // header
+    0x01, 0x05, 0x01, 0x60, 0x01, 0x7f, 0x00, // type section
+    0x03, 0x02, 0x01, 0x00, // function section
+    0x07, 0x05, 0x01, 0x01, 0x66, 0x00, 0x00, // export section
+    0x0a, 0x09, 0x01, 0x07, 0x00, 0x20, 0x00, 0xfd, 0x11, 0x1a, 0x0b, // code section
+];
+
+// (module (func (export "f") atomic.fence))
+const THREADS_WASM_BYTES: &[u8] = &[
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // header
+    0x01, 0x04, 0x01, 0x60, 0x00, 0x00, // type section
+    0x03, 0x02, 0x01, 0x00, // function section
+    0x07, 0x05, 0x01, 0x01, 0x66, 0x00, 0x00, // export section
+    0x0a, 0x07, 0x01, 0x05, 0x00, 0xfe, 0x03, 0x00, 0x0b, // code section
+];
+
+// (module (func (export "f") (result f32) f32.const 1.0)) with a data count section,
+// which is the only part of the bulk memory proposal the module parser supports
+const FLOAT_DATA_COUNT_WASM_BYTES: &[u8] = &[
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // header
+    0x01, 0x05, 0x01, 0x60, 0x00, 0x01, 0x7d, // type section
+    0x03, 0x02, 0x01, 0x00, // function section
+    0x07, 0x05, 0x01, 0x01, 0x66, 0x00, 0x00, // export section
+    0x0c, 0x01, 0x00, // data count section
+    0x0a, 0x09, 0x01, 0x07, 0x00, 0x43, 0x00, 0x00, 0x80, 0x3f, 0x0b, // code section
+];
+
+fn features_config(features: WasmFeatures) -> FCEModuleConfig {
+    FCEModuleConfig {
+        wasm_features: features,
+        ..<_>::default()
+    }
+}
+
+#[test]
+pub fn floats_are_rejected() {
+    let mut fce = FCE::new();
+    let features = WasmFeatures {
+        floats: false,
+        ..<_>::default()
+    };
+
+    let violations = match fce.load_module("float", FLOAT_WASM_BYTES, features_config(features)) {
+        Err(FCEError::FeatureViolations(violations)) => violations,
+        result => panic!("module with floats should be rejected, got {:?}", result),
+    };
+
+    // the signature and the f32.const instruction
+    assert_eq!(violations.len(), 2);
+    assert!(violations
+        .iter()
+        .all(|violation| violation.function.as_deref() == Some("f (#0)")));
+    let instruction = violations[1]
+        .instruction
+        .as_deref()
+        .expect("instruction should be reported");
+    assert!(instruction.starts_with("f32.const"));
+}
+
+#[test]
+pub fn globals_are_limited() {
+    let mut fce = FCE::new();
+    let features = WasmFeatures {
+        max_globals: Some(0),
+        ..<_>::default()
+    };
+
+    let load_result = fce.load_module("greeting", &*GREETING_WASM_BYTES, features_config(features));
+    match load_result {
+        Err(FCEError::FeatureViolations(violations)) => {
+            assert_eq!(violations.len(), 1);
+            assert_eq!(violations[0].function, None);
+        }
+        result => panic!("module with globals should be rejected, got {:?}", result),
+    }
+
+    let features = WasmFeatures {
+        max_globals: Some(1024),
+        max_tables: Some(1),
+        ..<_>::default()
+    };
+    fce.load_module("greeting", &*GREETING_WASM_BYTES, features_config(features))
+        .unwrap_or_else(|e| panic!("can't load a module into FCE: {:?}", e));
+}
+
+#[test]
+pub fn float_types_are_rejected() {
+    let mut fce = FCE::new();
+    let features = WasmFeatures {
+        floats: false,
+        ..<_>::default()
+    };
+
+    let load_result = fce.load_module(
+        "float_types",
+        FLOAT_TYPES_WASM_BYTES,
+        features_config(features),
+    );
+    let violations = match load_result {
+        Err(FCEError::FeatureViolations(violations)) => violations,
+        result => panic!(
+            "module with float types should be rejected, got {:?}",
+            result
+        ),
+    };
+
+    // the signature of the imported function and the unused type
+    assert_eq!(violations.len(), 2);
+    assert_eq!(violations[0].function.as_deref(), Some("env.g (#0)"));
+    assert_eq!(violations[1].function, None);
+    assert!(violations[1].reason.starts_with("type #2"));
+}
+
+#[test]
+pub fn proposals_are_reported() {
+    let mut fce = FCE::new();
+
+    let cases = [
+        (SIMD_WASM_BYTES, "I32x4Splat"),
+        (THREADS_WASM_BYTES, "AtomicFence"),
+    ];
+    for &(wasm_bytes, instruction) in cases.iter() {
+        let load_result = fce.load_module("proposals", wasm_bytes, <_>::default());
+        let violations = match load_result {
+            Err(FCEError::FeatureViolations(violations)) => violations,
+            result => panic!(
+                "module with {} should be rejected, got {:?}",
+                instruction, result
+            ),
+        };
+
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].function.as_deref(), Some("f (#0)"));
+        assert_eq!(violations[0].instruction.as_deref(), Some(instruction));
+    }
+
+    // allowed proposals pass the validation, but the module parser doesn't support them
+    let features = WasmFeatures {
+        simd: true,
+        ..<_>::default()
+    };
+    let load_result = fce.load_module("proposals", SIMD_WASM_BYTES, features_config(features));
+    match load_result {
+        Err(FCEError::PrepareError(message)) => assert!(message.contains("SIMD")),
+        result => panic!("module with SIMD shouldn't be prepared, got {:?}", result),
+    }
+}
+
+#[test]
+pub fn proposals_are_reported_with_other_violations() {
+    let mut fce = FCE::new();
+    let features = WasmFeatures {
+        floats: false,
+        ..<_>::default()
+    };
+
+    let load_result = fce.load_module(
+        "proposals",
+        FLOAT_DATA_COUNT_WASM_BYTES,
+        features_config(features),
+    );
+    let violations = match load_result {
+        Err(FCEError::FeatureViolations(violations)) => violations,
+        result => panic!(
+            "module with bulk memory and floats should be rejected, got {:?}",
+            result
+        ),
+    };
+
+    assert_eq!(violations.len(), 3, "{:?}", violations);
+    assert!(violations[0].reason.contains("data count section"));
+    assert!(violations[1..]
+        .iter()
+        .all(|violation| violation.reason.contains("float")));
+}
@@ -0,0 +1,41 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::raw_toml_config::TomlAppServiceConfig;
+
+use serde_derive::Deserialize;
+use serde_derive::Serialize;
+
+/// Describes how to create a service: modules identified by hashes of their Wasm
+/// and a config of the service.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Blueprint {
+    pub name: String,
+
+    /// Modules of the service in the loading order, the last one is the default facade.
+    pub modules: Vec<BlueprintModule>,
+
+    /// Config of the service, module configs are matched with blueprint modules by names,
+    /// modules without configs are loaded with the default one, not with a config
+    /// saved with them in the module store.
+    pub config: TomlAppServiceConfig,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct BlueprintModule {
+    pub name: String,
+    pub hash: String,
+}
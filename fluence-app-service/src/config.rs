@@ -15,6 +15,7 @@
  */
 
 use std::path::PathBuf;
+use std::time::Duration;
 use fluence_faas::FaaSConfig;
 
 /// Describes behaviour of the Fluence AppService.
@@ -23,4 +24,92 @@ pub struct AppServiceConfig {
     /// Used for preparing filesystem on the service initialization stage.
     pub service_base_dir: PathBuf,
     pub faas_config: FaaSConfig,
+
+    /// Name of the facade module, all functions of the facade are public if no exports
+    /// are declared. The last module of faas_config is used if it isn't set, this fallback
+    /// is deprecated and logged.
+    pub facade: Option<String>,
+
+    /// Functions of service modules that make up the public interface of the service.
+    pub exports: Vec<ServiceExport>,
+
+    /// Lifecycle hooks of service modules.
+    pub lifecycle_hooks: Vec<LifecycleHooks>,
+
+    /// Settings of the pooled mode, used only by AppServicePool.
+    pub pool_config: Option<ServicePoolConfig>,
+}
+
+/// A function of a service module available to callers of the service.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServiceExport {
+    pub module_name: String,
+    pub function_name: String,
+
+    /// Name under which the function is called, the function name is used if it isn't set.
+    pub alias: Option<String>,
+}
+
+impl ServiceExport {
+    /// Returns a name under which the function is available to callers.
+    pub fn public_name(&self) -> &str {
+        self.alias.as_deref().unwrap_or(&self.function_name)
+    }
+}
+
+/// Export functions of a module called on lifecycle events of the service, each hook
+/// is bounded by the timeout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LifecycleHooks {
+    pub module_name: String,
+
+    /// Called once after the service is created with init_config as an argument.
+    pub on_init: Option<String>,
+
+    /// JSON passed to on_init.
+    pub init_config: String,
+
+    /// Called after the module is replaced with a new version, the old version is passed.
+    pub on_upgrade: Option<String>,
+
+    /// Called before the module is unloaded.
+    pub on_shutdown: Option<String>,
+
+    pub timeout: Duration,
+}
+
+/// Describes how AppServicePool manages instances of the same service.
+#[derive(Debug, Clone)]
+pub struct ServicePoolConfig {
+    /// Maximum count of service instances that could serve calls concurrently.
+    pub size: usize,
+
+    /// If true, all instances are created with the pool, otherwise on the first demand.
+    pub warm_up: bool,
+
+    /// If set, an instance is recreated after it has served this count of calls.
+    pub recycle_after_calls: Option<usize>,
+}
+
+impl Default for ServicePoolConfig {
+    fn default() -> Self {
+        Self {
+            size: 1,
+            warm_up: false,
+            recycle_after_calls: None,
+        }
+    }
+}
+
+/// Describes where AppServiceManager keeps modules, services and the registry of services.
+#[derive(Debug, Clone)]
+pub struct ServiceManagerConfig {
+    /// Dir of a module store with modules referenced by blueprints.
+    pub modules_dir: PathBuf,
+
+    /// Base dir of all created services.
+    pub service_base_dir: PathBuf,
+
+    /// Path to a file with created services, they are recreated from it on start.
+    pub registry_path: PathBuf,
 }
@@ -0,0 +1,507 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::config::ServiceExport;
+use crate::service_interface::collect_record_ids;
+use crate::service_interface::exported_function;
+use crate::AppServiceError;
+use crate::Result;
+
+use fluence_faas::ByteArrayEncoding;
+use fluence_faas::FaaSFunctionSignature;
+use fluence_faas::FaaSInterface;
+use fluence_faas::FaaSModuleInterface;
+use fluence_faas::IRecordType;
+use fluence_faas::IType;
+use fluence_faas::JsonOptions;
+use fluence_faas::RecordTypes;
+use fluence_faas::itype_text_view;
+
+use std::collections::HashSet;
+
+/// A record to declare, types of its fields refer to record types of its module.
+struct DeclaredRecord<'a> {
+    record: &'a IRecordType,
+    record_types: &'a RecordTypes,
+}
+
+/// A function to declare under its public name, its types refer to record types of its module.
+struct DeclaredFunction<'a> {
+    name: &'a str,
+    signature: &'a FaaSFunctionSignature,
+    record_types: &'a RecordTypes,
+}
+
+/// A service to declare along with records declared before it.
+struct DeclaredService<'a> {
+    name: &'a str,
+    id: &'a str,
+    records: Vec<DeclaredRecord<'a>>,
+    functions: Vec<DeclaredFunction<'a>>,
+}
+
+/// Renders a module interface as TypeScript declarations: an interface for every record
+/// and an interface with methods for functions of the module. Numeric types are declared
+/// as aliases named after their widths, e.g. `type U32 = number`, types follow the JSON
+/// representation of values chosen by the options.
+pub fn service_typescript_declarations(
+    service_name: &str,
+    interface: &FaaSModuleInterface<'_>,
+    options: &JsonOptions,
+) -> String {
+    let service = module_service(service_name, service_name, interface);
+    typescript_declarations(&[service], options)
+}
+
+/// Renders interfaces of all modules of a FaaS as TypeScript declarations, records shared
+/// by several modules are declared once.
+pub fn faas_typescript_declarations(
+    interface: &FaaSInterface<'_>,
+    options: &JsonOptions,
+) -> String {
+    typescript_declarations(&faas_services(interface), options)
+}
+
+/// Renders exported functions of a service as TypeScript declarations of one interface.
+pub(crate) fn exports_typescript_declarations(
+    service_name: &str,
+    interface: &FaaSInterface<'_>,
+    exports: &[ServiceExport],
+    options: &JsonOptions,
+) -> String {
+    let service = exports_service(service_name, service_name, interface, exports);
+    typescript_declarations(&[service], options)
+}
+
+/// Renders a module interface as Aqua `data` definitions for records and a `service`
+/// definition with the given default service id. Fails on functions that can't be
+/// declared in Aqua: ones with several results or with anyref values.
+pub fn service_aqua_declarations(
+    service_name: &str,
+    service_id: &str,
+    interface: &FaaSModuleInterface<'_>,
+) -> Result<String> {
+    let service = module_service(service_name, service_id, interface);
+    aqua_declarations(&[service])
+}
+
+/// Renders interfaces of all modules of a FaaS as Aqua definitions, records shared
+/// by several modules are defined once and module names are used as service ids.
+pub fn faas_aqua_declarations(interface: &FaaSInterface<'_>) -> Result<String> {
+    aqua_declarations(&faas_services(interface))
+}
+
+/// Renders exported functions of a service as Aqua definitions of one service.
+pub(crate) fn exports_aqua_declarations(
+    service_name: &str,
+    service_id: &str,
+    interface: &FaaSInterface<'_>,
+    exports: &[ServiceExport],
+) -> Result<String> {
+    let service = exports_service(service_name, service_id, interface, exports);
+    aqua_declarations(&[service])
+}
+
+fn module_service<'i>(
+    name: &'i str,
+    id: &'i str,
+    interface: &'i FaaSModuleInterface<'_>,
+) -> DeclaredService<'i> {
+    DeclaredService {
+        name,
+        id,
+        records: module_records(interface),
+        functions: module_functions(interface),
+    }
+}
+
+/// Modules are declared in the order of their names, each one is a service
+/// with the module name as its id.
+fn faas_services<'i>(interface: &'i FaaSInterface<'_>) -> Vec<DeclaredService<'i>> {
+    let mut modules = interface.modules.iter().collect::<Vec<_>>();
+    modules.sort_by_key(|(name, _)| **name);
+
+    let mut record_names = HashSet::new();
+    modules
+        .into_iter()
+        .map(|(name, interface)| {
+            let mut service = module_service(name, name, interface);
+            service
+                .records
+                .retain(|declared| record_names.insert(declared.record.name.as_str()));
+            service
+        })
+        .collect()
+}
+
+/// Exported functions are declared under their public names along with records
+/// used by them, records with the same name are declared once.
+fn exports_service<'i>(
+    name: &'i str,
+    id: &'i str,
+    interface: &'i FaaSInterface<'_>,
+    exports: &'i [ServiceExport],
+) -> DeclaredService<'i> {
+    let functions = exports
+        .iter()
+        // exports are checked on the service creation stage
+        .filter_map(|export| {
+            let (module_interface, signature) = exported_function(interface, export)?;
+            Some(DeclaredFunction {
+                name: export.public_name(),
+                signature,
+                record_types: module_interface.record_types,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut record_names = HashSet::new();
+    let mut records = Vec::new();
+    for function in functions.iter() {
+        let mut record_ids = Vec::new();
+        for ty in function
+            .signature
+            .arguments
+            .iter()
+            .map(|arg| &arg.ty)
+            .chain(function.signature.outputs.iter())
+        {
+            collect_record_ids(ty, function.record_types, &mut record_ids);
+        }
+        record_ids.sort_unstable();
+
+        for declared in declared_records(record_ids, function.record_types) {
+            if record_names.insert(declared.record.name.as_str()) {
+                records.push(declared);
+            }
+        }
+    }
+
+    DeclaredService {
+        name,
+        id,
+        records,
+        functions,
+    }
+}
+
+/// Records are sorted by their ids, so inner records precede records containing them.
+fn module_records<'i>(interface: &'i FaaSModuleInterface<'_>) -> Vec<DeclaredRecord<'i>> {
+    let mut record_ids = interface.record_types.keys().copied().collect::<Vec<_>>();
+    record_ids.sort_unstable();
+
+    declared_records(record_ids, interface.record_types)
+}
+
+fn declared_records(record_ids: Vec<u64>, record_types: &RecordTypes) -> Vec<DeclaredRecord<'_>> {
+    record_ids
+        .into_iter()
+        .filter_map(|id| record_types.get(&id))
+        .map(|record| DeclaredRecord {
+            record,
+            record_types,
+        })
+        .collect()
+}
+
+fn module_functions<'i>(interface: &'i FaaSModuleInterface<'_>) -> Vec<DeclaredFunction<'i>> {
+    interface
+        .function_signatures
+        .iter()
+        .map(|signature| DeclaredFunction {
+            name: signature.name.as_str(),
+            signature,
+            record_types: interface.record_types,
+        })
+        .collect()
+}
+
+fn typescript_declarations(services: &[DeclaredService<'_>], options: &JsonOptions) -> String {
+    let mut used_types = Vec::new();
+    let mut declarations = String::new();
+    for service in services {
+        for record in service.records.iter() {
+            declarations.push_str(&typescript_record(record, options, &mut used_types));
+        }
+        declarations.push_str(&typescript_service(service, options, &mut used_types));
+    }
+
+    typescript_aliases(&used_types, options) + &declarations
+}
+
+fn typescript_record(
+    declared: &DeclaredRecord<'_>,
+    options: &JsonOptions,
+    used_types: &mut Vec<&'static str>,
+) -> String {
+    let mut declaration = format!("export interface {} {{\n", declared.record.name);
+    for field in declared.record.fields.iter() {
+        declaration.push_str(&format!(
+            "    {}: {};\n",
+            field.name,
+            typescript_type(&field.ty, declared.record_types, options, used_types)
+        ));
+    }
+    declaration.push_str("}\n\n");
+
+    declaration
+}
+
+fn typescript_service(
+    service: &DeclaredService<'_>,
+    options: &JsonOptions,
+    used_types: &mut Vec<&'static str>,
+) -> String {
+    let mut declaration = format!("export interface {} {{\n", pascal_case(service.name));
+    for function in service.functions.iter() {
+        let record_types = function.record_types;
+        let arguments = function
+            .signature
+            .arguments
+            .iter()
+            .map(|arg| {
+                let ty = typescript_argument_type(&arg.ty, record_types, options, used_types);
+                format!("{}: {}", arg.name, ty)
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let output = match function.signature.outputs.as_slice() {
+            [] => String::from("void"),
+            [ty] => typescript_type(ty, record_types, options, used_types),
+            types => {
+                let types = types
+                    .iter()
+                    .map(|ty| typescript_type(ty, record_types, options, used_types))
+                    .collect::<Vec<_>>();
+                format!("[{}]", types.join(", "))
+            }
+        };
+
+        declaration.push_str(&format!(
+            "    {}({}): {};\n",
+            function.name, arguments, output
+        ));
+    }
+    declaration.push_str("}\n");
+
+    declaration
+}
+
+/// Byte arrays are accepted as arguments both as arrays of numbers and as encoded strings
+/// regardless of options, other arguments have the same types as results.
+fn typescript_argument_type(
+    ty: &IType,
+    record_types: &RecordTypes,
+    options: &JsonOptions,
+    used_types: &mut Vec<&'static str>,
+) -> String {
+    match ty {
+        IType::Array(value_type) if **value_type == IType::U8 => format!(
+            "{}[] | string",
+            typescript_type(value_type, record_types, options, used_types)
+        ),
+        ty => typescript_type(ty, record_types, options, used_types),
+    }
+}
+
+/// Converts a type to a TypeScript type of results.
+fn typescript_type(
+    ty: &IType,
+    record_types: &RecordTypes,
+    options: &JsonOptions,
+    used_types: &mut Vec<&'static str>,
+) -> String {
+    if let Some(numeric_type) = numeric_type(ty) {
+        if !used_types.contains(&numeric_type) {
+            used_types.push(numeric_type);
+        }
+        return numeric_type.to_string();
+    }
+
+    match ty {
+        // encoded byte arrays are returned as strings
+        IType::Array(value_type)
+            if **value_type == IType::U8
+                && options.byte_array_encoding != ByteArrayEncoding::Array =>
+        {
+            String::from("string")
+        }
+        IType::Array(value_type) => format!(
+            "{}[]",
+            typescript_type(value_type, record_types, options, used_types)
+        ),
+        IType::Record(record_id) => record_name(*record_id, record_types),
+        IType::Anyref => String::from("unknown"),
+        // numeric types are converted above
+        _ => String::from("string"),
+    }
+}
+
+/// Numeric aliases are numbers except 64-bit integers passed as decimal strings.
+fn typescript_aliases(used_types: &[&'static str], options: &JsonOptions) -> String {
+    let mut aliases = NUMERIC_TYPES
+        .iter()
+        .filter(|numeric_type| used_types.contains(numeric_type))
+        .map(|&numeric_type| {
+            let ts_type = match numeric_type {
+                "I64" | "U64" if options.int64_as_string => "string",
+                _ => "number",
+            };
+            format!("export type {} = {};\n", numeric_type, ts_type)
+        })
+        .collect::<String>();
+
+    if !aliases.is_empty() {
+        aliases.push('\n');
+    }
+
+    aliases
+}
+
+fn aqua_declarations(services: &[DeclaredService<'_>]) -> Result<String> {
+    let mut declarations = String::new();
+    for service in services {
+        for record in service.records.iter() {
+            declarations.push_str(&aqua_record(record)?);
+        }
+        declarations.push_str(&aqua_service(service)?);
+    }
+
+    Ok(declarations)
+}
+
+fn aqua_record(declared: &DeclaredRecord<'_>) -> Result<String> {
+    let record = declared.record;
+    let mut definition = format!("data {}:\n", record.name);
+    for field in record.fields.iter() {
+        let ty = aqua_type(&field.ty, declared.record_types).ok_or_else(|| {
+            AppServiceError::InvalidAquaDeclaration(format!(
+                "field {} of record {} has type {} that can't be declared in Aqua",
+                field.name,
+                record.name,
+                itype_text_view(&field.ty, declared.record_types)
+            ))
+        })?;
+        definition.push_str(&format!("  {}: {}\n", field.name, ty));
+    }
+    definition.push('\n');
+
+    Ok(definition)
+}
+
+fn aqua_service(service: &DeclaredService<'_>) -> Result<String> {
+    let name = pascal_case(service.name);
+    let mut definition = format!("service {}({:?}):\n", name, service.id);
+    for function in service.functions.iter() {
+        let record_types = function.record_types;
+        let aqua_type = |ty: &IType| {
+            aqua_type(ty, record_types).ok_or_else(|| {
+                AppServiceError::InvalidAquaDeclaration(format!(
+                    "function {} uses type {} that can't be declared in Aqua",
+                    function.name,
+                    itype_text_view(ty, record_types)
+                ))
+            })
+        };
+
+        let arguments = function
+            .signature
+            .arguments
+            .iter()
+            .map(|arg| aqua_type(&arg.ty).map(|ty| format!("{}: {}", arg.name, ty)))
+            .collect::<Result<Vec<_>>>()?
+            .join(", ");
+
+        definition.push_str(&format!("  {}({})", function.name, arguments));
+        match function.signature.outputs.as_slice() {
+            [] => {}
+            [ty] => definition.push_str(&format!(" -> {}", aqua_type(ty)?)),
+            types => {
+                return Err(AppServiceError::InvalidAquaDeclaration(format!(
+                    "function {} has {} results, but Aqua functions return at most one",
+                    function.name,
+                    types.len()
+                )))
+            }
+        }
+        definition.push('\n');
+    }
+
+    Ok(definition)
+}
+
+/// Converts a type to an Aqua type, anyref values have no Aqua counterpart.
+fn aqua_type(ty: &IType, record_types: &RecordTypes) -> Option<String> {
+    if let Some(numeric_type) = numeric_type(ty) {
+        return Some(numeric_type.to_lowercase());
+    }
+
+    match ty {
+        IType::Array(value_type) => {
+            aqua_type(value_type, record_types).map(|value_type| format!("[]{}", value_type))
+        }
+        IType::Record(record_id) => Some(record_name(*record_id, record_types)),
+        IType::Anyref => None,
+        // numeric types are converted above
+        _ => Some(String::from("string")),
+    }
+}
+
+const NUMERIC_TYPES: &[&str] = &[
+    "I8", "I16", "I32", "I64", "U8", "U16", "U32", "U64", "F32", "F64",
+];
+
+/// Returns a width-named type for numeric types, IT signed integers (S* and I*)
+/// are both signed integers of the corresponding width.
+fn numeric_type(ty: &IType) -> Option<&'static str> {
+    let numeric_type = match ty {
+        IType::S8 => "I8",
+        IType::S16 => "I16",
+        IType::S32 | IType::I32 => "I32",
+        IType::S64 | IType::I64 => "I64",
+        IType::U8 => "U8",
+        IType::U16 => "U16",
+        IType::U32 => "U32",
+        IType::U64 => "U64",
+        IType::F32 => "F32",
+        IType::F64 => "F64",
+        _ => return None,
+    };
+
+    Some(numeric_type)
+}
+
+fn record_name(record_id: u64, record_types: &RecordTypes) -> String {
+    // record types are checked by the interface types parser
+    record_types
+        .get(&record_id)
+        .map(|record| record.name.clone())
+        .unwrap_or_default()
+}
+
+fn pascal_case(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
@@ -19,6 +19,7 @@ use fluence_faas::FaaSError;
 use std::io::Error as IOError;
 use std::error::Error;
 use std::path::PathBuf;
+use std::time::Duration;
 
 #[derive(Debug)]
 pub enum AppServiceError {
@@ -36,6 +37,50 @@ pub enum AppServiceError {
 
     /// Errors related to malformed config.
     ConfigParseError(String),
+
+    /// Called function isn't a part of the public interface of the service.
+    FunctionNotExported(String),
+
+    /// A module can't be unloaded because its functions are exported by the service.
+    ModuleExported(String),
+
+    /// A lifecycle hook of a module has failed.
+    LifecycleHookError {
+        module_name: String,
+        hook: &'static str,
+        reason: String,
+    },
+
+    /// A lifecycle hook of a module hasn't finished in time.
+    LifecycleHookTimeout {
+        module_name: String,
+        hook: &'static str,
+        timeout: Duration,
+    },
+
+    /// A lifecycle hook of a module has panicked, FaaS of the service has been lost with it.
+    LifecycleHookPanic {
+        module_name: String,
+        hook: &'static str,
+    },
+
+    /// The service can't be used anymore after a lifecycle hook has timed out or panicked.
+    ServicePoisoned(String),
+
+    /// A service with such id doesn't exist in the service manager.
+    NoSuchService(String),
+
+    /// A service with such id has been already created by the service manager.
+    DuplicateServiceId(String),
+
+    /// A module with such hash isn't found in the modules dir of the service manager.
+    ModuleNotFound(String),
+
+    /// The registry of services can't be loaded or saved.
+    ServiceRegistryError(String),
+
+    /// The service interface can't be declared in Aqua.
+    InvalidAquaDeclaration(String),
 }
 
 impl Error for AppServiceError {}
@@ -50,6 +95,49 @@ impl std::fmt::Display for AppServiceError {
                 write!(f, "Failed to create dir {:?}: {:?}", path, err)
             }
             AppServiceError::ConfigParseError(err_msg) => write!(f, "{}", err_msg),
+            AppServiceError::FunctionNotExported(func_name) => {
+                write!(f, "function `{}` isn't exported by the service", func_name)
+            }
+            AppServiceError::ModuleExported(module_name) => write!(
+                f,
+                "module {} can't be unloaded, its functions are exported by the service",
+                module_name
+            ),
+            AppServiceError::LifecycleHookError {
+                module_name,
+                hook,
+                reason,
+            } => write!(
+                f,
+                "{} hook of module {} has failed: {}",
+                hook, module_name, reason
+            ),
+            AppServiceError::LifecycleHookTimeout {
+                module_name,
+                hook,
+                timeout,
+            } => write!(
+                f,
+                "{} hook of module {} hasn't finished in {:?}",
+                hook, module_name, timeout
+            ),
+            AppServiceError::LifecycleHookPanic { module_name, hook } => {
+                write!(f, "{} hook of module {} has panicked", hook, module_name)
+            }
+            AppServiceError::ServicePoisoned(reason) => {
+                write!(f, "service can't be used anymore: {}", reason)
+            }
+            AppServiceError::NoSuchService(service_id) => {
+                write!(f, "service with id {} doesn't exist", service_id)
+            }
+            AppServiceError::DuplicateServiceId(service_id) => {
+                write!(f, "service with id {} already exists", service_id)
+            }
+            AppServiceError::ModuleNotFound(hash) => {
+                write!(f, "module with hash {} isn't found", hash)
+            }
+            AppServiceError::ServiceRegistryError(err_msg) => write!(f, "{}", err_msg),
+            AppServiceError::InvalidAquaDeclaration(err_msg) => write!(f, "{}", err_msg),
         }
     }
 }
@@ -24,36 +24,87 @@
     unreachable_patterns
 )]
 
+mod blueprint;
 mod config;
+mod declarations;
 mod errors;
+mod lifecycle;
 mod service;
 mod service_interface;
+mod service_manager;
+mod service_pool;
 mod raw_toml_config;
 
 pub(crate) type Result<T> = std::result::Result<T, AppServiceError>;
 
 pub use errors::AppServiceError;
 pub use service::AppService;
+pub use service_pool::AppServicePool;
+pub use service_manager::AppServiceManager;
+pub use service_manager::FailedService;
+pub use service_manager::ServiceInfo;
+pub use blueprint::Blueprint;
+pub use blueprint::BlueprintModule;
 pub use service_interface::FunctionSignature;
 pub use service_interface::RecordType;
 pub use service_interface::ServiceInterface;
+pub use declarations::service_typescript_declarations;
+pub use declarations::service_aqua_declarations;
+pub use declarations::faas_typescript_declarations;
+pub use declarations::faas_aqua_declarations;
 
 pub use config::AppServiceConfig;
+pub use config::ServicePoolConfig;
+pub use config::ServiceManagerConfig;
+pub use config::ServiceExport;
+pub use config::LifecycleHooks;
 pub use raw_toml_config::TomlAppServiceConfig;
+pub use raw_toml_config::TomlServicePoolConfig;
+pub use raw_toml_config::TomlServiceExport;
+pub use raw_toml_config::TomlLifecycleHooks;
 
 pub use fluence_faas::FaaSConfig;
 pub use fluence_faas::FaaSModuleConfig;
 pub use fluence_faas::FaaSWASIConfig;
+pub use fluence_faas::PureFunctions;
+pub use fluence_faas::JsonOptions;
+pub use fluence_faas::ByteArrayEncoding;
 pub use fluence_faas::TomlFaaSConfig;
 pub use fluence_faas::TomlFaaSModuleConfig;
 pub use fluence_faas::TomlFaaSNamedModuleConfig;
 pub use fluence_faas::TomlWASIConfig;
+pub use fluence_faas::TomlWasmFeatures;
+pub use fluence_faas::WasmFeatures;
+pub use fluence_faas::FeatureViolation;
 pub use fluence_faas::from_toml_faas_config;
 pub use fluence_faas::from_toml_module_config;
 pub use fluence_faas::from_toml_named_module_config;
 pub use fluence_faas::from_toml_wasi_config;
+pub use fluence_faas::from_toml_wasm_features;
 
 pub use fluence_faas::FaaSError;
+pub use fluence_faas::FaaSInterface;
+pub use fluence_faas::FaaSModuleInterface;
+pub use fluence_faas::JsonArgumentError;
+pub use fluence_faas::JsonArgumentErrorKind;
+pub use fluence_faas::JsonKind;
+pub use fluence_faas::FCEError;
+pub use fluence_faas::CallContext;
+pub use fluence_faas::CallInterceptor;
+pub use fluence_faas::JournalEntry;
+pub use fluence_faas::JournalArguments;
+pub use fluence_faas::JournalResult;
+pub use fluence_faas::JournalValue;
+pub use fluence_faas::HostResponse;
+pub use fluence_faas::Divergence;
+pub use fluence_faas::ReplayDivergence;
+pub use fluence_faas::ReplayReport;
+pub use fluence_faas::MemoizationStats;
+pub use fluence_faas::ModuleStore;
+pub use fluence_faas::StoredModule;
+pub use fluence_faas::BinaryEncoding;
+
+pub use fluence_faas::module_json_schema;
 
 pub use fluence_faas::IValue;
 pub use fluence_faas::IRecordType;
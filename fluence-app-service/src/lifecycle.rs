@@ -0,0 +1,180 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::Result;
+use crate::AppServiceError;
+use crate::config::LifecycleHooks;
+
+use fluence_faas::FaaSConfig;
+use fluence_faas::FaaSInterface;
+use fluence_faas::FluenceFaaS;
+use fluence_faas::IType;
+use fluence_faas::IValue;
+
+use std::collections::HashMap;
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// Lifecycle events of a service module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Hook {
+    Init,
+    Upgrade,
+    Shutdown,
+}
+
+impl Hook {
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Hook::Init => "on_init",
+            Hook::Upgrade => "on_upgrade",
+            Hook::Shutdown => "on_shutdown",
+        }
+    }
+
+    /// Returns a name of the export function handling this event, if it's declared.
+    pub(crate) fn function(self, hooks: &LifecycleHooks) -> Option<&str> {
+        match self {
+            Hook::Init => hooks.on_init.as_deref(),
+            Hook::Upgrade => hooks.on_upgrade.as_deref(),
+            Hook::Shutdown => hooks.on_shutdown.as_deref(),
+        }
+    }
+
+    fn argument_types(self) -> Vec<IType> {
+        match self {
+            Hook::Init | Hook::Upgrade => vec![IType::String],
+            Hook::Shutdown => vec![],
+        }
+    }
+}
+
+/// Checks that all declared hooks are export functions with suitable signatures.
+pub(crate) fn check_hooks(
+    faas_interface: &FaaSInterface<'_>,
+    lifecycle_hooks: &[LifecycleHooks],
+) -> Result<()> {
+    for hooks in lifecycle_hooks {
+        for &hook in [Hook::Init, Hook::Upgrade, Hook::Shutdown].iter() {
+            let function_name = match hook.function(hooks) {
+                Some(function_name) => function_name,
+                None => continue,
+            };
+
+            let signature = faas_interface
+                .modules
+                .get(hooks.module_name.as_str())
+                .and_then(|module_interface| {
+                    module_interface
+                        .function_signatures
+                        .iter()
+                        .find(|signature| signature.name.as_str() == function_name)
+                })
+                .ok_or_else(|| {
+                    AppServiceError::InvalidConfig(format!(
+                        "{} hook {} isn't exported by module {}",
+                        hook.name(),
+                        function_name,
+                        hooks.module_name
+                    ))
+                })?;
+
+            let argument_types = signature
+                .arguments
+                .iter()
+                .map(|arg| arg.ty.clone())
+                .collect::<Vec<_>>();
+            if argument_types != hook.argument_types() {
+                return Err(AppServiceError::InvalidConfig(format!(
+                    "{} hook {} of module {} should have arguments {:?}, but it has {:?}",
+                    hook.name(),
+                    function_name,
+                    hooks.module_name,
+                    hook.argument_types(),
+                    argument_types
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Calls a hook on a separate thread and waits for it no longer than the timeout.
+/// If the hook has timed out, FaaS stays with the hook thread, because Wasm execution
+/// can't be interrupted, and the supplied one is replaced with FaaS without modules,
+/// so the service owning it can't be used anymore. The same happens if the hook thread
+/// has panicked, see faas_lost.
+pub(crate) fn call_hook(
+    faas: &mut FluenceFaaS,
+    hooks: &LifecycleHooks,
+    hook: Hook,
+    arguments: Vec<IValue>,
+    timeout: Duration,
+) -> Result<()> {
+    let function_name = match hook.function(hooks) {
+        Some(function_name) => function_name.to_string(),
+        None => return Ok(()),
+    };
+    let module_name = hooks.module_name.clone();
+    let mut hook_faas = std::mem::replace(faas, empty_faas()?);
+
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        let result = hook_faas
+            .call_with_ivalues(&module_name, &function_name, &arguments, <_>::default())
+            .map(|_| ())
+            .map_err(|e| e.to_string());
+        // the receiver is dropped if the hook has timed out
+        let _ = sender.send((hook_faas, result));
+    });
+
+    let hook_error = |reason| AppServiceError::LifecycleHookError {
+        module_name: hooks.module_name.clone(),
+        hook: hook.name(),
+        reason,
+    };
+
+    match receiver.recv_timeout(timeout) {
+        Ok((hook_faas, result)) => {
+            *faas = hook_faas;
+            result.map_err(hook_error)
+        }
+        Err(mpsc::RecvTimeoutError::Timeout) => Err(AppServiceError::LifecycleHookTimeout {
+            module_name: hooks.module_name.clone(),
+            hook: hook.name(),
+            timeout,
+        }),
+        Err(mpsc::RecvTimeoutError::Disconnected) => Err(AppServiceError::LifecycleHookPanic {
+            module_name: hooks.module_name.clone(),
+            hook: hook.name(),
+        }),
+    }
+}
+
+/// Returns true if FaaS hasn't been returned by the hook thread after this error.
+pub(crate) fn faas_lost(error: &AppServiceError) -> bool {
+    matches!(
+        error,
+        AppServiceError::LifecycleHookTimeout { .. } | AppServiceError::LifecycleHookPanic { .. }
+    )
+}
+
+/// Creates FaaS without modules, it takes place of FaaS that is being used by a hook.
+fn empty_faas() -> Result<FluenceFaaS> {
+    FluenceFaaS::with_modules::<FaaSConfig>(HashMap::new(), FaaSConfig::default())
+        .map_err(Into::into)
+}
@@ -17,23 +17,110 @@
 use crate::Result;
 use crate::AppServiceError;
 use crate::config::AppServiceConfig;
+use crate::config::LifecycleHooks;
+use crate::config::ServiceExport;
+use crate::config::ServicePoolConfig;
 
 use fluence_faas::TomlFaaSConfig;
 use fluence_faas::from_toml_faas_config;
 use serde_derive::Serialize;
 use serde_derive::Deserialize;
 
+use std::collections::HashSet;
 use std::convert::TryInto;
 use std::path::PathBuf;
+use std::time::Duration;
+
+const DEFAULT_HOOK_TIMEOUT_MS: u64 = 5000;
+
+/*
+An example of the facade declaration and exports, exports make up the public interface
+of the service (by default it consists of all functions of the facade module). Configs
+without a facade use the last module as the facade, but this fallback is deprecated:
+
+facade = "records_pure"
+
+[[export]]
+    module = "records_pure"
+    function = "invoke"
+
+[[export]]
+    module = "records_effector"
+    function = "mutate_struct"
+    alias = "mutate"
+
+An example of lifecycle hooks, hooks are export functions of the module with
+signatures on_init(config_json: String), on_upgrade(old_version: String) and on_shutdown():
+
+[[lifecycle]]
+    module = "storage"
+    on_init = "init"
+    on_upgrade = "migrate"
+    on_shutdown = "flush"
+    timeout_ms = 1000
+
+    [lifecycle.init_config]
+    db_path = "/tmp/storage.db"
+
+An example of the pool section (it's used only by AppServicePool,
+and all modules of the service should be declared as stateless):
+
+[pool]
+    size = 4
+    warm_up = true
+    recycle_after_calls = 1000
+ */
 
 #[derive(Deserialize, Serialize, Debug, Clone, Default)]
 pub struct TomlAppServiceConfig {
     pub service_base_dir: Option<String>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub facade: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub export: Vec<TomlServiceExport>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub lifecycle: Vec<TomlLifecycleHooks>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pool: Option<TomlServicePoolConfig>,
+
     #[serde(flatten)]
     pub toml_faas_config: TomlFaaSConfig,
 }
 
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct TomlServiceExport {
+    pub module: String,
+    pub function: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct TomlLifecycleHooks {
+    pub module: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_init: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub init_config: Option<toml::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_upgrade: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_shutdown: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct TomlServicePoolConfig {
+    pub size: Option<usize>,
+    pub warm_up: Option<bool>,
+    pub recycle_after_calls: Option<usize>,
+}
+
 impl TomlAppServiceConfig {
     /// Load config from filesystem.
     pub fn load<P: Into<PathBuf>>(path: P) -> Result<Self> {
@@ -55,10 +142,132 @@ impl TryInto<AppServiceConfig> for TomlAppServiceConfig {
             // use tmp dir for service base dir if it isn't defined
             None => std::env::temp_dir(),
         };
+        let pool_config = self.pool.map(from_toml_pool_config).transpose()?;
+
+        let module_names = faas_config
+            .modules_config
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+        check_facade(self.facade.as_deref(), &module_names)?;
+        let exports = from_toml_exports(self.export, &module_names)?;
+        let lifecycle_hooks = self
+            .lifecycle
+            .into_iter()
+            .map(|hooks| from_toml_lifecycle_hooks(hooks, &module_names))
+            .collect::<Result<Vec<_>>>()?;
 
         Ok(AppServiceConfig {
             service_base_dir,
             faas_config,
+            facade: self.facade,
+            exports,
+            lifecycle_hooks,
+            pool_config,
         })
     }
 }
+
+fn check_facade(facade: Option<&str>, module_names: &[&str]) -> Result<()> {
+    match facade {
+        Some(facade) if !module_names.contains(&facade) => Err(AppServiceError::InvalidConfig(
+            format!("facade module {} isn't declared in the config", facade),
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn from_toml_exports(
+    exports: Vec<TomlServiceExport>,
+    module_names: &[&str],
+) -> Result<Vec<ServiceExport>> {
+    let mut public_names = HashSet::new();
+
+    exports
+        .into_iter()
+        .map(|export| {
+            if !module_names.contains(&export.module.as_str()) {
+                return Err(AppServiceError::InvalidConfig(format!(
+                    "module {} of exported function {} isn't declared in the config",
+                    export.module, export.function
+                )));
+            }
+
+            let export = ServiceExport {
+                module_name: export.module,
+                function_name: export.function,
+                alias: export.alias,
+            };
+            if !public_names.insert(export.public_name().to_string()) {
+                return Err(AppServiceError::InvalidConfig(format!(
+                    "function {} is exported more than once",
+                    export.public_name()
+                )));
+            }
+
+            Ok(export)
+        })
+        .collect()
+}
+
+fn from_toml_lifecycle_hooks(
+    hooks: TomlLifecycleHooks,
+    module_names: &[&str],
+) -> Result<LifecycleHooks> {
+    if !module_names.contains(&hooks.module.as_str()) {
+        return Err(AppServiceError::InvalidConfig(format!(
+            "module {} with lifecycle hooks isn't declared in the config",
+            hooks.module
+        )));
+    }
+
+    let init_config = match &hooks.init_config {
+        Some(init_config) => serde_json::to_string(init_config).map_err(|e| {
+            AppServiceError::InvalidConfig(format!(
+                "init_config of module {} can't be converted to JSON: {}",
+                hooks.module, e
+            ))
+        })?,
+        None => String::from("{}"),
+    };
+
+    let timeout_ms = hooks.timeout_ms.unwrap_or(DEFAULT_HOOK_TIMEOUT_MS);
+    if timeout_ms == 0 {
+        return Err(AppServiceError::InvalidConfig(format!(
+            "timeout of lifecycle hooks of module {} should be greater than zero",
+            hooks.module
+        )));
+    }
+
+    Ok(LifecycleHooks {
+        module_name: hooks.module,
+        on_init: hooks.on_init,
+        init_config,
+        on_upgrade: hooks.on_upgrade,
+        on_shutdown: hooks.on_shutdown,
+        timeout: Duration::from_millis(timeout_ms),
+    })
+}
+
+fn from_toml_pool_config(config: TomlServicePoolConfig) -> Result<ServicePoolConfig> {
+    let default = ServicePoolConfig::default();
+
+    let size = config.size.unwrap_or(default.size);
+    if size == 0 {
+        return Err(AppServiceError::InvalidConfig(String::from(
+            "service pool size should be greater than zero",
+        )));
+    }
+
+    if config.recycle_after_calls == Some(0) {
+        return Err(AppServiceError::InvalidConfig(String::from(
+            "recycle_after_calls should be greater than zero",
+        )));
+    }
+
+    Ok(ServicePoolConfig {
+        size,
+        warm_up: config.warm_up.unwrap_or(default.warm_up),
+        recycle_after_calls: config.recycle_after_calls,
+    })
+}
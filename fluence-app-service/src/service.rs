@@ -16,25 +16,59 @@
 
 use crate::Result;
 use crate::config::AppServiceConfig;
+use crate::config::LifecycleHooks;
+use crate::config::ServiceExport;
+use crate::lifecycle::call_hook;
+use crate::lifecycle::check_hooks;
+use crate::lifecycle::faas_lost;
+use crate::lifecycle::Hook;
+use crate::service_interface::check_record_conflicts;
 use crate::service_interface::ServiceInterface;
 use super::AppServiceError;
 
+use fluence_faas::BinaryEncoding;
+use fluence_faas::CallInterceptor;
+use fluence_faas::FaaSInterface;
 use fluence_faas::FluenceFaaS;
+use fluence_faas::IValue;
+use fluence_faas::JsonOptions;
+use fluence_faas::JSON_SCHEMA_DRAFT;
+use fluence_faas::MemoizationStats;
+use fluence_faas::ReplayReport;
 use serde_json::Value as JValue;
 
 use std::convert::TryInto;
+use std::sync::Arc;
+use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::path::Path;
 use std::path::PathBuf;
 use std::io::ErrorKind;
+use std::time::Duration;
+use std::time::Instant;
 
 const SERVICE_ID_ENV_NAME: &str = "service_id";
 const SERVICE_LOCAL_DIR_NAME: &str = "local";
 const SERVICE_TMP_DIR_NAME: &str = "tmp";
+const INSTANCE_ID_ENV_NAME: &str = "instance_id";
+const SERVICE_INSTANCES_DIR_NAME: &str = "instances";
+/// Bounds the time of all on_shutdown hooks called on drop of a service.
+const SHUTDOWN_ON_DROP_TIMEOUT: Duration = Duration::from_millis(1000);
 
 pub struct AppService {
     faas: FluenceFaaS,
+    service_id: String,
     facade_module_name: String,
+    json_options: JsonOptions,
+
+    /// Functions that make up the public interface of the service.
+    exports: Vec<ServiceExport>,
+
+    lifecycle_hooks: Vec<LifecycleHooks>,
+
+    /// Set if a lifecycle hook has timed out or panicked, the service can't be used after that.
+    poisoned: Option<String>,
 }
 
 impl AppService {
@@ -45,30 +79,137 @@ impl AppService {
         S: Into<String>,
         AppServiceError: From<C::Error>,
     {
-        let mut config: AppServiceConfig = config.try_into()?;
-        let facade_module_name = config
-            .faas_config
-            .modules_config
-            .last()
-            .ok_or_else(|| {
-                AppServiceError::ConfigParseError(String::from(
-                    "config should contain at least one module",
-                ))
-            })?
-            .0
-            .clone();
+        Self::with_modules_impl(
+            None,
+            config.try_into()?,
+            service_id.into(),
+            None,
+            envs,
+            true,
+        )
+    }
 
-        let service_id = service_id.into();
-        Self::set_env_and_dirs(&mut config, service_id, envs)?;
+    /// Create Service with given module bytes by module names instead of loading them
+    /// from modules_dir of the config.
+    pub fn with_modules<C, S>(
+        modules: HashMap<String, Vec<u8>>,
+        config: C,
+        service_id: S,
+        envs: HashMap<Vec<u8>, Vec<u8>>,
+    ) -> Result<Self>
+    where
+        C: TryInto<AppServiceConfig>,
+        S: Into<String>,
+        AppServiceError: From<C::Error>,
+    {
+        Self::with_modules_impl(
+            Some(modules),
+            config.try_into()?,
+            service_id.into(),
+            None,
+            envs,
+            true,
+        )
+    }
 
-        let faas = FluenceFaaS::with_raw_config(config.faas_config)?;
+    /// Recreate a service created before with the same modules and service id,
+    /// its directories are kept, and on_init hooks aren't called again.
+    pub(crate) fn restore_with_modules(
+        modules: HashMap<String, Vec<u8>>,
+        config: AppServiceConfig,
+        service_id: String,
+        envs: HashMap<Vec<u8>, Vec<u8>>,
+    ) -> Result<Self> {
+        Self::with_modules_impl(Some(modules), config, service_id, None, envs, false)
+    }
+
+    /// Create an instance of a pooled service, each instance has its own directories
+    /// and gets its id in the instance_id environment variable.
+    pub(crate) fn new_instance(
+        config: AppServiceConfig,
+        service_id: String,
+        instance_id: usize,
+        envs: HashMap<Vec<u8>, Vec<u8>>,
+    ) -> Result<Self> {
+        Self::with_modules_impl(None, config, service_id, Some(instance_id), envs, true)
+    }
+
+    fn with_modules_impl(
+        modules: Option<HashMap<String, Vec<u8>>>,
+        mut config: AppServiceConfig,
+        service_id: String,
+        instance_id: Option<usize>,
+        envs: HashMap<Vec<u8>, Vec<u8>>,
+        call_on_init: bool,
+    ) -> Result<Self> {
+        let facade_module_name = match config.facade.take() {
+            Some(facade_module_name) => facade_module_name,
+            None => {
+                let facade_module_name = config
+                    .faas_config
+                    .modules_config
+                    .last()
+                    .ok_or_else(|| {
+                        AppServiceError::ConfigParseError(String::from(
+                            "config should contain at least one module",
+                        ))
+                    })?
+                    .0
+                    .clone();
+                log::warn!(
+                    "service {} doesn't declare a facade, the last module {} is used as the facade, \
+                     this fallback is deprecated",
+                    service_id,
+                    facade_module_name
+                );
+                facade_module_name
+            }
+        };
+        let exports = std::mem::take(&mut config.exports);
+        let lifecycle_hooks = std::mem::take(&mut config.lifecycle_hooks);
+        let json_options = config.faas_config.json_options;
+
+        Self::set_env_and_dirs(&mut config, service_id.clone(), instance_id, envs)?;
+
+        let mut faas = match modules {
+            Some(modules) => FluenceFaaS::with_modules(modules, config.faas_config)?,
+            None => FluenceFaaS::with_raw_config(config.faas_config)?,
+        };
+        let exports = resolve_exports(&faas, &facade_module_name, exports)?;
+        init_modules(&mut faas, &lifecycle_hooks, call_on_init)?;
 
         Ok(Self {
             faas,
+            service_id,
             facade_module_name,
+            json_options,
+            exports,
+            lifecycle_hooks,
+            poisoned: None,
         })
     }
 
+    /// Run on_shutdown hooks of modules in the reverse order and destroy the service,
+    /// hooks are also run on drop within SHUTDOWN_ON_DROP_TIMEOUT, but their errors
+    /// are only logged there.
+    pub fn shutdown(mut self) -> Result<()> {
+        self.check_poisoned()?;
+        let lifecycle_hooks = std::mem::take(&mut self.lifecycle_hooks);
+
+        let mut result = Ok(());
+        for hooks in lifecycle_hooks.iter().rev() {
+            let hook_result =
+                call_hook(&mut self.faas, hooks, Hook::Shutdown, vec![], hooks.timeout);
+            if matches!(&hook_result, Err(e) if faas_lost(e)) {
+                // FaaS has been left to the hook thread
+                return hook_result;
+            }
+            result = result.and(hook_result);
+        }
+
+        result
+    }
+
     /// Call a specified function of loaded module by its name.
     // TODO: replace serde_json::Value with Vec<u8>?
     pub fn call<S: AsRef<str>>(
@@ -77,44 +218,306 @@ impl AppService {
         arguments: JValue,
         call_parameters: crate::CallParameters,
     ) -> Result<JValue> {
+        self.check_poisoned()?;
+        let export = find_export(&self.exports, func_name.as_ref())?;
+
         self.faas
             .call_with_json(
-                &self.facade_module_name,
-                func_name,
+                &export.module_name,
+                &export.function_name,
+                arguments,
+                call_parameters,
+            )
+            .map_err(Into::into)
+    }
+
+    /// Call a function from the public interface of the service by its public name using
+    /// the given options of JSON mapping instead of the ones from the config.
+    pub fn call_with_options<S: AsRef<str>>(
+        &mut self,
+        func_name: S,
+        arguments: JValue,
+        call_parameters: crate::CallParameters,
+        json_options: JsonOptions,
+    ) -> Result<JValue> {
+        self.check_poisoned()?;
+        let export = find_export(&self.exports, func_name.as_ref())?;
+
+        self.faas
+            .call_with_json_options(
+                &export.module_name,
+                &export.function_name,
+                arguments,
+                call_parameters,
+                json_options,
+            )
+            .map_err(Into::into)
+    }
+
+    /// Call a specified function of the facade module with arguments encoded in MessagePack
+    /// or CBOR, results are returned in the same encoding.
+    pub fn call_with_binary<S: AsRef<str>>(
+        &mut self,
+        func_name: S,
+        encoding: BinaryEncoding,
+        arguments: &[u8],
+        call_parameters: crate::CallParameters,
+    ) -> Result<Vec<u8>> {
+        self.check_poisoned()?;
+        let export = find_export(&self.exports, func_name.as_ref())?;
+
+        self.faas
+            .call_with_binary(
+                &export.module_name,
+                &export.function_name,
+                encoding,
                 arguments,
                 call_parameters,
             )
             .map_err(Into::into)
     }
 
-    /// Return interface (function signatures and record types) of this service.
+    /// Register an interceptor of calls of this service, including nested calls between
+    /// its modules.
+    pub fn add_interceptor(&mut self, interceptor: Arc<dyn CallInterceptor>) {
+        self.faas.add_interceptor(interceptor);
+    }
+
+    /// Start recording calls of this service to a journal file.
+    pub fn start_journal<P: AsRef<Path>>(&mut self, journal_path: P) -> Result<()> {
+        self.check_poisoned()?;
+        self.faas.start_journal(journal_path).map_err(Into::into)
+    }
+
+    /// Stop recording calls to the journal.
+    pub fn stop_journal(&mut self) {
+        self.faas.stop_journal()
+    }
+
+    /// Re-execute calls from a journal and report divergences from the recorded behaviour.
+    pub fn replay_journal<P: AsRef<Path>>(&mut self, journal_path: P) -> Result<ReplayReport> {
+        self.check_poisoned()?;
+        self.faas.replay_journal(journal_path).map_err(Into::into)
+    }
+
+    /// Return statistics of memoization of pure functions of this service.
+    pub fn memoization_stats(&self) -> MemoizationStats {
+        self.faas.memoization_stats()
+    }
+
+    /// Render metrics of this service in the Prometheus text exposition format.
+    pub fn prometheus_metrics(&self) -> String {
+        self.faas.prometheus_metrics()
+    }
+
+    /// Return interface (function signatures and record types) of this service,
+    /// it consists only of exported functions and records used by them.
     pub fn get_interface(&self) -> ServiceInterface {
-        use crate::service_interface::into_service_interface;
+        use crate::service_interface::into_public_interface;
 
-        let faas_facade_interface = self
+        into_public_interface(self.faas.get_interface(), &self.exports)
+    }
+
+    /// Return JSON Schema of arguments and results of functions of this service
+    /// as they are accepted and returned by call.
+    pub fn json_schema(&self) -> Result<JValue> {
+        let mut module_schemas = HashMap::new();
+        let mut schema = serde_json::json!({
+            "$schema": JSON_SCHEMA_DRAFT,
+            "title": self.facade_module_name,
+            "type": "object",
+            "properties": {},
+            "additionalProperties": false,
+            "definitions": { "arguments": {}, "results": {}, "functions": {} },
+        });
+
+        for export in self.exports.iter() {
+            let module_schema = match module_schemas.entry(export.module_name.as_str()) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(self.faas.json_schema(&export.module_name)?),
+            };
+
+            for kind in ["arguments", "results"].iter() {
+                let definitions = module_schema["definitions"][*kind].as_object();
+                let service_definitions = schema["definitions"][*kind].as_object_mut();
+                if let (Some(definitions), Some(service_definitions)) =
+                    (definitions, service_definitions)
+                {
+                    for (name, definition) in definitions {
+                        service_definitions
+                            .entry(name.clone())
+                            .or_insert_with(|| definition.clone());
+                    }
+                }
+            }
+
+            let public_name = export.public_name();
+            schema["definitions"]["functions"][public_name] =
+                module_schema["definitions"]["functions"][&export.function_name].clone();
+            schema["properties"][public_name] = serde_json::json!({
+                "$ref": format!("#/definitions/functions/{}", public_name)
+            });
+        }
+
+        Ok(schema)
+    }
+
+    /// Return TypeScript declarations of records and functions of this service,
+    /// types follow JSON options of the service.
+    pub fn typescript_declarations(&self) -> String {
+        use crate::declarations::exports_typescript_declarations;
+
+        exports_typescript_declarations(
+            &self.facade_module_name,
+            &self.faas.get_interface(),
+            &self.exports,
+            &self.json_options,
+        )
+    }
+
+    /// Return Aqua data and service definitions of this service, the service id
+    /// is the default id of the service definition.
+    pub fn aqua_declarations(&self) -> Result<String> {
+        use crate::declarations::exports_aqua_declarations;
+
+        exports_aqua_declarations(
+            &self.facade_module_name,
+            &self.service_id,
+            &self.faas.get_interface(),
+            &self.exports,
+        )
+    }
+
+    /// Load a new module into the service, its on_init hook isn't called.
+    pub fn load_module<S, C>(&mut self, name: S, wasm_bytes: &[u8], config: Option<C>) -> Result<()>
+    where
+        S: Into<String>,
+        C: TryInto<crate::FaaSModuleConfig>,
+        fluence_faas::FaaSError: From<C::Error>,
+    {
+        self.check_poisoned()?;
+        self.faas
+            .load_module(name, wasm_bytes, config)
+            .map_err(Into::into)
+    }
+
+    /// Unload a module, its on_shutdown hook is called before. Modules with exported
+    /// functions can't be unloaded.
+    pub fn unload_module<S: AsRef<str>>(&mut self, module_name: S) -> Result<()> {
+        self.check_poisoned()?;
+        let module_name = module_name.as_ref();
+        if self
+            .exports
+            .iter()
+            .any(|export| export.module_name == module_name)
+        {
+            return Err(AppServiceError::ModuleExported(module_name.to_string()));
+        }
+        self.call_module_hooks(module_name, Hook::Shutdown, vec![])?;
+
+        self.faas.unload_module(module_name).map_err(Into::into)
+    }
+
+    /// Replace a module with a new version. The new version is created and checked
+    /// against hooks and exports of the service first, then on_shutdown hook of the old
+    /// version is called, and on_upgrade of the new one is called with the old version
+    /// after the replacement.
+    pub fn upgrade_module<S, C>(
+        &mut self,
+        name: S,
+        wasm_bytes: &[u8],
+        config: Option<C>,
+        old_version: &str,
+    ) -> Result<()>
+    where
+        S: Into<String>,
+        C: TryInto<crate::FaaSModuleConfig>,
+        fluence_faas::FaaSError: From<C::Error>,
+    {
+        self.check_poisoned()?;
+        let name = name.into();
+        let module = self
             .faas
-            .get_interface()
+            .prepare_module(name.as_str(), wasm_bytes, config)?;
+
+        let mut faas_interface = self.faas.get_interface();
+        if !faas_interface.modules.contains_key(name.as_str()) {
+            return Err(fluence_faas::FaaSError::NoSuchModule(name).into());
+        }
+        faas_interface
             .modules
-            .remove(self.facade_module_name.as_str())
-            // facade module must be loaded in FaaS, so unwrap is safe here
-            .unwrap();
+            .insert(name.as_str(), module.interface());
+        let module_hooks = self
+            .lifecycle_hooks
+            .iter()
+            .filter(|hooks| hooks.module_name == name)
+            .cloned()
+            .collect::<Vec<_>>();
+        check_hooks(&faas_interface, &module_hooks)?;
+        check_exports(&faas_interface, &self.exports)?;
+
+        self.call_module_hooks(&name, Hook::Shutdown, vec![])?;
+        self.faas.replace_module(module)?;
+
+        let arguments = vec![IValue::String(old_version.to_string())];
+        self.call_module_hooks(&name, Hook::Upgrade, arguments)
+    }
 
-        into_service_interface(faas_facade_interface)
+    /// Calls hooks of a module, the service is poisoned if a hook has timed out or panicked.
+    fn call_module_hooks(
+        &mut self,
+        module_name: &str,
+        hook: Hook,
+        arguments: Vec<IValue>,
+    ) -> Result<()> {
+        for hooks in self.lifecycle_hooks.iter() {
+            if hooks.module_name != module_name {
+                continue;
+            }
+
+            let result = call_hook(
+                &mut self.faas,
+                hooks,
+                hook,
+                arguments.clone(),
+                hooks.timeout,
+            );
+            if let Err(err) = &result {
+                if faas_lost(err) {
+                    // FaaS has been left to the hook thread
+                    self.poisoned = Some(err.to_string());
+                }
+            }
+            result?;
+        }
+
+        Ok(())
+    }
+
+    fn check_poisoned(&self) -> Result<()> {
+        match &self.poisoned {
+            Some(reason) => Err(AppServiceError::ServicePoisoned(reason.clone())),
+            None => Ok(()),
+        }
     }
 
     /// Prepare service before starting by:
     ///  1. creating a directory structure in the following form:
     ///     - service_base_dir/service_id/SERVICE_LOCAL_DIR_NAME
     ///     - service_base_dir/service_id/SERVICE_TMP_DIR_NAME
-    ///  2. adding service_id to environment variables
+    ///
+    ///     instances of pooled services use service_base_dir/service_id/instances/instance_id
+    ///     instead of service_base_dir/service_id
+    ///  2. adding service_id and instance_id to environment variables
     fn set_env_and_dirs(
         config: &mut AppServiceConfig,
         service_id: String,
+        instance_id: Option<usize>,
         mut envs: HashMap<Vec<u8>, Vec<u8>>,
     ) -> Result<()> {
         use maplit::hashmap;
 
-        let create = |dir: &PathBuf| match std::fs::create_dir(dir) {
+        let create = |dir: &PathBuf| match std::fs::create_dir_all(dir) {
             Err(e) if e.kind() == ErrorKind::AlreadyExists => Ok(()),
             Err(err) => Err(AppServiceError::CreateDir {
                 err,
@@ -124,7 +527,16 @@ impl AppService {
         };
 
         let base_dir = &config.service_base_dir;
-        let service_dir = base_dir.join(&service_id);
+        let mut service_dir = base_dir.join(&service_id);
+        if let Some(instance_id) = instance_id {
+            service_dir = service_dir
+                .join(SERVICE_INSTANCES_DIR_NAME)
+                .join(instance_id.to_string());
+            envs.insert(
+                INSTANCE_ID_ENV_NAME.as_bytes().to_vec(),
+                instance_id.to_string().into_bytes(),
+            );
+        }
         create(&service_dir)?;
 
         let local_dir = service_dir.join(SERVICE_LOCAL_DIR_NAME);
@@ -159,6 +571,125 @@ impl AppService {
     }
 }
 
+impl Drop for AppService {
+    fn drop(&mut self) {
+        if self.poisoned.is_some() {
+            return;
+        }
+
+        // all hooks share one timeout, so dropping a service doesn't block for long
+        let deadline = Instant::now() + SHUTDOWN_ON_DROP_TIMEOUT;
+        for hooks in self.lifecycle_hooks.iter().rev() {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining == Duration::from_secs(0) {
+                log::warn!(
+                    "on_shutdown hooks haven't finished in {:?} on drop, the rest are skipped",
+                    SHUTDOWN_ON_DROP_TIMEOUT
+                );
+                break;
+            }
+
+            let timeout = remaining.min(hooks.timeout);
+            match call_hook(&mut self.faas, hooks, Hook::Shutdown, vec![], timeout) {
+                Ok(()) => {}
+                Err(e) if faas_lost(&e) => {
+                    log::warn!("{}", e);
+                    // FaaS has been left to the hook thread
+                    break;
+                }
+                Err(e) => log::warn!("{}", e),
+            }
+        }
+    }
+}
+
+/// Checks declared hooks and runs on_init hooks of modules in the declaration order,
+/// if the service is created for the first time.
+fn init_modules(
+    faas: &mut FluenceFaaS,
+    lifecycle_hooks: &[LifecycleHooks],
+    call_on_init: bool,
+) -> Result<()> {
+    check_hooks(&faas.get_interface(), lifecycle_hooks)?;
+    if !call_on_init {
+        return Ok(());
+    }
+
+    for hooks in lifecycle_hooks {
+        let arguments = vec![IValue::String(hooks.init_config.clone())];
+        call_hook(faas, hooks, Hook::Init, arguments, hooks.timeout)?;
+    }
+
+    Ok(())
+}
+
+/// Checks exports of the service, all functions of the facade module are exported
+/// if exports aren't declared.
+fn resolve_exports(
+    faas: &FluenceFaaS,
+    facade_module_name: &str,
+    exports: Vec<ServiceExport>,
+) -> Result<Vec<ServiceExport>> {
+    let faas_interface = faas.get_interface();
+
+    if exports.is_empty() {
+        let exports = faas_interface
+            .modules
+            .get(facade_module_name)
+            .map(|module_interface| {
+                module_interface
+                    .function_signatures
+                    .iter()
+                    .map(|signature| ServiceExport {
+                        module_name: facade_module_name.to_string(),
+                        function_name: signature.name.to_string(),
+                        alias: None,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        return Ok(exports);
+    }
+
+    check_exports(&faas_interface, &exports)?;
+
+    Ok(exports)
+}
+
+/// Checks that all exported functions are present in modules and records used by them
+/// don't conflict.
+fn check_exports(faas_interface: &FaaSInterface<'_>, exports: &[ServiceExport]) -> Result<()> {
+    for export in exports.iter() {
+        let is_present = faas_interface
+            .modules
+            .get(export.module_name.as_str())
+            .map(|module_interface| {
+                module_interface
+                    .function_signatures
+                    .iter()
+                    .any(|signature| signature.name.as_str() == export.function_name)
+            })
+            .unwrap_or(false);
+
+        if !is_present {
+            return Err(AppServiceError::InvalidConfig(format!(
+                "module {} doesn't have exported function {}",
+                export.module_name, export.function_name
+            )));
+        }
+    }
+
+    check_record_conflicts(faas_interface, exports)
+}
+
+fn find_export<'e>(exports: &'e [ServiceExport], func_name: &str) -> Result<&'e ServiceExport> {
+    exports
+        .iter()
+        .find(|export| export.public_name() == func_name)
+        .ok_or_else(|| AppServiceError::FunctionNotExported(func_name.to_string()))
+}
+
 // This API is intended for testing purposes (mostly in FCE REPL)
 #[cfg(feature = "raw-module-api")]
 impl AppService {
@@ -173,14 +704,22 @@ impl AppService {
         AppServiceError: From<C::Error>,
     {
         let mut config: AppServiceConfig = config.try_into()?;
+        let lifecycle_hooks = std::mem::take(&mut config.lifecycle_hooks);
+        let json_options = config.faas_config.json_options;
         let service_id = service_id.into();
-        Self::set_env_and_dirs(&mut config, service_id, envs)?;
+        Self::set_env_and_dirs(&mut config, service_id.clone(), None, envs)?;
 
-        let faas = FluenceFaaS::with_raw_config(config.faas_config)?;
+        let mut faas = FluenceFaaS::with_raw_config(config.faas_config)?;
+        init_modules(&mut faas, &lifecycle_hooks, true)?;
 
         Ok(Self {
             faas,
+            service_id,
             facade_module_name: String::new(),
+            json_options,
+            exports: vec![],
+            lifecycle_hooks,
+            poisoned: None,
         })
     }
 
@@ -191,26 +730,12 @@ impl AppService {
         arguments: JValue,
         call_parameters: crate::CallParameters,
     ) -> Result<JValue> {
+        self.check_poisoned()?;
         self.faas
             .call_with_json(module_name, func_name, arguments, call_parameters)
             .map_err(Into::into)
     }
 
-    pub fn load_module<S, C>(&mut self, name: S, wasm_bytes: &[u8], config: Option<C>) -> Result<()>
-    where
-        S: Into<String>,
-        C: TryInto<crate::FaaSModuleConfig>,
-        fluence_faas::FaaSError: From<C::Error>,
-    {
-        self.faas
-            .load_module(name, &wasm_bytes, config)
-            .map_err(Into::into)
-    }
-
-    pub fn unload_module<S: AsRef<str>>(&mut self, module_name: S) -> Result<()> {
-        self.faas.unload_module(module_name).map_err(Into::into)
-    }
-
     /// Return raw interface of the underlying [[FluenceFaaS]] instance
     pub fn get_full_interface(&self) -> fluence_faas::FaaSInterface<'_> {
         self.faas.get_interface()
@@ -14,15 +14,21 @@
  * limitations under the License.
  */
 
+use crate::AppServiceError;
+use crate::Result;
+use crate::config::ServiceExport;
+
+use fluence_faas::FaaSInterface;
 use fluence_faas::FaaSModuleInterface;
 use fluence_faas::FaaSFunctionSignature;
 use fluence_faas::IRecordType;
+use fluence_faas::IType;
 use fluence_faas::RecordTypes;
 use fluence_faas::itype_text_view;
 
 use serde::Serialize;
 
-use std::rc::Rc;
+use std::sync::Arc;
 
 #[derive(Serialize)]
 pub struct FunctionSignature {
@@ -44,6 +50,12 @@ pub struct ServiceInterface {
     pub record_types: Vec<RecordType>,
 }
 
+impl<'a> From<FaaSModuleInterface<'a>> for ServiceInterface {
+    fn from(faas_interface: FaaSModuleInterface<'a>) -> Self {
+        into_service_interface(faas_interface)
+    }
+}
+
 pub(crate) fn into_service_interface(faas_interface: FaaSModuleInterface<'_>) -> ServiceInterface {
     let record_types = faas_interface.record_types;
 
@@ -64,6 +76,132 @@ pub(crate) fn into_service_interface(faas_interface: FaaSModuleInterface<'_>) ->
     }
 }
 
+/// Builds an interface consisting of exported functions under their public names
+/// and records used by them, records are distinguished by names across modules,
+/// so records with the same names must be the same, see check_record_conflicts.
+pub(crate) fn into_public_interface(
+    faas_interface: FaaSInterface<'_>,
+    exports: &[ServiceExport],
+) -> ServiceInterface {
+    let mut function_signatures = Vec::with_capacity(exports.len());
+    let mut record_types: Vec<RecordType> = Vec::new();
+
+    for export in exports {
+        let (module_interface, signature) = match exported_function(&faas_interface, export) {
+            Some(exported_function) => exported_function,
+            // exports are checked on the service creation stage
+            None => continue,
+        };
+
+        for record in used_records(module_interface, signature) {
+            if record_types.iter().all(|known| known.name != record.name) {
+                record_types.push(record);
+            }
+        }
+
+        let mut signature =
+            serialize_function_signature(signature.clone(), module_interface.record_types);
+        signature.name = export.public_name().to_string();
+        function_signatures.push(signature);
+    }
+
+    ServiceInterface {
+        function_signatures,
+        record_types,
+    }
+}
+
+/// Checks that records with the same name used by exported functions of different
+/// modules have the same fields, otherwise they can't be told apart in the public
+/// interface of the service.
+pub(crate) fn check_record_conflicts(
+    faas_interface: &FaaSInterface<'_>,
+    exports: &[ServiceExport],
+) -> Result<()> {
+    let mut known_records: Vec<(&str, RecordType)> = Vec::new();
+
+    for export in exports {
+        let (module_interface, signature) = match exported_function(faas_interface, export) {
+            Some(exported_function) => exported_function,
+            None => continue,
+        };
+
+        for record in used_records(module_interface, signature) {
+            match known_records
+                .iter()
+                .find(|(_, known)| known.name == record.name)
+            {
+                Some((module_name, known)) if known.fields != record.fields => {
+                    return Err(AppServiceError::InvalidConfig(format!(
+                        "record {} is defined differently in modules {} and {}, \
+                         so functions using it can't be exported together",
+                        record.name, module_name, export.module_name
+                    )));
+                }
+                Some(_) => {}
+                None => known_records.push((export.module_name.as_str(), record)),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) fn exported_function<'i, 'a>(
+    faas_interface: &'i FaaSInterface<'a>,
+    export: &ServiceExport,
+) -> Option<(&'i FaaSModuleInterface<'a>, &'i FaaSFunctionSignature)> {
+    let module_interface = faas_interface.modules.get(export.module_name.as_str())?;
+    let signature = module_interface
+        .function_signatures
+        .iter()
+        .find(|signature| signature.name.as_str() == export.function_name)?;
+
+    Some((module_interface, signature))
+}
+
+/// Returns records used by arguments and results of the function.
+fn used_records(
+    module_interface: &FaaSModuleInterface<'_>,
+    signature: &FaaSFunctionSignature,
+) -> Vec<RecordType> {
+    let mut record_ids = Vec::new();
+    for ty in signature
+        .arguments
+        .iter()
+        .map(|arg| &arg.ty)
+        .chain(signature.outputs.iter())
+    {
+        collect_record_ids(ty, module_interface.record_types, &mut record_ids);
+    }
+
+    record_ids
+        .into_iter()
+        .filter_map(|id| {
+            module_interface.record_types.get(&id).map(|record| {
+                serialize_record_type(id, record.clone(), module_interface.record_types)
+            })
+        })
+        .collect()
+}
+
+/// Collects ids of records used by the type including records of their fields.
+pub(crate) fn collect_record_ids(ty: &IType, record_types: &RecordTypes, record_ids: &mut Vec<u64>) {
+    match ty {
+        IType::Array(value_type) => collect_record_ids(value_type, record_types, record_ids),
+        IType::Record(record_id) if !record_ids.contains(record_id) => {
+            record_ids.push(*record_id);
+
+            if let Some(record) = record_types.get(record_id) {
+                for field in record.fields.iter() {
+                    collect_record_ids(&field.ty, record_types, record_ids);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
 fn serialize_function_signature(
     signature: FaaSFunctionSignature,
     record_types: &RecordTypes,
@@ -89,7 +227,7 @@ fn serialize_function_signature(
 
 fn serialize_record_type(
     id: u64,
-    record: Rc<IRecordType>,
+    record: Arc<IRecordType>,
     record_types: &RecordTypes,
 ) -> RecordType {
     let fields = record
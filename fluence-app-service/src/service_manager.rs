@@ -0,0 +1,358 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::Result;
+use crate::AppService;
+use crate::AppServiceError;
+use crate::blueprint::Blueprint;
+use crate::config::AppServiceConfig;
+use crate::config::ServiceManagerConfig;
+use crate::service_interface::ServiceInterface;
+
+use fluence_faas::ModuleStore;
+use fluence_faas::TomlFaaSNamedModuleConfig;
+use serde_derive::Deserialize;
+use serde_derive::Serialize;
+use serde_json::Value as JValue;
+
+use std::collections::HashMap;
+use std::convert::TryInto;
+use std::io::ErrorKind;
+use std::io::Write;
+use std::path::Path;
+
+/// Information about a service created by the manager.
+#[derive(Debug, Clone, Copy)]
+pub struct ServiceInfo<'m> {
+    pub service_id: &'m str,
+    pub blueprint: &'m Blueprint,
+}
+
+/// A service from the registry that couldn't be recreated on start, it's kept
+/// in the registry until it's removed.
+#[derive(Debug)]
+pub struct FailedService {
+    pub service_id: String,
+    pub blueprint: Blueprint,
+    pub error: AppServiceError,
+}
+
+struct ManagedService {
+    blueprint: Blueprint,
+    service: AppService,
+}
+
+/// Persisted list of services used to recover them after restart, module configs
+/// of each service are kept in its blueprint, since the module store shares them.
+#[derive(Deserialize, Serialize, Debug, Default)]
+struct Registry {
+    services: Vec<RegistryRecord>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct RegistryRecord {
+    service_id: String,
+    blueprint: Blueprint,
+}
+
+/// Creates services from blueprints and keeps them by service ids, created services
+/// are persisted to the registry file and recreated by a new manager with the same config.
+pub struct AppServiceManager {
+    config: ServiceManagerConfig,
+    module_store: ModuleStore,
+    services: HashMap<String, ManagedService>,
+    failed_services: Vec<FailedService>,
+}
+
+impl AppServiceManager {
+    /// Create a manager and recover services from the registry file if it exists,
+    /// services that can't be recovered are skipped and reported by failed_services.
+    pub fn new(config: ServiceManagerConfig) -> Result<Self> {
+        let service_base_dir = &config.service_base_dir;
+        std::fs::create_dir_all(service_base_dir).map_err(|err| AppServiceError::CreateDir {
+            err,
+            path: service_base_dir.clone(),
+        })?;
+        let registry = load_registry(&config.registry_path)?;
+
+        let mut manager = Self {
+            module_store: ModuleStore::new(&config.modules_dir),
+            config,
+            services: HashMap::new(),
+            failed_services: Vec::new(),
+        };
+
+        for record in registry.services {
+            match manager.instantiate(&record.service_id, &record.blueprint, true) {
+                Ok(service) => {
+                    let managed_service = ManagedService {
+                        blueprint: record.blueprint,
+                        service,
+                    };
+                    manager.services.insert(record.service_id, managed_service);
+                }
+                Err(error) => {
+                    log::warn!(
+                        "service {} can't be recovered: {}",
+                        record.service_id,
+                        error
+                    );
+                    manager.failed_services.push(FailedService {
+                        service_id: record.service_id,
+                        blueprint: record.blueprint,
+                        error,
+                    });
+                }
+            }
+        }
+
+        Ok(manager)
+    }
+
+    /// Create a new service from the blueprint and persist it to the registry.
+    pub fn create_service<S: Into<String>>(
+        &mut self,
+        service_id: S,
+        blueprint: Blueprint,
+    ) -> Result<()> {
+        let service_id = service_id.into();
+        check_name("service id", &service_id)?;
+        if self.services.contains_key(&service_id) || self.failed_position(&service_id).is_some() {
+            return Err(AppServiceError::DuplicateServiceId(service_id));
+        }
+
+        let service = self.instantiate(&service_id, &blueprint, false)?;
+        let managed_service = ManagedService { blueprint, service };
+        self.services.insert(service_id, managed_service);
+
+        self.save_registry()
+    }
+
+    /// Call a function of a service with the given id.
+    pub fn call<S: AsRef<str>, FN: AsRef<str>>(
+        &mut self,
+        service_id: S,
+        func_name: FN,
+        arguments: JValue,
+        call_parameters: crate::CallParameters,
+    ) -> Result<JValue> {
+        self.service_mut(service_id)?
+            .call(func_name, arguments, call_parameters)
+    }
+
+    /// Return a service with the given id.
+    pub fn service_mut<S: AsRef<str>>(&mut self, service_id: S) -> Result<&mut AppService> {
+        let service_id = service_id.as_ref();
+
+        self.services
+            .get_mut(service_id)
+            .map(|managed_service| &mut managed_service.service)
+            .ok_or_else(|| AppServiceError::NoSuchService(service_id.to_string()))
+    }
+
+    /// Return interface of a service with the given id.
+    pub fn get_interface<S: AsRef<str>>(&self, service_id: S) -> Result<ServiceInterface> {
+        let service_id = service_id.as_ref();
+
+        self.services
+            .get(service_id)
+            .map(|managed_service| managed_service.service.get_interface())
+            .ok_or_else(|| AppServiceError::NoSuchService(service_id.to_string()))
+    }
+
+    /// Return all services sorted by their ids.
+    pub fn list_services(&self) -> Vec<ServiceInfo<'_>> {
+        let mut services = self
+            .services
+            .iter()
+            .map(|(service_id, managed_service)| ServiceInfo {
+                service_id,
+                blueprint: &managed_service.blueprint,
+            })
+            .collect::<Vec<_>>();
+        services.sort_by_key(|info| info.service_id);
+
+        services
+    }
+
+    /// Return services from the registry that couldn't be recovered with their errors.
+    pub fn failed_services(&self) -> &[FailedService] {
+        &self.failed_services
+    }
+
+    /// Remove a service: run its shutdown hooks, remove it from the registry
+    /// and delete its directory in service_base_dir. Errors of shutdown hooks are
+    /// returned after the service has been removed. Services that couldn't be recovered
+    /// are removed the same way, but without hooks.
+    pub fn remove_service<S: AsRef<str>>(&mut self, service_id: S) -> Result<()> {
+        let service_id = service_id.as_ref();
+
+        let shutdown_result = match self.services.remove(service_id) {
+            Some(managed_service) => managed_service.service.shutdown(),
+            None => {
+                let position = self
+                    .failed_position(service_id)
+                    .ok_or_else(|| AppServiceError::NoSuchService(service_id.to_string()))?;
+                self.failed_services.remove(position);
+                Ok(())
+            }
+        };
+
+        self.save_registry()?;
+
+        let service_dir = self.config.service_base_dir.join(service_id);
+        match std::fs::remove_dir_all(&service_dir) {
+            Err(e) if e.kind() != ErrorKind::NotFound => return Err(e.into()),
+            _ => {}
+        }
+
+        shutdown_result
+    }
+
+    fn failed_position(&self, service_id: &str) -> Option<usize> {
+        self.failed_services
+            .iter()
+            .position(|failed_service| failed_service.service_id == service_id)
+    }
+
+    /// Creates a service from the blueprint, restored services have been created before,
+    /// so their on_init hooks aren't called.
+    fn instantiate(
+        &self,
+        service_id: &str,
+        blueprint: &Blueprint,
+        restore: bool,
+    ) -> Result<AppService> {
+        let mut config = blueprint.config.clone();
+        config.service_base_dir = Some(self.config.service_base_dir.to_string_lossy().to_string());
+        config.toml_faas_config.modules_dir = None;
+        config.toml_faas_config.module_store_dir = None;
+
+        // modules are loaded in the order of the blueprint
+        let mut module_configs = std::mem::take(&mut config.toml_faas_config.module);
+        let mut modules = HashMap::new();
+        for module in blueprint.modules.iter() {
+            if !self.module_store.contains(&module.hash) {
+                return Err(AppServiceError::ModuleNotFound(module.hash.clone()));
+            }
+            let wasm = self.module_store.load_wasm(&module.hash)?;
+
+            // configs saved with modules in the store are shared by all services, so they
+            // aren't used, and modules without configs in the blueprint get the default one
+            let module_config = match module_configs.iter().position(|c| c.name == module.name) {
+                Some(position) => module_configs.remove(position),
+                None => TomlFaaSNamedModuleConfig::new(module.name.clone()),
+            };
+            // modules bytes are provided directly, so hashes from the config aren't needed
+            config
+                .toml_faas_config
+                .module
+                .push(TomlFaaSNamedModuleConfig {
+                    hash: None,
+                    ..module_config
+                });
+
+            modules.insert(module.name.clone(), wasm);
+        }
+
+        if let Some(module_config) = module_configs.first() {
+            return Err(AppServiceError::InvalidConfig(format!(
+                "module {} from config isn't a part of blueprint {}",
+                module_config.name, blueprint.name
+            )));
+        }
+
+        let config: AppServiceConfig = config.try_into()?;
+        if restore {
+            AppService::restore_with_modules(
+                modules,
+                config,
+                service_id.to_string(),
+                HashMap::new(),
+            )
+        } else {
+            AppService::with_modules(modules, config, service_id, HashMap::new())
+        }
+    }
+
+    fn save_registry(&self) -> Result<()> {
+        let mut services = self
+            .services
+            .iter()
+            .map(|(service_id, managed_service)| RegistryRecord {
+                service_id: service_id.clone(),
+                blueprint: managed_service.blueprint.clone(),
+            })
+            .chain(
+                self.failed_services
+                    .iter()
+                    .map(|failed_service| RegistryRecord {
+                        service_id: failed_service.service_id.clone(),
+                        blueprint: failed_service.blueprint.clone(),
+                    }),
+            )
+            .collect::<Vec<_>>();
+        services.sort_by(|lhs, rhs| lhs.service_id.cmp(&rhs.service_id));
+
+        let registry = serde_json::to_vec_pretty(&Registry { services })
+            .map_err(|e| AppServiceError::ServiceRegistryError(e.to_string()))?;
+
+        // the registry is replaced atomically to not be corrupted by a crash during writing
+        let registry_path = &self.config.registry_path;
+        let tmp_path = registry_path.with_extension("tmp");
+        let mut tmp_file = std::fs::File::create(&tmp_path)?;
+        tmp_file.write_all(&registry)?;
+        tmp_file.sync_all()?;
+        std::fs::rename(&tmp_path, registry_path)?;
+
+        // the rename itself is persisted by syncing the dir of the registry
+        let registry_dir = match registry_path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        std::fs::File::open(registry_dir)?.sync_all()?;
+
+        Ok(())
+    }
+}
+
+fn load_registry(registry_path: &Path) -> Result<Registry> {
+    let registry = match std::fs::read(registry_path) {
+        Ok(registry) => registry,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Registry::default()),
+        Err(e) => return Err(e.into()),
+    };
+
+    serde_json::from_slice(&registry).map_err(|e| {
+        AppServiceError::ServiceRegistryError(format!(
+            "registry {:?} is malformed: {}",
+            registry_path, e
+        ))
+    })
+}
+
+/// Service ids are used as dir names, so they are restricted to not escape service_base_dir.
+fn check_name(kind: &str, name: &str) -> Result<()> {
+    let is_allowed = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_';
+    if name.is_empty() || !name.chars().all(is_allowed) {
+        return Err(AppServiceError::InvalidConfig(format!(
+            "{} {} should be a non-empty string of alphanumeric characters, '-' and '_'",
+            kind, name
+        )));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,234 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::Result;
+use crate::AppService;
+use crate::AppServiceError;
+use crate::config::AppServiceConfig;
+use crate::config::ServicePoolConfig;
+use crate::raw_toml_config::TomlAppServiceConfig;
+use crate::service_interface::ServiceInterface;
+
+use parking_lot::Condvar;
+use parking_lot::Mutex;
+use serde_json::Value as JValue;
+
+use std::collections::HashMap;
+use std::convert::TryInto;
+
+struct PooledService {
+    service: AppService,
+    calls_count: usize,
+}
+
+/// Keeps several instances of the same stateless service and dispatches calls to a free one,
+/// so calls to the pool could be made concurrently from different threads. Each instance
+/// has its own local and tmp dirs and gets its id in the instance_id environment variable.
+pub struct AppServicePool {
+    /// Config used to create new instances of the service.
+    config: TomlAppServiceConfig,
+    service_id: String,
+    envs: HashMap<Vec<u8>, Vec<u8>>,
+    pool_config: ServicePoolConfig,
+
+    /// Instance slots, None means that an instance hasn't been created yet or has been recycled.
+    instances: Vec<Mutex<Option<PooledService>>>,
+
+    /// Ids of instances that don't serve any call at the moment.
+    free_instances: Mutex<Vec<usize>>,
+    instance_released: Condvar,
+}
+
+impl AppServicePool {
+    /// Create a pool of services with given config and service id, all modules of the service
+    /// should be declared as stateless.
+    pub fn new<S>(
+        config: TomlAppServiceConfig,
+        service_id: S,
+        envs: HashMap<Vec<u8>, Vec<u8>>,
+    ) -> Result<Self>
+    where
+        S: Into<String>,
+    {
+        let service_config: AppServiceConfig = config.clone().try_into()?;
+        check_stateless(&service_config)?;
+
+        let pool_config = service_config.pool_config.unwrap_or_default();
+        let instances = (0..pool_config.size).map(|_| Mutex::new(None)).collect();
+        // reversed to make instances with lower ids be taken first
+        let free_instances = Mutex::new((0..pool_config.size).rev().collect());
+
+        let pool = Self {
+            config,
+            service_id: service_id.into(),
+            envs,
+            pool_config,
+            instances,
+            free_instances,
+            instance_released: Condvar::new(),
+        };
+
+        if pool.pool_config.warm_up {
+            for (instance_id, instance) in pool.instances.iter().enumerate() {
+                *instance.lock() = Some(pool.create_instance(instance_id)?);
+            }
+        }
+
+        Ok(pool)
+    }
+
+    /// Call a specified function of the facade module on a free instance of the service,
+    /// blocks if all instances are busy.
+    pub fn call<S: AsRef<str>>(
+        &self,
+        func_name: S,
+        arguments: JValue,
+        call_parameters: crate::CallParameters,
+    ) -> Result<JValue> {
+        let instance_id = self.acquire_instance();
+        let _guard = ReleaseGuard {
+            pool: self,
+            instance_id,
+        };
+
+        let mut instance = self.instances[instance_id].lock();
+        if instance.is_none() {
+            *instance = Some(self.create_instance(instance_id)?);
+        }
+        // unwrap is safe here because the instance has been created just above
+        let pooled_service = instance.as_mut().unwrap();
+
+        let result = pooled_service
+            .service
+            .call(func_name, arguments, call_parameters);
+        pooled_service.calls_count += 1;
+
+        if self.should_be_recycled(pooled_service) {
+            log::debug!(
+                "instance {} of service {} is recycled after {} calls",
+                instance_id,
+                self.service_id,
+                pooled_service.calls_count
+            );
+            *instance = None;
+
+            if self.pool_config.warm_up {
+                match self.create_instance(instance_id) {
+                    Ok(pooled_service) => *instance = Some(pooled_service),
+                    // it'll be created again on the next call
+                    Err(e) => log::warn!(
+                        "failed to recreate instance {} of service {}: {}",
+                        instance_id,
+                        self.service_id,
+                        e
+                    ),
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Return interface (function signatures and record types) of this service.
+    pub fn get_interface(&self) -> Result<ServiceInterface> {
+        let instance_id = self.acquire_instance();
+        let _guard = ReleaseGuard {
+            pool: self,
+            instance_id,
+        };
+
+        let mut instance = self.instances[instance_id].lock();
+        if instance.is_none() {
+            *instance = Some(self.create_instance(instance_id)?);
+        }
+        // unwrap is safe here because the instance has been created just above
+        let pooled_service = instance.as_mut().unwrap();
+
+        Ok(pooled_service.service.get_interface())
+    }
+
+    /// Return maximum count of instances in this pool.
+    pub fn size(&self) -> usize {
+        self.pool_config.size
+    }
+
+    fn create_instance(&self, instance_id: usize) -> Result<PooledService> {
+        let service = AppService::new_instance(
+            self.config.clone().try_into()?,
+            self.service_id.clone(),
+            instance_id,
+            self.envs.clone(),
+        )?;
+
+        Ok(PooledService {
+            service,
+            calls_count: 0,
+        })
+    }
+
+    fn should_be_recycled(&self, pooled_service: &PooledService) -> bool {
+        match self.pool_config.recycle_after_calls {
+            Some(calls_count) => pooled_service.calls_count >= calls_count,
+            None => false,
+        }
+    }
+
+    fn acquire_instance(&self) -> usize {
+        let mut free_instances = self.free_instances.lock();
+        loop {
+            match free_instances.pop() {
+                Some(instance_id) => return instance_id,
+                None => self.instance_released.wait(&mut free_instances),
+            }
+        }
+    }
+
+    fn release_instance(&self, instance_id: usize) {
+        self.free_instances.lock().push(instance_id);
+        self.instance_released.notify_one();
+    }
+}
+
+/// Returns an instance to the pool even if a call has panicked.
+struct ReleaseGuard<'pool> {
+    pool: &'pool AppServicePool,
+    instance_id: usize,
+}
+
+impl Drop for ReleaseGuard<'_> {
+    fn drop(&mut self) {
+        self.pool.release_instance(self.instance_id);
+    }
+}
+
+fn check_stateless(config: &AppServiceConfig) -> Result<()> {
+    let stateful_modules = config
+        .faas_config
+        .modules_config
+        .iter()
+        .filter(|(_, module_config)| !module_config.stateless)
+        .map(|(module_name, _)| module_name.as_str())
+        .collect::<Vec<_>>();
+
+    if !stateful_modules.is_empty() {
+        return Err(AppServiceError::InvalidConfig(format!(
+            "service pool could be used only with modules declared as stateless, but {:?} aren't",
+            stateful_modules
+        )));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,184 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// every test crate uses only a part of these helpers
+#![allow(dead_code)]
+
+use fluence_app_service::AppServiceConfig;
+use fluence_app_service::FaaSConfig;
+use fluence_app_service::FaaSModuleConfig;
+
+use parity_wasm::builder;
+use parity_wasm::elements::BlockType;
+use parity_wasm::elements::Instruction;
+use parity_wasm::elements::Instructions;
+use parity_wasm::elements::ValueType;
+
+/// An export function of a test module, functions have no arguments.
+pub struct CoreFunction {
+    pub name: &'static str,
+    pub result: Option<ValueType>,
+    pub instructions: Vec<Instruction>,
+}
+
+/// Builds a module with a memory, a mutable i32 global and the given export functions,
+/// `wit` describes interface types of the module in the text form.
+pub fn test_module(functions: Vec<CoreFunction>, wit: &str) -> Vec<u8> {
+    let mut module = builder::module()
+        .memory()
+        .with_min(1)
+        .build()
+        .export()
+        .field("memory")
+        .internal()
+        .memory(0)
+        .build()
+        .global()
+        .value_type()
+        .i32()
+        .mutable()
+        .init_expr(Instruction::I32Const(0))
+        .build();
+
+    for (index, function) in functions.into_iter().enumerate() {
+        let mut instructions = function.instructions;
+        instructions.push(Instruction::End);
+
+        module = module
+            .function()
+            .signature()
+            .with_return_type(function.result)
+            .build()
+            .body()
+            .with_instructions(Instructions::new(instructions))
+            .build()
+            .build()
+            .export()
+            .field(function.name)
+            .internal()
+            .func(index as u32)
+            .build();
+    }
+
+    let mut module = module.build();
+
+    let buffer = wasmer_wit::decoders::wat::Buffer::new(wit)
+        .unwrap_or_else(|e| panic!("IT of the module should be valid: {:?}", e));
+    let interfaces = wasmer_wit::decoders::wat::parse(&buffer)
+        .unwrap_or_else(|e| panic!("IT of the module should be valid: {:?}", e));
+    let mut wit_bytes = vec![];
+    wasmer_wit::ToBytes::to_bytes(&interfaces, &mut wit_bytes)
+        .expect("IT of the module should be serializable");
+    module.set_custom_section("interface-types", wit_bytes);
+
+    parity_wasm::serialize(module).expect("module should be serializable")
+}
+
+/// Creates a config of a service consisting of modules with the given names
+/// and default module configs.
+pub fn test_service_config(module_names: &[&str]) -> AppServiceConfig {
+    let modules_config = module_names
+        .iter()
+        .map(|name| (name.to_string(), FaaSModuleConfig::default()))
+        .collect();
+
+    AppServiceConfig {
+        service_base_dir: std::env::temp_dir(),
+        faas_config: FaaSConfig {
+            modules_config,
+            ..<_>::default()
+        },
+        ..<_>::default()
+    }
+}
+
+/// IT of a module with hooks on_init(config: string), on_upgrade(old_version: string),
+/// on_shutdown(), spin() and a function counter() -> s32.
+const HOOKS_WIT: &str = r#"
+(@interface type (func (param $config: string)))        ;; 0
+(@interface type (func (param $config: string)))        ;; 1
+(@interface type (func (param $old_version: string)))   ;; 2
+(@interface type (func (param $old_version: string)))   ;; 3
+(@interface type (func ))                               ;; 4
+(@interface type (func ))                               ;; 5
+(@interface type (func ))                               ;; 6
+(@interface type (func ))                               ;; 7
+(@interface type (func (result s32)))                   ;; 8
+(@interface type (func (result s32)))                   ;; 9
+
+(@interface func (type 0)
+  call-core 0)
+(@interface func (type 2)
+  call-core 1)
+(@interface func (type 4)
+  call-core 2)
+(@interface func (type 6)
+  call-core 3)
+(@interface func (type 8)
+  call-core 4
+  s32.from_i32)
+
+(@interface export "on_init" (func 1))
+(@interface export "on_upgrade" (func 3))
+(@interface export "on_shutdown" (func 5))
+(@interface export "spin" (func 7))
+(@interface export "counter" (func 9))
+
+(@interface implement (func 1) (func 0))
+(@interface implement (func 3) (func 2))
+(@interface implement (func 5) (func 4))
+(@interface implement (func 7) (func 6))
+(@interface implement (func 9) (func 8))
+"#;
+
+/// Builds a module whose hooks add 1 (on_init), 10 (on_upgrade) and 100 (on_shutdown)
+/// to a counter, the spin hook never finishes.
+pub fn hooks_module() -> Vec<u8> {
+    use Instruction::*;
+
+    let add_to_counter = |value| CoreFunction {
+        name: "",
+        result: None,
+        instructions: vec![GetGlobal(0), I32Const(value), I32Add, SetGlobal(0)],
+    };
+
+    let functions = vec![
+        CoreFunction {
+            name: "on_init",
+            ..add_to_counter(1)
+        },
+        CoreFunction {
+            name: "on_upgrade",
+            ..add_to_counter(10)
+        },
+        CoreFunction {
+            name: "on_shutdown",
+            ..add_to_counter(100)
+        },
+        CoreFunction {
+            name: "spin",
+            result: None,
+            instructions: vec![Loop(BlockType::NoResult), Br(0), End],
+        },
+        CoreFunction {
+            name: "counter",
+            result: Some(ValueType::I32),
+            instructions: vec![GetGlobal(0)],
+        },
+    ];
+
+    test_module(functions, HOOKS_WIT)
+}
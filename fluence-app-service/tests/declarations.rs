@@ -0,0 +1,312 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use fluence_app_service::AppService;
+use fluence_app_service::AppServiceError;
+use fluence_app_service::ByteArrayEncoding;
+use fluence_app_service::FaaSModuleInterface;
+use fluence_app_service::JsonOptions;
+use fluence_app_service::TomlAppServiceConfig;
+use fluence_faas::FaaSFunctionSignature;
+use fluence_faas::IFunctionArg;
+use fluence_faas::IRecordType;
+use fluence_faas::IType;
+use fluence_faas::RecordTypes;
+use wasmer_wit::IRecordFieldType;
+use wasmer_wit::NEVec;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+fn signature(
+    name: &str,
+    arguments: &[(&str, IType)],
+    outputs: Vec<IType>,
+) -> FaaSFunctionSignature {
+    let arguments = arguments
+        .iter()
+        .map(|(name, ty)| IFunctionArg {
+            name: name.to_string(),
+            ty: ty.clone(),
+        })
+        .collect();
+
+    FaaSFunctionSignature {
+        name: Arc::new(name.to_string()),
+        arguments: Arc::new(arguments),
+        outputs: Arc::new(outputs),
+    }
+}
+
+fn record(name: &str, fields: &[(&str, IType)]) -> Arc<IRecordType> {
+    let fields = fields
+        .iter()
+        .map(|(name, ty)| IRecordFieldType {
+            name: name.to_string(),
+            ty: ty.clone(),
+        })
+        .collect();
+
+    Arc::new(IRecordType {
+        name: name.to_string(),
+        fields: NEVec::new(fields).expect("record has fields"),
+    })
+}
+
+fn test_record_types() -> RecordTypes {
+    let mut record_types = RecordTypes::new();
+    record_types.insert(
+        0,
+        record(
+            "Tag",
+            &[
+                ("weight", IType::F64),
+                (
+                    "links",
+                    IType::Array(Box::new(IType::Array(Box::new(IType::String)))),
+                ),
+            ],
+        ),
+    );
+    record_types.insert(
+        1,
+        record(
+            "User",
+            &[
+                ("name", IType::String),
+                ("age", IType::S8),
+                ("avatar", IType::Array(Box::new(IType::U8))),
+                ("tags", IType::Array(Box::new(IType::Record(0)))),
+            ],
+        ),
+    );
+
+    record_types
+}
+
+fn test_interface(record_types: &RecordTypes) -> FaaSModuleInterface<'_> {
+    FaaSModuleInterface {
+        record_types,
+        function_signatures: vec![
+            signature(
+                "get_user",
+                &[("id", IType::U64), ("with_tags", IType::I32)],
+                vec![IType::Record(1)],
+            ),
+            signature("clear", &[], vec![]),
+        ],
+    }
+}
+
+#[test]
+pub fn typescript_declarations() {
+    let record_types = test_record_types();
+    let declarations = fluence_app_service::service_typescript_declarations(
+        "user_list",
+        &test_interface(&record_types),
+        &JsonOptions::default(),
+    );
+
+    let right_declarations = r#"export type I8 = number;
+export type I32 = number;
+export type U8 = number;
+export type U64 = number;
+export type F64 = number;
+
+export interface Tag {
+    weight: F64;
+    links: string[][];
+}
+
+export interface User {
+    name: string;
+    age: I8;
+    avatar: U8[];
+    tags: Tag[];
+}
+
+export interface UserList {
+    get_user(id: U64, with_tags: I32): User;
+    clear(): void;
+}
+"#;
+
+    assert_eq!(declarations, right_declarations);
+}
+
+#[test]
+pub fn aqua_declarations() {
+    let record_types = test_record_types();
+    let declarations = fluence_app_service::service_aqua_declarations(
+        "user_list",
+        "users",
+        &test_interface(&record_types),
+    )
+    .unwrap_or_else(|e| panic!("can't declare service in Aqua: {:?}", e));
+
+    let right_declarations = r#"data Tag:
+  weight: f64
+  links: [][]string
+
+data User:
+  name: string
+  age: i8
+  avatar: []u8
+  tags: []Tag
+
+service UserList("users"):
+  get_user(id: u64, with_tags: i32) -> User
+  clear()
+"#;
+
+    assert_eq!(declarations, right_declarations);
+}
+
+#[test]
+pub fn typescript_declarations_follow_json_options() {
+    let record_types = test_record_types();
+    let options = JsonOptions {
+        byte_array_encoding: ByteArrayEncoding::Base64,
+        int64_as_string: true,
+    };
+    let declarations = fluence_app_service::service_typescript_declarations(
+        "user_list",
+        &test_interface(&record_types),
+        &options,
+    );
+
+    let right_declarations = r#"export type I8 = number;
+export type I32 = number;
+export type U64 = string;
+export type F64 = number;
+
+export interface Tag {
+    weight: F64;
+    links: string[][];
+}
+
+export interface User {
+    name: string;
+    age: I8;
+    avatar: string;
+    tags: Tag[];
+}
+
+export interface UserList {
+    get_user(id: U64, with_tags: I32): User;
+    clear(): void;
+}
+"#;
+
+    assert_eq!(declarations, right_declarations);
+}
+
+#[test]
+pub fn typescript_byte_array_arguments_accept_strings() {
+    let record_types = RecordTypes::new();
+    let mut interface = test_interface(&record_types);
+    interface.function_signatures[0].outputs = Arc::new(vec![]);
+    interface.function_signatures[1] = signature(
+        "clear",
+        &[("avatar", IType::Array(Box::new(IType::U8)))],
+        vec![IType::Array(Box::new(IType::U8))],
+    );
+
+    let declarations = fluence_app_service::service_typescript_declarations(
+        "user_list",
+        &interface,
+        &JsonOptions::default(),
+    );
+
+    let right_declarations = r#"export type I32 = number;
+export type U8 = number;
+export type U64 = number;
+
+export interface UserList {
+    get_user(id: U64, with_tags: I32): void;
+    clear(avatar: U8[] | string): U8[];
+}
+"#;
+
+    assert_eq!(declarations, right_declarations);
+}
+
+#[test]
+pub fn aqua_declarations_reject_unsupported_types() {
+    let record_types = test_record_types();
+    let mut multi_output = test_interface(&record_types);
+    multi_output.function_signatures[1].outputs = Arc::new(vec![IType::U32, IType::String]);
+
+    let result =
+        fluence_app_service::service_aqua_declarations("user_list", "users", &multi_output);
+    assert!(matches!(
+        result,
+        Err(AppServiceError::InvalidAquaDeclaration(_))
+    ));
+
+    let mut anyref_argument = test_interface(&record_types);
+    anyref_argument.function_signatures[1] = signature(
+        "clear",
+        &[("value", IType::Array(Box::new(IType::Anyref)))],
+        vec![],
+    );
+
+    let result =
+        fluence_app_service::service_aqua_declarations("user_list", "users", &anyref_argument);
+    assert!(matches!(
+        result,
+        Err(AppServiceError::InvalidAquaDeclaration(_))
+    ));
+
+    let mut anyref_record_types = test_record_types();
+    anyref_record_types.insert(2, record("Extra", &[("extra", IType::Anyref)]));
+    let anyref_field = test_interface(&anyref_record_types);
+
+    let result =
+        fluence_app_service::service_aqua_declarations("user_list", "users", &anyref_field);
+    assert!(matches!(
+        result,
+        Err(AppServiceError::InvalidAquaDeclaration(_))
+    ));
+}
+
+#[test]
+pub fn greeting_declarations() {
+    let greeting_config_path = "../examples/greeting/Config.toml";
+
+    let greeting_config_raw = std::fs::read(greeting_config_path)
+        .expect("../examples/greeting/Config.toml should presence");
+
+    let mut greeting_config: TomlAppServiceConfig =
+        toml::from_slice(&greeting_config_raw).expect("greeting config should be well-formed");
+    greeting_config.toml_faas_config.modules_dir =
+        Some(String::from("../examples/greeting/artifacts"));
+
+    let service = AppService::new(greeting_config, "greeting_declarations", HashMap::new())
+        .unwrap_or_else(|e| panic!("can't create app service: {:?}", e));
+
+    assert_eq!(
+        service.typescript_declarations(),
+        "export interface Greeting {\n    greeting(name: string): string;\n}\n"
+    );
+    let aqua_declarations = service
+        .aqua_declarations()
+        .unwrap_or_else(|e| panic!("can't declare service in Aqua: {:?}", e));
+    assert_eq!(
+        aqua_declarations,
+        "service Greeting(\"greeting_declarations\"):\n  greeting(name: string) -> string\n"
+    );
+}
@@ -0,0 +1,222 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+mod common;
+
+use common::test_module;
+use common::test_service_config;
+use common::CoreFunction;
+
+use fluence_app_service::AppService;
+use fluence_app_service::AppServiceError;
+use fluence_app_service::ServiceExport;
+use fluence_app_service::TomlAppServiceConfig;
+use fluence_app_service::TomlServiceExport;
+
+use serde_json::json;
+
+use std::collections::HashMap;
+
+fn records_config() -> TomlAppServiceConfig {
+    let records_config_path = "../examples/records/Config.toml";
+
+    let records_config_raw = std::fs::read(records_config_path)
+        .expect("../examples/records/Config.toml should presence");
+
+    let mut records_config: TomlAppServiceConfig =
+        toml::from_slice(&records_config_raw).expect("records config should be well-formed");
+    records_config.toml_faas_config.modules_dir =
+        Some(String::from("../examples/records/artifacts/"));
+
+    records_config
+}
+
+fn export(module: &str, function: &str, alias: Option<&str>) -> TomlServiceExport {
+    TomlServiceExport {
+        module: module.to_string(),
+        function: function.to_string(),
+        alias: alias.map(ToString::to_string),
+    }
+}
+
+#[test]
+pub fn explicit_facade() {
+    let mut config = records_config();
+    config.facade = Some(String::from("records_effector"));
+
+    let mut service = AppService::new(config, "explicit_facade", HashMap::new())
+        .unwrap_or_else(|e| panic!("can't create app service: {:?}", e));
+
+    let interface = service.get_interface();
+    let function_names = interface
+        .function_signatures
+        .iter()
+        .map(|signature| signature.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(function_names, vec!["mutate_struct"]);
+
+    let result = service.call("invoke", json!([]), <_>::default());
+    assert!(matches!(
+        result,
+        Err(AppServiceError::FunctionNotExported(func_name)) if func_name == "invoke"
+    ));
+}
+
+#[test]
+pub fn exports_from_several_modules() {
+    let mut config = records_config();
+    config.export = vec![
+        export("records_pure", "invoke", None),
+        export("records_effector", "mutate_struct", Some("mutate")),
+    ];
+
+    let mut service = AppService::new(config, "exports_from_several_modules", HashMap::new())
+        .unwrap_or_else(|e| panic!("can't create app service: {:?}", e));
+
+    let interface = service.get_interface();
+    let function_names = interface
+        .function_signatures
+        .iter()
+        .map(|signature| signature.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(function_names, vec!["invoke", "mutate"]);
+    // the same record of both modules is described once
+    assert_eq!(interface.record_types.len(), 1);
+    assert_eq!(interface.record_types[0].name, "TestRecord");
+
+    let record = service
+        .call("invoke", json!([]), <_>::default())
+        .unwrap_or_else(|e| panic!("can't invoke invoke: {:?}", e));
+    assert_eq!(record["field_11"], json!("field_11"));
+
+    let result = service
+        .call("mutate", json!({ "test_record": record }), <_>::default())
+        .unwrap_or_else(|e| panic!("can't invoke mutate: {:?}", e));
+    assert_eq!(result["field_12"], json!([0x13, 0x37]));
+
+    let result = service.call("mutate_struct", json!([record]), <_>::default());
+    assert!(matches!(
+        result,
+        Err(AppServiceError::FunctionNotExported(func_name)) if func_name == "mutate_struct"
+    ));
+
+    let schema = service
+        .json_schema()
+        .unwrap_or_else(|e| panic!("can't build schema: {:?}", e));
+    assert!(schema["definitions"]["functions"]["mutate"].is_object());
+    assert!(schema["definitions"]["functions"]["mutate_struct"].is_null());
+    assert_eq!(
+        schema["properties"]["mutate"],
+        json!({ "$ref": "#/definitions/functions/mutate" })
+    );
+    assert_eq!(
+        schema["$schema"],
+        json!("http://json-schema.org/draft-07/schema#")
+    );
+}
+
+#[test]
+pub fn invalid_exports() {
+    let mut config = records_config();
+    config.facade = Some(String::from("unknown_module"));
+    let service = AppService::new(config, "invalid_exports", HashMap::new());
+    assert!(matches!(service, Err(AppServiceError::InvalidConfig(_))));
+
+    let mut config = records_config();
+    config.export = vec![export("records_pure", "unknown_function", None)];
+    let service = AppService::new(config, "invalid_exports", HashMap::new());
+    assert!(matches!(service, Err(AppServiceError::InvalidConfig(_))));
+
+    let mut config = records_config();
+    config.export = vec![
+        export("records_pure", "invoke", None),
+        export("records_effector", "mutate_struct", Some("invoke")),
+    ];
+    let service = AppService::new(config, "invalid_exports", HashMap::new());
+    assert!(matches!(service, Err(AppServiceError::InvalidConfig(_))));
+}
+
+/// Builds a module with a function get(point: Point) that does nothing,
+/// `fields` are fields of the Point record in the IT text form.
+fn point_module(fields: &str) -> Vec<u8> {
+    let wit = format!(
+        r#"
+(@interface type (record $Point ({})))              ;; 0
+(@interface type (func (param $point: record 0)))   ;; 1
+(@interface type (func (param $point: record 0)))   ;; 2
+
+(@interface func (type 1)
+  call-core 0)
+
+(@interface export "get" (func 2))
+
+(@interface implement (func 2) (func 1))
+"#,
+        fields
+    );
+
+    let get = CoreFunction {
+        name: "get",
+        result: None,
+        instructions: vec![],
+    };
+
+    test_module(vec![get], &wit)
+}
+
+#[test]
+pub fn conflicting_records() {
+    let mut modules = HashMap::new();
+    modules.insert(
+        String::from("point_2d"),
+        point_module("field $x: s32 field $y: s32"),
+    );
+    modules.insert(
+        String::from("point_3d"),
+        point_module("field $x: s32 field $y: s32 field $z: s32"),
+    );
+    modules.insert(
+        String::from("another_point_2d"),
+        point_module("field $x: s32 field $y: s32"),
+    );
+
+    let export = |module: &str, alias: &str| ServiceExport {
+        module_name: module.to_string(),
+        function_name: String::from("get"),
+        alias: Some(alias.to_string()),
+    };
+
+    // the same records of different modules are merged
+    let mut config = test_service_config(&["point_2d", "another_point_2d"]);
+    config.exports = vec![
+        export("point_2d", "get_2d"),
+        export("another_point_2d", "get_another_2d"),
+    ];
+    let service = AppService::with_modules(modules.clone(), config, "same_records", HashMap::new())
+        .unwrap_or_else(|e| panic!("can't create app service: {:?}", e));
+    let interface = service.get_interface();
+    assert_eq!(interface.record_types.len(), 1);
+    assert_eq!(interface.record_types[0].name, "Point");
+
+    // different records with the same name can't be told apart by callers
+    let mut config = test_service_config(&["point_2d", "point_3d"]);
+    config.exports = vec![export("point_2d", "get_2d"), export("point_3d", "get_3d")];
+    let service = AppService::with_modules(modules, config, "conflicting_records", HashMap::new());
+    assert!(matches!(
+        service,
+        Err(AppServiceError::InvalidConfig(err_msg)) if err_msg.contains("record Point")
+    ));
+}
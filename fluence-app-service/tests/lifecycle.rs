@@ -0,0 +1,294 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+mod common;
+
+use common::hooks_module;
+use common::test_module;
+use common::test_service_config;
+use common::CoreFunction;
+
+use fluence_app_service::AppService;
+use fluence_app_service::AppServiceError;
+use fluence_app_service::FaaSModuleConfig;
+use fluence_app_service::LifecycleHooks;
+use fluence_app_service::TomlAppServiceConfig;
+use fluence_app_service::TomlLifecycleHooks;
+
+use parity_wasm::elements::Instruction;
+use parity_wasm::elements::ValueType;
+use serde_json::json;
+
+use std::collections::HashMap;
+use std::time::Duration;
+use std::time::Instant;
+
+fn hooks_service(service_id: &str, lifecycle_hooks: LifecycleHooks) -> AppService {
+    let mut config = test_service_config(&["hooks"]);
+    config.lifecycle_hooks = vec![lifecycle_hooks];
+
+    let mut modules = HashMap::new();
+    modules.insert(String::from("hooks"), hooks_module());
+
+    AppService::with_modules(modules, config, service_id, HashMap::new())
+        .unwrap_or_else(|e| panic!("can't create app service: {:?}", e))
+}
+
+fn module_hooks() -> LifecycleHooks {
+    LifecycleHooks {
+        module_name: String::from("hooks"),
+        on_init: None,
+        init_config: String::from("{}"),
+        on_upgrade: None,
+        on_shutdown: None,
+        timeout: Duration::from_secs(10),
+    }
+}
+
+fn counter(service: &mut AppService) -> serde_json::Value {
+    service
+        .call("counter", json!([]), <_>::default())
+        .unwrap_or_else(|e| panic!("can't invoke counter: {:?}", e))
+}
+
+fn example_config(example_name: &str) -> TomlAppServiceConfig {
+    let config_path = format!("../examples/{}/Config.toml", example_name);
+    let config_raw =
+        std::fs::read(&config_path).unwrap_or_else(|_| panic!("{} should presence", config_path));
+
+    let mut config: TomlAppServiceConfig =
+        toml::from_slice(&config_raw).expect("config should be well-formed");
+    config.toml_faas_config.modules_dir = Some(format!("../examples/{}/artifacts", example_name));
+
+    config
+}
+
+fn hooks(module: &str) -> TomlLifecycleHooks {
+    TomlLifecycleHooks {
+        module: module.to_string(),
+        on_init: None,
+        init_config: None,
+        on_upgrade: None,
+        on_shutdown: None,
+        timeout_ms: None,
+    }
+}
+
+#[test]
+pub fn init_and_shutdown_hooks() {
+    // existing functions with suitable signatures play the role of hooks here
+    let mut config = example_config("records");
+    config.lifecycle = vec![
+        TomlLifecycleHooks {
+            on_shutdown: Some(String::from("invoke")),
+            timeout_ms: Some(10_000),
+            ..hooks("records_pure")
+        },
+        TomlLifecycleHooks {
+            on_shutdown: Some(String::from("mutate_struct")),
+            ..hooks("records_effector")
+        },
+    ];
+    let service = AppService::new(config, "init_and_shutdown_hooks", HashMap::new());
+    assert!(matches!(service, Err(AppServiceError::InvalidConfig(_))));
+
+    let mut config = example_config("records");
+    config.lifecycle = vec![TomlLifecycleHooks {
+        on_shutdown: Some(String::from("invoke")),
+        timeout_ms: Some(10_000),
+        ..hooks("records_pure")
+    }];
+    let service = AppService::new(config, "init_and_shutdown_hooks", HashMap::new())
+        .unwrap_or_else(|e| panic!("can't create app service: {:?}", e));
+    service
+        .shutdown()
+        .unwrap_or_else(|e| panic!("shutdown hooks have failed: {:?}", e));
+
+    let mut config = example_config("greeting");
+    config.lifecycle = vec![TomlLifecycleHooks {
+        on_init: Some(String::from("greeting")),
+        init_config: Some(toml::toml! { key = "value" }),
+        ..hooks("greeting")
+    }];
+    let mut service = AppService::new(config, "init_and_shutdown_hooks", HashMap::new())
+        .unwrap_or_else(|e| panic!("can't create app service: {:?}", e));
+
+    let result = service
+        .call("greeting", serde_json::json!(["Fluence"]), <_>::default())
+        .unwrap_or_else(|e| panic!("can't invoke greeting: {:?}", e));
+    assert_eq!(result, serde_json::json!("Hi, Fluence"));
+}
+
+#[test]
+pub fn invalid_hooks() {
+    let mut config = example_config("greeting");
+    config.lifecycle = vec![TomlLifecycleHooks {
+        on_init: Some(String::from("unknown_function")),
+        ..hooks("greeting")
+    }];
+    let service = AppService::new(config, "invalid_hooks", HashMap::new());
+    assert!(matches!(service, Err(AppServiceError::InvalidConfig(_))));
+
+    let mut config = example_config("greeting");
+    config.lifecycle = vec![TomlLifecycleHooks {
+        on_shutdown: Some(String::from("greeting")),
+        ..hooks("greeting")
+    }];
+    let service = AppService::new(config, "invalid_hooks", HashMap::new());
+    assert!(matches!(service, Err(AppServiceError::InvalidConfig(_))));
+
+    let mut config = example_config("greeting");
+    config.lifecycle = vec![TomlLifecycleHooks {
+        timeout_ms: Some(0),
+        ..hooks("greeting")
+    }];
+    let service = AppService::new(config, "invalid_hooks", HashMap::new());
+    assert!(matches!(service, Err(AppServiceError::InvalidConfig(_))));
+
+    let mut config = example_config("greeting");
+    config.lifecycle = vec![hooks("unknown_module")];
+    let service = AppService::new(config, "invalid_hooks", HashMap::new());
+    assert!(matches!(service, Err(AppServiceError::InvalidConfig(_))));
+}
+
+#[test]
+pub fn upgrade_hooks() {
+    let hooks = LifecycleHooks {
+        on_init: Some(String::from("on_init")),
+        on_upgrade: Some(String::from("on_upgrade")),
+        on_shutdown: Some(String::from("on_shutdown")),
+        ..module_hooks()
+    };
+    let mut service = hooks_service("upgrade_hooks", hooks);
+    assert_eq!(counter(&mut service), json!(1));
+
+    // the new version starts with a fresh state and only its on_upgrade is called
+    service
+        .upgrade_module("hooks", &hooks_module(), None::<FaaSModuleConfig>, "0.1.0")
+        .unwrap_or_else(|e| panic!("can't upgrade module: {:?}", e));
+    assert_eq!(counter(&mut service), json!(10));
+
+    // the old version stays intact if the new one can't be loaded
+    let result =
+        service.upgrade_module("hooks", b"not a module", None::<FaaSModuleConfig>, "0.2.0");
+    assert!(matches!(result, Err(AppServiceError::FaaSError(_))));
+    assert_eq!(counter(&mut service), json!(10));
+
+    // or if it doesn't export declared hooks
+    let counter_wit = r#"
+(@interface type (func (result s32)))   ;; 0
+(@interface type (func (result s32)))   ;; 1
+
+(@interface func (type 0)
+  call-core 0
+  s32.from_i32)
+
+(@interface export "counter" (func 1))
+
+(@interface implement (func 1) (func 0))
+"#;
+    let counter_function = CoreFunction {
+        name: "counter",
+        result: Some(ValueType::I32),
+        instructions: vec![Instruction::GetGlobal(0)],
+    };
+    let result = service.upgrade_module(
+        "hooks",
+        &test_module(vec![counter_function], counter_wit),
+        None::<FaaSModuleConfig>,
+        "0.2.0",
+    );
+    assert!(matches!(result, Err(AppServiceError::InvalidConfig(_))));
+    assert_eq!(counter(&mut service), json!(10));
+
+    // modules backing exported functions can't be unloaded
+    let result = service.unload_module("hooks");
+    assert!(matches!(
+        result,
+        Err(AppServiceError::ModuleExported(module_name)) if module_name == "hooks"
+    ));
+    assert_eq!(counter(&mut service), json!(10));
+}
+
+#[test]
+pub fn upgrade_keeps_exports() {
+    let mut service = hooks_service("upgrade_keeps_exports", module_hooks());
+    let counter_value = counter(&mut service);
+
+    // the new version should have all exported functions of the old one
+    let noop_wit = r#"
+(@interface type (func ))   ;; 0
+(@interface type (func ))   ;; 1
+
+(@interface func (type 0)
+  call-core 0)
+
+(@interface export "noop" (func 1))
+
+(@interface implement (func 1) (func 0))
+"#;
+    let noop_function = CoreFunction {
+        name: "noop",
+        result: None,
+        instructions: vec![],
+    };
+    let result = service.upgrade_module(
+        "hooks",
+        &test_module(vec![noop_function], noop_wit),
+        None::<FaaSModuleConfig>,
+        "0.2.0",
+    );
+    assert!(matches!(result, Err(AppServiceError::InvalidConfig(_))));
+    assert_eq!(counter(&mut service), counter_value);
+}
+
+#[test]
+pub fn timed_out_hook_poisons_service() {
+    let hooks = LifecycleHooks {
+        on_init: Some(String::from("on_init")),
+        on_shutdown: Some(String::from("spin")),
+        timeout: Duration::from_millis(100),
+        ..module_hooks()
+    };
+    let mut service = hooks_service("timed_out_hook", hooks);
+    assert_eq!(counter(&mut service), json!(1));
+
+    let result =
+        service.upgrade_module("hooks", &hooks_module(), None::<FaaSModuleConfig>, "0.1.0");
+    assert!(matches!(
+        result,
+        Err(AppServiceError::LifecycleHookTimeout { hook, .. }) if hook == "on_shutdown"
+    ));
+
+    let result = service.call("counter", json!([]), <_>::default());
+    assert!(matches!(result, Err(AppServiceError::ServicePoisoned(_))));
+    let result = service.shutdown();
+    assert!(matches!(result, Err(AppServiceError::ServicePoisoned(_))));
+}
+
+#[test]
+pub fn shutdown_on_drop_is_bounded() {
+    let hooks = LifecycleHooks {
+        on_shutdown: Some(String::from("spin")),
+        timeout: Duration::from_secs(60),
+        ..module_hooks()
+    };
+    let service = hooks_service("shutdown_on_drop", hooks);
+
+    let start = Instant::now();
+    drop(service);
+    assert!(start.elapsed() < Duration::from_secs(10));
+}
@@ -0,0 +1,316 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+mod common;
+
+use common::hooks_module;
+
+use fluence_app_service::AppServiceError;
+use fluence_app_service::AppServiceManager;
+use fluence_app_service::Blueprint;
+use fluence_app_service::BlueprintModule;
+use fluence_app_service::CallParameters;
+use fluence_app_service::ModuleStore;
+use fluence_app_service::ServiceManagerConfig;
+use fluence_app_service::TomlAppServiceConfig;
+use fluence_app_service::TomlFaaSModuleConfig;
+use fluence_app_service::TomlFaaSNamedModuleConfig;
+use fluence_app_service::TomlLifecycleHooks;
+use fluence_app_service::TomlWasmFeatures;
+
+use serde_json::json;
+
+use std::path::PathBuf;
+
+fn manager_config(test_name: &str) -> (ServiceManagerConfig, String) {
+    let base_dir = std::env::temp_dir().join(format!("service_manager_{}", test_name));
+    let _ = std::fs::remove_dir_all(&base_dir);
+
+    let modules_dir = base_dir.join("modules");
+    let greeting_wasm = std::fs::read("../examples/greeting/artifacts/greeting.wasm")
+        .expect("greeting.wasm should presence");
+    let greeting_hash = ModuleStore::new(&modules_dir)
+        .put(&greeting_wasm, &<_>::default())
+        .unwrap_or_else(|e| panic!("can't put module to the store: {:?}", e));
+
+    let config = ServiceManagerConfig {
+        modules_dir,
+        service_base_dir: base_dir.join("services"),
+        registry_path: base_dir.join("registry.json"),
+    };
+
+    (config, greeting_hash)
+}
+
+fn greeting_blueprint(hash: &str) -> Blueprint {
+    Blueprint {
+        name: String::from("greeting"),
+        modules: vec![BlueprintModule {
+            name: String::from("greeting"),
+            hash: hash.to_string(),
+        }],
+        config: TomlAppServiceConfig::default(),
+    }
+}
+
+/// Greeting has globals, so it can't be loaded with a config that forbids them.
+fn broken_module_config() -> TomlFaaSModuleConfig {
+    TomlFaaSModuleConfig {
+        features: Some(TomlWasmFeatures {
+            max_globals: Some(0),
+            ..<_>::default()
+        }),
+        ..<_>::default()
+    }
+}
+
+fn put_greeting(config: &ServiceManagerConfig, module_config: &TomlFaaSModuleConfig) {
+    let greeting_wasm = std::fs::read("../examples/greeting/artifacts/greeting.wasm")
+        .expect("greeting.wasm should presence");
+    ModuleStore::new(&config.modules_dir)
+        .put(&greeting_wasm, module_config)
+        .unwrap_or_else(|e| panic!("can't put module to the store: {:?}", e));
+}
+
+#[test]
+pub fn create_call_and_recover() {
+    let (config, greeting_hash) = manager_config("create_call_and_recover");
+    let service_base_dir: PathBuf = config.service_base_dir.clone();
+
+    let mut manager = AppServiceManager::new(config.clone())
+        .unwrap_or_else(|e| panic!("can't create service manager: {:?}", e));
+    manager
+        .create_service("greeter", greeting_blueprint(&greeting_hash))
+        .unwrap_or_else(|e| panic!("can't create service: {:?}", e));
+
+    let result = manager.create_service("greeter", greeting_blueprint(&greeting_hash));
+    assert!(matches!(
+        result,
+        Err(AppServiceError::DuplicateServiceId(_))
+    ));
+
+    let result = manager.create_service("another", greeting_blueprint("missing"));
+    assert!(matches!(result, Err(AppServiceError::ModuleNotFound(_))));
+
+    let result = manager.create_service("../escaped", greeting_blueprint(&greeting_hash));
+    assert!(matches!(result, Err(AppServiceError::InvalidConfig(_))));
+
+    let result = manager
+        .call(
+            "greeter",
+            "greeting",
+            json!(["Fluence"]),
+            CallParameters::default(),
+        )
+        .unwrap_or_else(|e| panic!("can't call service: {:?}", e));
+    assert_eq!(result, json!("Hi, Fluence"));
+    drop(manager);
+
+    let mut manager = AppServiceManager::new(config)
+        .unwrap_or_else(|e| panic!("can't recover service manager: {:?}", e));
+    let services = manager.list_services();
+    assert_eq!(services.len(), 1);
+    assert_eq!(services[0].service_id, "greeter");
+    assert_eq!(services[0].blueprint.modules[0].hash, greeting_hash);
+
+    let result = manager
+        .call(
+            "greeter",
+            "greeting",
+            json!(["Fluence"]),
+            CallParameters::default(),
+        )
+        .unwrap_or_else(|e| panic!("can't call recovered service: {:?}", e));
+    assert_eq!(result, json!("Hi, Fluence"));
+
+    manager
+        .remove_service("greeter")
+        .unwrap_or_else(|e| panic!("can't remove service: {:?}", e));
+    assert!(manager.list_services().is_empty());
+    assert!(!service_base_dir.join("greeter").exists());
+
+    let result = manager.call(
+        "greeter",
+        "greeting",
+        json!(["Fluence"]),
+        CallParameters::default(),
+    );
+    assert!(matches!(result, Err(AppServiceError::NoSuchService(_))));
+}
+
+#[test]
+pub fn recovery_skips_failed_services() {
+    let (config, greeting_hash) = manager_config("recovery_skips_failed_services");
+
+    let mut manager = AppServiceManager::new(config.clone())
+        .unwrap_or_else(|e| panic!("can't create service manager: {:?}", e));
+    manager
+        .create_service("greeter", greeting_blueprint(&greeting_hash))
+        .unwrap_or_else(|e| panic!("can't create service: {:?}", e));
+    drop(manager);
+
+    // add a service whose module has disappeared from the store
+    let registry = std::fs::read(&config.registry_path)
+        .unwrap_or_else(|e| panic!("can't read registry: {:?}", e));
+    let mut registry: serde_json::Value = serde_json::from_slice(&registry)
+        .unwrap_or_else(|e| panic!("can't parse registry: {:?}", e));
+    registry["services"]
+        .as_array_mut()
+        .expect("services should be an array")
+        .push(json!({
+            "service_id": "broken",
+            "blueprint": greeting_blueprint("missing"),
+        }));
+    std::fs::write(&config.registry_path, registry.to_string())
+        .unwrap_or_else(|e| panic!("can't write registry: {:?}", e));
+
+    let mut manager = AppServiceManager::new(config.clone())
+        .unwrap_or_else(|e| panic!("can't recover service manager: {:?}", e));
+    let services = manager.list_services();
+    assert_eq!(services.len(), 1);
+    assert_eq!(services[0].service_id, "greeter");
+
+    let failed_services = manager.failed_services();
+    assert_eq!(failed_services.len(), 1);
+    assert_eq!(failed_services[0].service_id, "broken");
+    assert!(matches!(
+        failed_services[0].error,
+        AppServiceError::ModuleNotFound(_)
+    ));
+
+    let result = manager.create_service("broken", greeting_blueprint(&greeting_hash));
+    assert!(matches!(
+        result,
+        Err(AppServiceError::DuplicateServiceId(_))
+    ));
+
+    manager
+        .remove_service("broken")
+        .unwrap_or_else(|e| panic!("can't remove failed service: {:?}", e));
+    assert!(manager.failed_services().is_empty());
+    drop(manager);
+
+    let manager = AppServiceManager::new(config)
+        .unwrap_or_else(|e| panic!("can't recover service manager: {:?}", e));
+    assert_eq!(manager.list_services().len(), 1);
+    assert!(manager.failed_services().is_empty());
+}
+
+#[test]
+pub fn recovery_does_not_call_on_init() {
+    let (config, _) = manager_config("recovery_does_not_call_on_init");
+    let hooks_hash = ModuleStore::new(&config.modules_dir)
+        .put(&hooks_module(), &<_>::default())
+        .unwrap_or_else(|e| panic!("can't put module to the store: {:?}", e));
+
+    let blueprint = Blueprint {
+        name: String::from("hooks"),
+        modules: vec![BlueprintModule {
+            name: String::from("hooks"),
+            hash: hooks_hash,
+        }],
+        config: TomlAppServiceConfig {
+            facade: Some(String::from("hooks")),
+            lifecycle: vec![TomlLifecycleHooks {
+                module: String::from("hooks"),
+                on_init: Some(String::from("on_init")),
+                init_config: None,
+                on_upgrade: None,
+                on_shutdown: None,
+                timeout_ms: None,
+            }],
+            ..<_>::default()
+        },
+    };
+
+    let mut manager = AppServiceManager::new(config.clone())
+        .unwrap_or_else(|e| panic!("can't create service manager: {:?}", e));
+    manager
+        .create_service("hooks", blueprint)
+        .unwrap_or_else(|e| panic!("can't create service: {:?}", e));
+    let counter = manager
+        .call("hooks", "counter", json!([]), CallParameters::default())
+        .unwrap_or_else(|e| panic!("can't invoke counter: {:?}", e));
+    assert_eq!(counter, json!(1));
+    drop(manager);
+
+    let mut manager = AppServiceManager::new(config)
+        .unwrap_or_else(|e| panic!("can't recover service manager: {:?}", e));
+    let counter = manager
+        .call("hooks", "counter", json!([]), CallParameters::default())
+        .unwrap_or_else(|e| panic!("can't invoke counter: {:?}", e));
+    assert_eq!(counter, json!(0));
+}
+
+#[test]
+pub fn modules_without_configs_use_default_one() {
+    let (config, greeting_hash) = manager_config("modules_without_configs_use_default_one");
+
+    let broken_config = broken_module_config();
+    put_greeting(&config, &broken_config);
+
+    let mut manager = AppServiceManager::new(config)
+        .unwrap_or_else(|e| panic!("can't create service manager: {:?}", e));
+    manager
+        .create_service("greeter", greeting_blueprint(&greeting_hash))
+        .unwrap_or_else(|e| panic!("can't create service: {:?}", e));
+    let greeting = manager
+        .call(
+            "greeter",
+            "greeting",
+            json!(["Fluence"]),
+            CallParameters::default(),
+        )
+        .unwrap_or_else(|e| panic!("can't invoke greeting: {:?}", e));
+    assert_eq!(greeting, json!("Hi, Fluence"));
+
+    let mut blueprint = greeting_blueprint(&greeting_hash);
+    blueprint.config.toml_faas_config.module = vec![TomlFaaSNamedModuleConfig {
+        name: String::from("greeting"),
+        hash: None,
+        config: broken_config,
+    }];
+    let result = manager.create_service("broken", blueprint);
+    assert!(result.is_err());
+}
+
+#[test]
+pub fn recovery_ignores_configs_put_later() {
+    let (config, greeting_hash) = manager_config("recovery_ignores_configs_put_later");
+
+    let mut manager = AppServiceManager::new(config.clone())
+        .unwrap_or_else(|e| panic!("can't create service manager: {:?}", e));
+    manager
+        .create_service("greeter", greeting_blueprint(&greeting_hash))
+        .unwrap_or_else(|e| panic!("can't create service: {:?}", e));
+    drop(manager);
+
+    // someone else puts the same module with a config the greeter can't be loaded with
+    put_greeting(&config, &broken_module_config());
+
+    let mut manager = AppServiceManager::new(config)
+        .unwrap_or_else(|e| panic!("can't recover service manager: {:?}", e));
+    assert!(manager.failed_services().is_empty());
+    let greeting = manager
+        .call(
+            "greeter",
+            "greeting",
+            json!(["Fluence"]),
+            CallParameters::default(),
+        )
+        .unwrap_or_else(|e| panic!("can't invoke greeting: {:?}", e));
+    assert_eq!(greeting, json!("Hi, Fluence"));
+}
@@ -0,0 +1,112 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use fluence_app_service::AppServiceError;
+use fluence_app_service::AppServicePool;
+use fluence_app_service::TomlAppServiceConfig;
+use fluence_app_service::TomlServicePoolConfig;
+
+use serde_json::json;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+fn greeting_config(stateless: bool, pool: TomlServicePoolConfig) -> TomlAppServiceConfig {
+    let greeting_config_path = "../examples/greeting/Config.toml";
+
+    let greeting_config_raw = std::fs::read(greeting_config_path)
+        .expect("../examples/greeting/Config.toml should presence");
+
+    let mut greeting_config: TomlAppServiceConfig =
+        toml::from_slice(&greeting_config_raw).expect("greeting config should be well-formed");
+    greeting_config.toml_faas_config.modules_dir =
+        Some(String::from("../examples/greeting/artifacts"));
+    for module in greeting_config.toml_faas_config.module.iter_mut() {
+        module.config.stateless = Some(stateless);
+    }
+    greeting_config.pool = Some(pool);
+
+    greeting_config
+}
+
+#[test]
+pub fn pool_requires_stateless_modules() {
+    let config = greeting_config(false, <_>::default());
+
+    let pool = AppServicePool::new(config, "pool_requires_stateless", HashMap::new());
+
+    assert!(matches!(pool, Err(AppServiceError::InvalidConfig(_))));
+}
+
+#[test]
+pub fn concurrent_calls() {
+    let pool_config = TomlServicePoolConfig {
+        size: Some(4),
+        warm_up: Some(true),
+        recycle_after_calls: Some(3),
+    };
+    let config = greeting_config(true, pool_config);
+
+    let pool = AppServicePool::new(config, "concurrent_calls", HashMap::new())
+        .unwrap_or_else(|e| panic!("can't create service pool: {:?}", e));
+    let pool = Arc::new(pool);
+    assert_eq!(pool.size(), 4);
+
+    let threads = (0..16)
+        .map(|thread_id| {
+            let pool = pool.clone();
+            std::thread::spawn(move || {
+                let name = format!("Fluence {}", thread_id);
+                let result = pool
+                    .call("greeting", json!([name]), <_>::default())
+                    .unwrap_or_else(|e| panic!("can't invoke greeting: {:?}", e));
+
+                (name, result)
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for thread in threads {
+        let (name, result) = thread.join().expect("thread shouldn't panic");
+        assert_eq!(result, json!(format!("Hi, {}", name)));
+    }
+}
+
+#[test]
+pub fn instances_have_own_dirs() {
+    let service_base_dir = std::env::temp_dir().join("service_pool_instances_have_own_dirs");
+    let _ = std::fs::remove_dir_all(&service_base_dir);
+
+    let pool_config = TomlServicePoolConfig {
+        size: Some(2),
+        warm_up: Some(true),
+        recycle_after_calls: None,
+    };
+    let mut config = greeting_config(true, pool_config);
+    config.service_base_dir = Some(service_base_dir.to_string_lossy().to_string());
+
+    let _pool = AppServicePool::new(config, "pooled", HashMap::new())
+        .unwrap_or_else(|e| panic!("can't create service pool: {:?}", e));
+
+    for instance_id in ["0", "1"].iter() {
+        let instance_dir = service_base_dir
+            .join("pooled")
+            .join("instances")
+            .join(instance_id);
+        assert!(instance_dir.join("local").is_dir());
+        assert!(instance_dir.join("tmp").is_dir());
+    }
+}
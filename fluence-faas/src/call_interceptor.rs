@@ -0,0 +1,104 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::IValue;
+
+use fce::FCECallContext;
+use fce::FCECallInterceptor;
+use fce::FCEError;
+use fluence_sdk_main::CallParameters;
+use parking_lot::Mutex;
+
+use std::sync::Arc;
+
+/// Describes a call of a module function made through FaaS or by another module.
+#[derive(Debug, Clone, Copy)]
+pub struct CallContext<'c> {
+    pub module_name: &'c str,
+    pub function_name: &'c str,
+    pub arguments: &'c [IValue],
+
+    /// Parameters of the top-level call, nested calls share them.
+    pub call_parameters: &'c CallParameters,
+}
+
+/// Could be registered in FaaS to check, measure or log calls of module functions,
+/// including nested calls between modules.
+pub trait CallInterceptor: Send + Sync {
+    /// Invoked before the call, an error rejects the call.
+    fn before_call(&self, _context: &CallContext<'_>) -> std::result::Result<(), String> {
+        Ok(())
+    }
+
+    /// Invoked after the call (or its rejection) with its result,
+    /// only if before_call of this interceptor has been invoked.
+    fn after_call(
+        &self,
+        _context: &CallContext<'_>,
+        _result: std::result::Result<&[IValue], &FCEError>,
+    ) {
+    }
+}
+
+/// Adapts FaaS interceptors to FCE ones by supplying call parameters of the current call.
+pub(crate) struct InterceptorAdapter {
+    interceptor: Arc<dyn CallInterceptor>,
+    call_parameters: Arc<Mutex<CallParameters>>,
+}
+
+impl InterceptorAdapter {
+    pub(crate) fn new(
+        interceptor: Arc<dyn CallInterceptor>,
+        call_parameters: Arc<Mutex<CallParameters>>,
+    ) -> Self {
+        Self {
+            interceptor,
+            call_parameters,
+        }
+    }
+}
+
+impl FCECallInterceptor for InterceptorAdapter {
+    fn before_call(&self, context: &FCECallContext<'_>) -> std::result::Result<(), String> {
+        let call_parameters = self.call_parameters.lock();
+        let context = to_faas_context(context, &call_parameters);
+
+        self.interceptor.before_call(&context)
+    }
+
+    fn after_call(
+        &self,
+        context: &FCECallContext<'_>,
+        result: std::result::Result<&[IValue], &FCEError>,
+    ) {
+        let call_parameters = self.call_parameters.lock();
+        let context = to_faas_context(context, &call_parameters);
+
+        self.interceptor.after_call(&context, result)
+    }
+}
+
+fn to_faas_context<'c>(
+    context: &FCECallContext<'c>,
+    call_parameters: &'c CallParameters,
+) -> CallContext<'c> {
+    CallContext {
+        module_name: context.module_name,
+        function_name: context.function_name,
+        arguments: context.arguments,
+        call_parameters,
+    }
+}
@@ -15,6 +15,9 @@
  */
 
 use fce::HostImportDescriptor;
+use fce::WasmFeatures;
+use serde::Deserialize;
+use serde::Serialize;
 
 use std::collections::HashMap;
 use std::collections::HashSet;
@@ -29,8 +32,27 @@ pub struct FaaSConfig {
     /// Settings for a module with particular name (not HashMap because the order is matter).
     pub modules_config: Vec<(String, FaaSModuleConfig)>,
 
+    /// Path to a module store used to load modules referenced by hashes.
+    pub module_store_dir: Option<PathBuf>,
+
+    /// Hashes of modules loaded from the module store instead of modules_dir by module names.
+    pub module_hashes: HashMap<String, String>,
+
+    /// Ed25519 public keys trusted to sign modules, if it isn't empty,
+    /// only modules signed by one of these keys are loaded.
+    pub trusted_keys: Vec<[u8; 32]>,
+
     /// Settings for a module that name's not been found in modules_config.
     pub default_modules_config: Option<FaaSModuleConfig>,
+
+    /// Path to a file where all calls are journaled to be replayed later.
+    pub journal_path: Option<PathBuf>,
+
+    /// Maximum count of memoized results of pure functions, the default one is used if None.
+    pub memoization_cache_size: Option<usize>,
+
+    /// Options of mapping between JSON and IValues used for JSON calls by default.
+    pub json_options: JsonOptions,
 }
 
 /// Various settings that could be used to guide FCE how to load a module in a proper way.
@@ -50,6 +72,79 @@ pub struct FaaSModuleConfig {
 
     /// Mask used to filter logs, for details see `log_utf8_string`
     pub logging_mask: i32,
+
+    /// Declares that a module doesn't keep any state between calls,
+    /// only services consisting of such modules could be pooled.
+    pub stateless: bool,
+
+    /// Functions which results depend only on their arguments, so they could be memoized.
+    pub pure_functions: PureFunctions,
+
+    /// A detached signature of the module checked against trusted keys,
+    /// the signature embedded to the module is checked if it isn't set.
+    pub signature: Option<Vec<u8>>,
+
+    /// Wasm features the module is allowed to use.
+    pub wasm_features: WasmFeatures,
+
+    /// If set, a call traps with StackOverflow when the module stack height exceeds it.
+    pub max_stack_height: Option<u32>,
+}
+
+/// Describes which functions of a module are pure.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PureFunctions {
+    None,
+    All,
+    Only(HashSet<String>),
+}
+
+impl PureFunctions {
+    pub fn contains(&self, function_name: &str) -> bool {
+        match self {
+            PureFunctions::None => false,
+            PureFunctions::All => true,
+            PureFunctions::Only(functions) => functions.contains(function_name),
+        }
+    }
+}
+
+impl Default for PureFunctions {
+    fn default() -> Self {
+        PureFunctions::None
+    }
+}
+
+/// Describes how arguments and results of JSON calls are mapped to IValues and back.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonOptions {
+    /// Encoding of byte arrays in results. Arguments are accepted in any encoding:
+    /// as arrays of numbers, hex strings prefixed with 0x and base64 strings.
+    pub byte_array_encoding: ByteArrayEncoding,
+
+    /// Accept and emit 64-bit integers as decimal strings, because JS clients
+    /// lose precision on big JSON numbers.
+    pub int64_as_string: bool,
+}
+
+/// Encoding of `Array<U8>` values in JSON.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ByteArrayEncoding {
+    /// An array of numbers.
+    Array,
+
+    /// A base64 string.
+    Base64,
+
+    /// A hex string prefixed with 0x.
+    Hex,
+}
+
+impl Default for ByteArrayEncoding {
+    fn default() -> Self {
+        ByteArrayEncoding::Array
+    }
 }
 
 impl FaaSModuleConfig {
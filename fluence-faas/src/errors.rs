@@ -15,6 +15,7 @@
  */
 
 use fce::FCEError;
+use serde_json::Value as JValue;
 
 use std::io::Error as IOError;
 use std::error::Error;
@@ -40,18 +41,105 @@ pub enum FaaSError {
     NoSuchModule(String),
 
     /// Provided arguments aren't compatible with a called function signature.
-    JsonArgumentsDeserializationError(String),
+    JsonArgumentsDeserializationError(JsonArgumentError),
 
     /// Returned outputs aren't compatible with a called function signature.
     JsonOutputSerializationError(String),
 
+    /// Provided binary arguments are malformed or aren't compatible with a called function signature.
+    BinaryArgumentsDecodingError(String),
+
+    /// Returned outputs couldn't be encoded to the requested binary encoding.
+    BinaryOutputEncodingError(String),
+
+    /// Provided Rust arguments couldn't be converted to a called function signature.
+    TypedArgumentsError(String),
+
+    /// Returned outputs couldn't be converted to the requested Rust type.
+    TypedResultError(String),
+
     /// Errors related to invalid config.
     ParseConfigError(toml::de::Error),
 
+    /// Errors related to recording or replaying of the call journal.
+    JournalError(String),
+
+    /// A module can't be saved to or loaded from the module store.
+    ModuleStoreError(String),
+
+    /// A module hash isn't a hex blake3 hash.
+    InvalidModuleHash(String),
+
+    /// Bytes of a module loaded from the module store don't match its hash.
+    ModuleIntegrityError { hash: String, actual_hash: String },
+
     /// FCE errors.
     EngineError(FCEError),
 }
 
+/// Describes which value of JSON arguments can't be converted to IValue and why.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonArgumentError {
+    /// Path to the value in arguments, e.g. `args.user.tags[3]`.
+    pub path: String,
+    pub kind: JsonArgumentErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JsonArgumentErrorKind {
+    /// A value has a JSON kind incompatible with the expected type.
+    TypeMismatch {
+        expected: String,
+        received: JsonKind,
+    },
+
+    /// A value has a proper JSON kind, but can't be represented by the expected type.
+    InvalidValue {
+        expected: String,
+        received: JsonKind,
+        reason: String,
+    },
+
+    /// An argument or a record field is missing.
+    MissingValue { expected: String },
+
+    /// A value doesn't correspond to any argument or record field.
+    UnexpectedValue { received: JsonKind },
+
+    /// Count of provided values differs from count of arguments or record fields.
+    CountMismatch { expected: usize, provided: usize },
+
+    /// The expected type couldn't be passed through JSON.
+    UnsupportedType { expected: String },
+
+    /// A record type with such id isn't defined by the module.
+    UnknownRecordType(u64),
+}
+
+/// Kind of a JSON value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsonKind {
+    Null,
+    Bool,
+    Number,
+    String,
+    Array,
+    Object,
+}
+
+impl JsonKind {
+    pub fn of(value: &JValue) -> Self {
+        match value {
+            JValue::Null => JsonKind::Null,
+            JValue::Bool(_) => JsonKind::Bool,
+            JValue::Number(_) => JsonKind::Number,
+            JValue::String(_) => JsonKind::String,
+            JValue::Array(_) => JsonKind::Array,
+            JValue::Object(_) => JsonKind::Object,
+        }
+    }
+}
+
 impl Error for FaaSError {}
 
 impl std::fmt::Display for FaaSError {
@@ -70,13 +158,86 @@ impl std::fmt::Display for FaaSError {
             }
             FaaSError::JsonArgumentsDeserializationError(args) => write!(f, "{}", args),
             FaaSError::JsonOutputSerializationError(args) => write!(f, "{}", args),
+            FaaSError::BinaryArgumentsDecodingError(err_msg) => write!(f, "{}", err_msg),
+            FaaSError::BinaryOutputEncodingError(err_msg) => write!(f, "{}", err_msg),
+            FaaSError::TypedArgumentsError(err_msg) => write!(f, "{}", err_msg),
+            FaaSError::TypedResultError(err_msg) => write!(f, "{}", err_msg),
             FaaSError::IOError(err_msg) => write!(f, "{}", err_msg),
+            FaaSError::JournalError(err_msg) => write!(f, "{}", err_msg),
+            FaaSError::ModuleStoreError(err_msg) => write!(f, "{}", err_msg),
+            FaaSError::InvalidModuleHash(hash) => write!(
+                f,
+                "{} isn't a valid module hash, it should be a hex blake3 hash",
+                hash
+            ),
+            FaaSError::ModuleIntegrityError { hash, actual_hash } => write!(
+                f,
+                "module with hash {} is corrupted, its actual hash is {}",
+                hash, actual_hash
+            ),
             FaaSError::EngineError(err) => write!(f, "{}", err),
             FaaSError::ParseConfigError(err) => write!(f, "{}", err),
         }
     }
 }
 
+impl std::fmt::Display for JsonArgumentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "{}: {}", self.path, self.kind)
+    }
+}
+
+impl std::fmt::Display for JsonArgumentErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            JsonArgumentErrorKind::TypeMismatch { expected, received } => {
+                write!(f, "expected {}, got {}", expected, received)
+            }
+            JsonArgumentErrorKind::InvalidValue {
+                expected,
+                received,
+                reason,
+            } => write!(f, "{} isn't a valid {}: {}", received, expected, reason),
+            JsonArgumentErrorKind::MissingValue { expected } => {
+                write!(f, "missing value of type {}", expected)
+            }
+            JsonArgumentErrorKind::UnexpectedValue { received } => {
+                write!(f, "unexpected {} value", received)
+            }
+            JsonArgumentErrorKind::CountMismatch { expected, provided } => {
+                write!(f, "expected {} values, {} provided", expected, provided)
+            }
+            JsonArgumentErrorKind::UnsupportedType { expected } => {
+                write!(f, "values of type {} aren't supported", expected)
+            }
+            JsonArgumentErrorKind::UnknownRecordType(record_type_id) => {
+                write!(f, "record with type id `{}` wasn't found", record_type_id)
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for JsonKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        let kind = match self {
+            JsonKind::Null => "null",
+            JsonKind::Bool => "bool",
+            JsonKind::Number => "number",
+            JsonKind::String => "string",
+            JsonKind::Array => "array",
+            JsonKind::Object => "object",
+        };
+
+        write!(f, "{}", kind)
+    }
+}
+
+impl From<JsonArgumentError> for FaaSError {
+    fn from(err: JsonArgumentError) -> Self {
+        FaaSError::JsonArgumentsDeserializationError(err)
+    }
+}
+
 impl From<IOError> for FaaSError {
     fn from(err: IOError) -> Self {
         FaaSError::IOError(format!("{}", err))
@@ -14,48 +14,101 @@
  * limitations under the License.
  */
 
+use crate::call_interceptor::CallInterceptor;
+use crate::call_interceptor::InterceptorAdapter;
 use crate::config::FaaSConfig;
+use crate::config::JsonOptions;
+use crate::config::PureFunctions;
 use crate::faas_interface::FaaSInterface;
+use crate::FaaSModuleInterface;
+use crate::journal::Divergence;
+use crate::journal::Journal;
+use crate::journal::JournalArguments;
+use crate::journal::JournalEntry;
+use crate::journal::JournalResult;
+use crate::journal::ReplayDivergence;
+use crate::journal::ReplayReport;
 use crate::FaaSError;
 use crate::Result;
 use crate::IValue;
 use crate::IType;
 use crate::misc::load_modules_from_fs;
+use crate::module_store::ModuleStore;
+use crate::misc::BinaryEncoding;
 use crate::misc::ModulesLoadStrategy;
 use crate::logger::LoggerFilter;
 use crate::logger::WASM_LOG_ENV_NAME;
+use crate::memoization::CallCache;
+use crate::memoization::MemoizationStats;
+use crate::metrics::CallMetrics;
 
 use fce::FCE;
+use fce::FCEModuleConfig;
+use fce::FCEPreparedModule;
 use fce::IFunctionArg;
 use fce_utils::SharedString;
 use fce::RecordTypes;
 use fluence_sdk_main::CallParameters;
 
+use parking_lot::Mutex;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 use serde_json::Value as JValue;
-use std::cell::RefCell;
+
 use std::convert::TryInto;
 use std::collections::HashSet;
 use std::collections::HashMap;
-use std::rc::Rc;
+use std::path::Path;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 struct ModuleInterface {
-    function_signatures: HashMap<SharedString, (Rc<Vec<IFunctionArg>>, Rc<Vec<IType>>)>,
-    record_types: Rc<RecordTypes>,
+    function_signatures: HashMap<SharedString, (Arc<Vec<IFunctionArg>>, Arc<Vec<IType>>)>,
+    record_types: Arc<RecordTypes>,
+}
+
+/// A module created by FaaS, but not loaded into it yet.
+pub struct PreparedModule {
+    module: FCEPreparedModule,
+    wasm_bytes: Vec<u8>,
+    pure_functions: PureFunctions,
 }
 
-// TODO: remove and use mutex instead
-unsafe impl Send for FluenceFaaS {}
+impl PreparedModule {
+    pub fn name(&self) -> &str {
+        self.module.name()
+    }
+
+    /// Return function signatures and record types exported by the module.
+    pub fn interface(&self) -> FaaSModuleInterface<'_> {
+        self.module.interface()
+    }
+}
 
 pub struct FluenceFaaS {
     /// The Fluence Compute Engine instance.
     fce: FCE,
 
     /// Parameters of call accessible by Wasm modules.
-    call_parameters: Rc<RefCell<CallParameters>>,
+    call_parameters: Arc<Mutex<CallParameters>>,
 
     /// Cached module interfaces by names.
     module_interfaces_cache: HashMap<String, ModuleInterface>,
+
+    /// Metrics of calls of module functions.
+    metrics: Arc<CallMetrics>,
+
+    /// Journal of calls shared with host imports of all modules.
+    journal: Arc<Mutex<Journal>>,
+
+    /// Memoized results of pure functions.
+    call_cache: CallCache,
+
+    /// Options of JSON calls made without explicitly specified ones.
+    json_options: JsonOptions,
+
+    /// Keys used to verify signatures of all loaded modules, including ones loaded later.
+    trusted_keys: Vec<[u8; 32]>,
 }
 
 impl FluenceFaaS {
@@ -90,28 +143,59 @@ impl FluenceFaaS {
     {
         let mut fce = FCE::new();
         let config = config.try_into()?;
-        let call_parameters = Rc::new(RefCell::new(<_>::default()));
+        let call_parameters = Arc::new(Mutex::new(<_>::default()));
+
+        // metrics interceptor goes first to measure time spent in other interceptors as well
+        let metrics = Arc::new(CallMetrics::default());
+        fce.add_interceptor(metrics.clone());
+
+        let journal = match &config.journal_path {
+            Some(journal_path) => Journal::recording(journal_path)?,
+            None => Journal::Disabled,
+        };
+        let journal = Arc::new(Mutex::new(journal));
+
+        let memoization_enabled = config
+            .modules_config
+            .iter()
+            .any(|(_, module_config)| module_config.pure_functions != PureFunctions::None);
+        let mut call_cache = CallCache::new(config.memoization_cache_size, memoization_enabled);
 
         let modules_dir = config.modules_dir;
+        let module_store = config.module_store_dir.map(ModuleStore::new);
+        let module_hashes = config.module_hashes;
+        let trusted_keys = config.trusted_keys;
+        let json_options = config.json_options;
 
         // LoggerFilter can be initialized with an empty string
         let wasm_log_env = std::env::var(WASM_LOG_ENV_NAME).unwrap_or_default();
         let logger_filter = LoggerFilter::from_env_string(&wasm_log_env);
 
         for (module_name, module_config) in config.modules_config {
-            let module_bytes =
-                modules.remove(&module_name).ok_or_else(|| {
+            let module_bytes = match (module_hashes.get(&module_name), &module_store) {
+                // a module referenced by hash takes precedence over a file in modules_dir
+                (Some(hash), Some(module_store)) => module_store.load_wasm(hash)?,
+                _ => modules.remove(&module_name).ok_or_else(|| {
                     FaaSError::InstantiationError(format!(
                     "module with name {} is specified in config (dir: {:?}), but not found in provided modules: {:?}",
                     module_name, modules_dir, modules.keys().collect::<Vec<_>>()
                 ))
-                })?;
+                })?,
+            };
+
+            call_cache.register_module(
+                &module_name,
+                &module_bytes,
+                module_config.pure_functions.clone(),
+            )?;
 
             let fce_module_config = crate::misc::make_fce_config(
                 module_name.clone(),
                 Some(module_config),
                 call_parameters.clone(),
+                journal.clone(),
                 &logger_filter,
+                &trusted_keys,
             )?;
             fce.load_module(module_name, &module_bytes, fce_module_config)?;
         }
@@ -120,6 +204,11 @@ impl FluenceFaaS {
             fce,
             call_parameters,
             module_interfaces_cache: HashMap::new(),
+            metrics,
+            journal,
+            call_cache,
+            json_options,
+            trusted_keys,
         })
     }
 
@@ -148,11 +237,36 @@ impl FluenceFaaS {
         args: &[IValue],
         call_parameters: fluence_sdk_main::CallParameters,
     ) -> Result<Vec<IValue>> {
-        self.call_parameters.replace(call_parameters);
+        let module_name = module_name.as_ref();
+        let func_name = func_name.as_ref();
 
-        self.fce
-            .call(module_name, func_name, args)
-            .map_err(Into::into)
+        let span = call_span(module_name, func_name, &call_parameters);
+        let _entered = span.enter();
+
+        *self.call_parameters.lock() = call_parameters;
+
+        let result = self.call_module(module_name, func_name, args);
+        if let Err(error) = &result {
+            self.metrics
+                .record_faas_error(module_name, func_name, error);
+        }
+
+        let mut journal = self.journal.lock();
+        if journal.is_recording() {
+            let arguments = JournalArguments::IValues(args.iter().map(Into::into).collect());
+            let call_parameters = self.call_parameters.lock().clone();
+            let journal_result = JournalResult::from_ivalues_result(&result);
+            journal.record_call(
+                module_name,
+                func_name,
+                arguments,
+                call_parameters,
+                None,
+                journal_result,
+            );
+        }
+
+        result
     }
 
     /// Call a specified function of loaded on a startup module by its name.
@@ -163,31 +277,472 @@ impl FluenceFaaS {
         json_args: JValue,
         call_parameters: fluence_sdk_main::CallParameters,
     ) -> Result<JValue> {
-        use crate::misc::json_to_ivalues;
-        use crate::misc::ivalues_to_json;
+        let json_options = self.json_options;
+        self.call_with_json_options(
+            module_name,
+            func_name,
+            json_args,
+            call_parameters,
+            json_options,
+        )
+    }
+
+    /// Call a specified function with JSON arguments using the given options of JSON mapping
+    /// instead of the ones from the config.
+    pub fn call_with_json_options<MN: AsRef<str>, FN: AsRef<str>>(
+        &mut self,
+        module_name: MN,
+        func_name: FN,
+        json_args: JValue,
+        call_parameters: fluence_sdk_main::CallParameters,
+        json_options: JsonOptions,
+    ) -> Result<JValue> {
+        let module_name = module_name.as_ref();
+        let func_name = func_name.as_ref();
+
+        let span = call_span(module_name, func_name, &call_parameters);
+        let _entered = span.enter();
+
+        let journaled_call = if self.journal.lock().is_recording() {
+            Some((json_args.clone(), call_parameters.clone()))
+        } else {
+            None
+        };
+
+        let result = self.call_with_json_impl(
+            module_name,
+            func_name,
+            json_args,
+            call_parameters,
+            &json_options,
+        );
+        if let Err(error) = &result {
+            self.metrics
+                .record_faas_error(module_name, func_name, error);
+        }
+
+        if let Some((json_args, call_parameters)) = journaled_call {
+            self.journal.lock().record_call(
+                module_name,
+                func_name,
+                JournalArguments::Json(json_args),
+                call_parameters,
+                Some(json_options),
+                JournalResult::from_json_result(&result),
+            );
+        }
+
+        result
+    }
 
+    /// Call a specified function with arguments encoded in MessagePack or CBOR,
+    /// results are returned in the same encoding.
+    pub fn call_with_binary<MN: AsRef<str>, FN: AsRef<str>>(
+        &mut self,
+        module_name: MN,
+        func_name: FN,
+        encoding: BinaryEncoding,
+        args: &[u8],
+        call_parameters: fluence_sdk_main::CallParameters,
+    ) -> Result<Vec<u8>> {
         let module_name = module_name.as_ref();
         let func_name = func_name.as_ref();
 
+        let result =
+            self.call_with_binary_impl(module_name, func_name, encoding, args, call_parameters);
+        if let Err(error) = &result {
+            self.metrics
+                .record_faas_error(module_name, func_name, error);
+        }
+
+        result
+    }
+
+    /// Call a specified function with Rust arguments, they are converted by serde according
+    /// to the function signature. Arguments could be passed as a tuple or a sequence
+    /// in the order of the signature, as a struct with fields named as the arguments or as
+    /// a unit for functions without arguments. The result is converted to `Ret` in the same way.
+    pub fn call_typed<Args, Ret, MN, FN>(
+        &mut self,
+        module_name: MN,
+        func_name: FN,
+        args: &Args,
+        call_parameters: fluence_sdk_main::CallParameters,
+    ) -> Result<Ret>
+    where
+        Args: Serialize + ?Sized,
+        Ret: DeserializeOwned,
+        MN: AsRef<str>,
+        FN: AsRef<str>,
+    {
+        let module_name = module_name.as_ref();
+        let func_name = func_name.as_ref();
+
+        let result = self.call_typed_impl(module_name, func_name, args, call_parameters);
+        if let Err(error) = &result {
+            self.metrics
+                .record_faas_error(module_name, func_name, error);
+        }
+
+        result
+    }
+
+    /// Register an interceptor that will see all calls of module functions,
+    /// including nested calls between modules.
+    pub fn add_interceptor(&mut self, interceptor: Arc<dyn CallInterceptor>) {
+        let adapter = InterceptorAdapter::new(interceptor, self.call_parameters.clone());
+        self.fce.add_interceptor(Arc::new(adapter));
+    }
+
+    /// Start recording all calls with responses of host imports to a journal file,
+    /// the file is truncated if it already exists.
+    pub fn start_journal<P: AsRef<Path>>(&mut self, journal_path: P) -> Result<()> {
+        *self.journal.lock() = Journal::recording(journal_path.as_ref())?;
+        Ok(())
+    }
+
+    /// Stop recording calls to the journal.
+    pub fn stop_journal(&mut self) {
+        *self.journal.lock() = Journal::Disabled;
+    }
+
+    /// Re-execute calls from a journal, feeding recorded responses of host imports back,
+    /// and report all divergences from the recorded behaviour. Intended to be used with
+    /// a freshly created FaaS, so that modules have the same state as during recording.
+    pub fn replay_journal<P: AsRef<Path>>(&mut self, journal_path: P) -> Result<ReplayReport> {
+        use crate::journal::into_ivalues;
+        use crate::journal::read_journal;
+
+        if !self.journal.lock().is_disabled() {
+            return Err(FaaSError::JournalError(String::from(
+                "journal can't be replayed while calls are being recorded",
+            )));
+        }
+
+        let entries = read_journal(journal_path.as_ref())?;
+        let mut report = ReplayReport::default();
+
+        for (entry_id, entry) in entries.into_iter().enumerate() {
+            let JournalEntry {
+                module_name,
+                function_name,
+                arguments,
+                call_parameters,
+                json_options,
+                host_responses,
+                result: expected,
+            } = entry;
+
+            *self.journal.lock() = Journal::replaying(host_responses);
+
+            let actual = match arguments {
+                JournalArguments::Json(arguments) => {
+                    let json_options = json_options.unwrap_or(self.json_options);
+                    let result = self.call_with_json_options(
+                        &module_name,
+                        &function_name,
+                        arguments,
+                        call_parameters,
+                        json_options,
+                    );
+                    JournalResult::from_json_result(&result)
+                }
+                JournalArguments::IValues(arguments) => {
+                    let result = into_ivalues(arguments).and_then(|arguments| {
+                        self.call_with_ivalues(
+                            &module_name,
+                            &function_name,
+                            &arguments,
+                            call_parameters,
+                        )
+                    });
+                    JournalResult::from_ivalues_result(&result)
+                }
+            };
+
+            let mut divergences = self.journal.lock().finish_replay();
+            if actual != expected {
+                divergences.push(Divergence::Result { expected, actual });
+            }
+
+            let divergences = divergences.into_iter().map(|divergence| ReplayDivergence {
+                entry_id,
+                module_name: module_name.clone(),
+                function_name: function_name.clone(),
+                divergence,
+            });
+            report.divergences.extend(divergences);
+            report.replayed_calls += 1;
+        }
+
+        Ok(report)
+    }
+
+    /// Return all export functions (name and signatures) of loaded modules.
+    pub fn get_interface(&self) -> FaaSInterface<'_> {
+        let modules = self.fce.interface().collect();
+
+        FaaSInterface { modules }
+    }
+
+    /// Return statistics of memoization of pure functions.
+    pub fn memoization_stats(&self) -> MemoizationStats {
+        self.call_cache.stats()
+    }
+
+    /// Return Rust source code with typed bindings to export functions of a module.
+    pub fn rust_bindings<S: AsRef<str>>(&self, module_name: S) -> Result<String> {
+        use crate::rust_bindings::module_rust_bindings;
+
+        let module_name = module_name.as_ref();
+        let module_interface = self
+            .fce
+            .module_interface(module_name)
+            .ok_or_else(|| FaaSError::NoSuchModule(module_name.to_string()))?;
+
+        Ok(module_rust_bindings(module_name, &module_interface))
+    }
+
+    /// Return JSON Schema of arguments and results of export functions of a module
+    /// as they are accepted and returned by call_with_json.
+    pub fn json_schema<S: AsRef<str>>(&self, module_name: S) -> Result<JValue> {
+        use crate::json_schema::module_json_schema;
+
+        let module_name = module_name.as_ref();
+        let module_interface = self
+            .fce
+            .module_interface(module_name)
+            .ok_or_else(|| FaaSError::NoSuchModule(module_name.to_string()))?;
+
+        Ok(module_json_schema(
+            module_name,
+            &module_interface,
+            &self.json_options,
+        ))
+    }
+
+    /// Render metrics of calls of module functions and loaded modules
+    /// in the Prometheus text exposition format.
+    pub fn prometheus_metrics(&self) -> String {
+        use crate::metrics::render_prometheus;
+        use crate::metrics::ModuleMetrics;
+
+        let mut modules = self
+            .fce
+            .interface()
+            .map(|(name, _)| ModuleMetrics {
+                name,
+                // module is loaded, so these unwraps are safe
+                memory_pages: self.fce.module_memory_pages(name).unwrap(),
+                host_imports: self.fce.module_host_imports_stats(name).unwrap(),
+            })
+            .collect::<Vec<_>>();
+        modules.sort_by(|lhs, rhs| lhs.name.cmp(rhs.name));
+
+        render_prometheus(&self.metrics, &modules, self.call_cache.stats())
+    }
+
+    /// Load a new module, if config isn't supplied the default module config is used.
+    pub fn load_module<S, C>(&mut self, name: S, wasm_bytes: &[u8], config: Option<C>) -> Result<()>
+    where
+        S: Into<String>,
+        C: TryInto<crate::FaaSModuleConfig>,
+        FaaSError: From<C::Error>,
+    {
+        let name = name.into();
+        let (fce_module_config, pure_functions) = self.module_config(&name, config)?;
+        self.fce
+            .load_module(name.clone(), wasm_bytes, fce_module_config)?;
+
+        if let Err(e) = self
+            .call_cache
+            .register_module(&name, wasm_bytes, pure_functions)
+        {
+            // the module shouldn't stay loaded if it can't be completely registered
+            self.fce.unload_module(&name)?;
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
+    /// Create a module without loading it, it could be checked by its interface
+    /// before it replaces a loaded module with the same name.
+    pub fn prepare_module<S, C>(
+        &self,
+        name: S,
+        wasm_bytes: &[u8],
+        config: Option<C>,
+    ) -> Result<PreparedModule>
+    where
+        S: Into<String>,
+        C: TryInto<crate::FaaSModuleConfig>,
+        FaaSError: From<C::Error>,
+    {
+        let name = name.into();
+        let (fce_module_config, pure_functions) = self.module_config(&name, config)?;
+        let module = self
+            .fce
+            .prepare_module(name, wasm_bytes, fce_module_config)?;
+
+        Ok(PreparedModule {
+            module,
+            wasm_bytes: wasm_bytes.to_vec(),
+            pure_functions,
+        })
+    }
+
+    /// Replace a loaded module with a prepared one, the loaded module stays intact
+    /// if it fails.
+    pub fn replace_module(&mut self, module: PreparedModule) -> Result<()> {
+        let name = module.name().to_string();
+        self.fce.replace_module(module.module)?;
+
+        self.module_interfaces_cache.remove(&name);
+        self.call_cache.unregister_module(&name);
+        self.call_cache
+            .register_module(&name, &module.wasm_bytes, module.pure_functions)
+    }
+
+    /// Unload a module, modules importing its functions must be unloaded before.
+    pub fn unload_module<S: AsRef<str>>(&mut self, module_name: S) -> Result<()> {
+        let module_name = module_name.as_ref();
+
+        self.fce.unload_module(module_name)?;
+        self.module_interfaces_cache.remove(module_name);
+        self.call_cache.unregister_module(module_name);
+
+        Ok(())
+    }
+
+    fn module_config<C>(
+        &self,
+        name: &str,
+        config: Option<C>,
+    ) -> Result<(FCEModuleConfig, PureFunctions)>
+    where
+        C: TryInto<crate::FaaSModuleConfig>,
+        FaaSError: From<C::Error>,
+    {
+        let config = config.map(|c| c.try_into()).transpose()?;
+
+        // LoggerFilter can be initialized with an empty string
+        let wasm_log_env = std::env::var(WASM_LOG_ENV_NAME).unwrap_or_default();
+        let logger_filter = LoggerFilter::from_env_string(&wasm_log_env);
+
+        let pure_functions = config
+            .as_ref()
+            .map(|config| config.pure_functions.clone())
+            .unwrap_or_default();
+
+        let fce_module_config = crate::misc::make_fce_config(
+            name.to_string(),
+            config,
+            self.call_parameters.clone(),
+            self.journal.clone(),
+            &logger_filter,
+            &self.trusted_keys,
+        )?;
+
+        Ok((fce_module_config, pure_functions))
+    }
+
+    fn call_with_json_impl(
+        &mut self,
+        module_name: &str,
+        func_name: &str,
+        json_args: JValue,
+        call_parameters: fluence_sdk_main::CallParameters,
+        json_options: &JsonOptions,
+    ) -> Result<JValue> {
+        use crate::misc::json_to_ivalues;
+        use crate::misc::ivalues_to_json;
+
         let (func_signature, output_types, record_types) =
             self.lookup_module_interface(module_name, func_name)?;
         let iargs = json_to_ivalues(
             json_args,
             func_signature.iter().map(|arg| (&arg.name, &arg.ty)),
             &record_types,
+            json_options,
         )?;
 
-        self.call_parameters.replace(call_parameters);
-        let result = self.fce.call(module_name, func_name, &iargs)?;
+        *self.call_parameters.lock() = call_parameters;
+        let result = self.call_module(module_name, func_name, &iargs)?;
 
-        ivalues_to_json(result, &output_types, &record_types)
+        ivalues_to_json(result, &output_types, &record_types, json_options)
     }
 
-    /// Return all export functions (name and signatures) of loaded modules.
-    pub fn get_interface(&self) -> FaaSInterface<'_> {
-        let modules = self.fce.interface().collect();
+    fn call_with_binary_impl(
+        &mut self,
+        module_name: &str,
+        func_name: &str,
+        encoding: BinaryEncoding,
+        args: &[u8],
+        call_parameters: fluence_sdk_main::CallParameters,
+    ) -> Result<Vec<u8>> {
+        use crate::misc::binary_to_ivalues;
+        use crate::misc::ivalues_to_binary;
 
-        FaaSInterface { modules }
+        let (func_signature, output_types, record_types) =
+            self.lookup_module_interface(module_name, func_name)?;
+        let iargs = binary_to_ivalues(
+            encoding.decode(args)?,
+            func_signature.iter().map(|arg| (&arg.name, &arg.ty)),
+            &record_types,
+        )?;
+
+        // the call goes through call_with_ivalues to be journaled
+        let result = self.call_with_ivalues(module_name, func_name, &iargs, call_parameters)?;
+        let result = ivalues_to_binary(result, &output_types, &record_types)?;
+
+        encoding.encode(result)
+    }
+
+    fn call_typed_impl<Args: Serialize + ?Sized, Ret: DeserializeOwned>(
+        &mut self,
+        module_name: &str,
+        func_name: &str,
+        args: &Args,
+        call_parameters: fluence_sdk_main::CallParameters,
+    ) -> Result<Ret> {
+        use crate::typed_call::from_outputs;
+        use crate::typed_call::to_arguments;
+
+        let (func_signature, output_types, record_types) =
+            self.lookup_module_interface(module_name, func_name)?;
+        let iargs = to_arguments(args, &func_signature, &record_types)
+            .map_err(|e| FaaSError::TypedArgumentsError(e.render("args")))?;
+
+        // the call goes through call_with_ivalues to be journaled
+        let result = self.call_with_ivalues(module_name, func_name, &iargs, call_parameters)?;
+
+        from_outputs(result, &output_types, &record_types)
+            .map_err(|e| FaaSError::TypedResultError(e.render("result")))
+    }
+
+    /// Calls a function of a module, results of pure functions are memoized.
+    fn call_module(
+        &mut self,
+        module_name: &str,
+        func_name: &str,
+        args: &[IValue],
+    ) -> Result<Vec<IValue>> {
+        let call_key = self.call_cache.call_key(module_name, func_name, args);
+        if let Some(result) = call_key.as_ref().and_then(|key| self.call_cache.get(key)) {
+            // interceptors and metrics see memoized calls as well, and could reject them
+            let result = self
+                .fce
+                .intercept_call(module_name, func_name, args, || Ok(result))?;
+            return Ok(result);
+        }
+
+        let result = self.fce.call(module_name, func_name, args)?;
+        if let Some(call_key) = call_key {
+            self.call_cache.put(call_key, result.clone());
+        }
+
+        Ok(result)
     }
 
     /// At first, tries to find function signature and record types in module_interface_cache,
@@ -196,7 +751,7 @@ impl FluenceFaaS {
         &'faas mut self,
         module_name: &str,
         func_name: &str,
-    ) -> Result<(Rc<Vec<IFunctionArg>>, Rc<Vec<IType>>, Rc<RecordTypes>)> {
+    ) -> Result<(Arc<Vec<IFunctionArg>>, Arc<Vec<IType>>, Arc<RecordTypes>)> {
         use FaaSError::NoSuchModule;
         use FaaSError::MissingFunctionError;
 
@@ -230,7 +785,7 @@ impl FluenceFaaS {
 
         let arg_types = arg_types.clone();
         let output_types = output_types.clone();
-        let record_types = Rc::new(module_interface.record_types.clone());
+        let record_types = Arc::new(module_interface.record_types.clone());
 
         let module_interface = ModuleInterface {
             function_signatures,
@@ -238,43 +793,31 @@ impl FluenceFaaS {
         };
 
         self.module_interfaces_cache
-            .insert(func_name.to_string(), module_interface);
+            .insert(module_name.to_string(), module_interface);
 
         Ok((arg_types, output_types, record_types))
     }
 }
 
+/// Creates a span of a top-level call, spans of nested calls between modules become its children.
+fn call_span(
+    module_name: &str,
+    func_name: &str,
+    call_parameters: &CallParameters,
+) -> tracing::Span {
+    tracing::info_span!(
+        "faas_call",
+        module = module_name,
+        function = func_name,
+        particle_id = call_parameters.particle_id.as_str(),
+        init_peer_id = call_parameters.init_peer_id.as_str(),
+        service_id = call_parameters.service_id.as_str()
+    )
+}
+
 // This API is intended for testing purposes (mostly in FCE REPL)
 #[cfg(feature = "raw-module-api")]
 impl FluenceFaaS {
-    pub fn load_module<S, C>(&mut self, name: S, wasm_bytes: &[u8], config: Option<C>) -> Result<()>
-    where
-        S: Into<String>,
-        C: TryInto<crate::FaaSModuleConfig>,
-        FaaSError: From<C::Error>,
-    {
-        let config = config.map(|c| c.try_into()).transpose()?;
-        let name = name.into();
-
-        // LoggerFilter can be initialized with an empty string
-        let wasm_log_env = std::env::var(WASM_LOG_ENV_NAME).unwrap_or_default();
-        let logger_filter = LoggerFilter::from_env_string(&wasm_log_env);
-
-        let fce_module_config = crate::misc::make_fce_config(
-            name.clone(),
-            config,
-            self.call_parameters.clone(),
-            &logger_filter,
-        )?;
-        self.fce
-            .load_module(name, &wasm_bytes, fce_module_config)
-            .map_err(Into::into)
-    }
-
-    pub fn unload_module<S: AsRef<str>>(&mut self, module_name: S) -> Result<()> {
-        self.fce.unload_module(module_name).map_err(Into::into)
-    }
-
     pub fn module_wasi_state<S: AsRef<str>>(
         &mut self,
         module_name: S,
@@ -0,0 +1,186 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::FaaSError;
+use crate::IValue;
+use crate::JsonOptions;
+use crate::Result;
+
+use fluence_sdk_main::CallParameters;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value as JValue;
+
+use std::convert::TryFrom;
+use std::io::BufRead;
+use std::path::Path;
+
+/// One call of a module function recorded in a journal, one entry is stored per line.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub module_name: String,
+    pub function_name: String,
+    pub arguments: JournalArguments,
+    pub call_parameters: CallParameters,
+
+    /// Options of JSON mapping, present only for calls with JSON arguments.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub json_options: Option<JsonOptions>,
+
+    /// Responses of host imports (including mounted binaries) in the order they were made
+    /// during the call, including calls from nested modules.
+    pub host_responses: Vec<HostResponse>,
+    pub result: JournalResult,
+}
+
+/// Arguments of a call in the form they were passed to FaaS.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum JournalArguments {
+    Json(JValue),
+    IValues(Vec<JournalValue>),
+}
+
+/// Result of a call in the form it was returned from FaaS.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum JournalResult {
+    Json(JValue),
+    IValues(Vec<JournalValue>),
+    Error(String),
+}
+
+/// A host import call made by a module.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HostResponse {
+    pub module_name: String,
+    pub import_name: String,
+    pub arguments: Vec<JournalValue>,
+    pub result: Option<JournalValue>,
+}
+
+/// Serializable counterpart of IValue.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum JournalValue {
+    S8(i8),
+    S16(i16),
+    S32(i32),
+    S64(i64),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    I32(i32),
+    I64(i64),
+    F32(f32),
+    F64(f64),
+    String(String),
+    Array(Vec<JournalValue>),
+    Record(Vec<JournalValue>),
+}
+
+impl JournalResult {
+    pub(crate) fn from_json_result(result: &Result<JValue>) -> Self {
+        match result {
+            Ok(value) => JournalResult::Json(value.clone()),
+            Err(e) => JournalResult::Error(e.to_string()),
+        }
+    }
+
+    pub(crate) fn from_ivalues_result(result: &Result<Vec<IValue>>) -> Self {
+        match result {
+            Ok(values) => JournalResult::IValues(values.iter().map(Into::into).collect()),
+            Err(e) => JournalResult::Error(e.to_string()),
+        }
+    }
+}
+
+impl From<&IValue> for JournalValue {
+    fn from(value: &IValue) -> Self {
+        match value {
+            IValue::S8(v) => JournalValue::S8(*v),
+            IValue::S16(v) => JournalValue::S16(*v),
+            IValue::S32(v) => JournalValue::S32(*v),
+            IValue::S64(v) => JournalValue::S64(*v),
+            IValue::U8(v) => JournalValue::U8(*v),
+            IValue::U16(v) => JournalValue::U16(*v),
+            IValue::U32(v) => JournalValue::U32(*v),
+            IValue::U64(v) => JournalValue::U64(*v),
+            IValue::I32(v) => JournalValue::I32(*v),
+            IValue::I64(v) => JournalValue::I64(*v),
+            IValue::F32(v) => JournalValue::F32(*v),
+            IValue::F64(v) => JournalValue::F64(*v),
+            IValue::String(v) => JournalValue::String(v.clone()),
+            IValue::Array(values) => JournalValue::Array(values.iter().map(Into::into).collect()),
+            IValue::Record(values) => JournalValue::Record(values.iter().map(Into::into).collect()),
+        }
+    }
+}
+
+impl TryFrom<JournalValue> for IValue {
+    type Error = FaaSError;
+
+    fn try_from(value: JournalValue) -> Result<Self> {
+        let value = match value {
+            JournalValue::S8(v) => IValue::S8(v),
+            JournalValue::S16(v) => IValue::S16(v),
+            JournalValue::S32(v) => IValue::S32(v),
+            JournalValue::S64(v) => IValue::S64(v),
+            JournalValue::U8(v) => IValue::U8(v),
+            JournalValue::U16(v) => IValue::U16(v),
+            JournalValue::U32(v) => IValue::U32(v),
+            JournalValue::U64(v) => IValue::U64(v),
+            JournalValue::I32(v) => IValue::I32(v),
+            JournalValue::I64(v) => IValue::I64(v),
+            JournalValue::F32(v) => IValue::F32(v),
+            JournalValue::F64(v) => IValue::F64(v),
+            JournalValue::String(v) => IValue::String(v),
+            JournalValue::Array(values) => IValue::Array(into_ivalues(values)?),
+            JournalValue::Record(values) => {
+                let values = crate::ne_vec::NEVec::new(into_ivalues(values)?).map_err(|_| {
+                    FaaSError::JournalError(String::from("record without fields in journal"))
+                })?;
+                IValue::Record(values)
+            }
+        };
+
+        Ok(value)
+    }
+}
+
+pub(crate) fn into_ivalues(values: Vec<JournalValue>) -> Result<Vec<IValue>> {
+    values.into_iter().map(IValue::try_from).collect()
+}
+
+/// Reads all entries from a journal file.
+pub(crate) fn read_journal(journal_path: &Path) -> Result<Vec<JournalEntry>> {
+    let file = std::fs::File::open(journal_path)
+        .map_err(|e| FaaSError::IOError(format!("{:?}: {}", journal_path, e)))?;
+
+    std::io::BufReader::new(file)
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, Ok(line) if line.trim().is_empty()))
+        .map(|(line_id, line)| {
+            serde_json::from_str(&line?).map_err(|e| {
+                FaaSError::JournalError(format!(
+                    "{:?}:{}: malformed journal entry: {}",
+                    journal_path,
+                    line_id + 1,
+                    e
+                ))
+            })
+        })
+        .collect()
+}
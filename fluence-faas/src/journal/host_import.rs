@@ -0,0 +1,90 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::HostResponse;
+use super::Journal;
+use crate::IValue;
+
+use fce::HostImportDescriptor;
+use fce::HostImportError;
+use parking_lot::Mutex;
+use wasmer_core::vm::Ctx;
+
+use std::sync::Arc;
+
+/// Wraps a host import to record its responses to the journal and replay them back.
+pub(crate) fn journaled_host_import(
+    descriptor: HostImportDescriptor,
+    module_name: String,
+    import_name: String,
+    journal: Arc<Mutex<Journal>>,
+) -> HostImportDescriptor {
+    let HostImportDescriptor {
+        host_exported_func,
+        argument_types,
+        output_type,
+        error_handler,
+    } = descriptor;
+
+    // the handler is shared with the engine, which calls it on lifting errors
+    let error_handler = error_handler.map(|handler| Arc::new(Mutex::new(handler)));
+    let divergence_handler = error_handler.clone();
+
+    let journaled_func = move |ctx: &mut Ctx, args: Vec<IValue>| {
+        if journal.lock().is_disabled() {
+            return host_exported_func(ctx, args);
+        }
+
+        let replayed = journal
+            .lock()
+            .replay_host_response(&module_name, &import_name, &args);
+        match replayed {
+            Some(Ok(result)) => return result,
+            // the live host import mustn't be called during a replay, and a panic here would
+            // abort the whole process, so the module gets an error value (or no value at all)
+            // instead, the divergence itself is already in the journal
+            Some(Err(divergence)) => {
+                let error =
+                    HostImportError::CallAborted(format!("replay has diverged: {}", divergence));
+                return divergence_handler
+                    .as_ref()
+                    .and_then(|handler| (handler.lock())(&error));
+            }
+            None => {}
+        }
+
+        let arguments = args.iter().map(Into::into).collect();
+        // the lock isn't held during the call, because a host import could take a while
+        let result = host_exported_func(ctx, args);
+
+        journal.lock().record_host_response(HostResponse {
+            module_name: module_name.clone(),
+            import_name: import_name.clone(),
+            arguments,
+            result: result.as_ref().map(Into::into),
+        });
+
+        result
+    };
+
+    HostImportDescriptor {
+        host_exported_func: Box::new(journaled_func),
+        argument_types,
+        output_type,
+        error_handler: error_handler
+            .map(|handler| Box::new(move |error: &HostImportError| (handler.lock())(error)) as _),
+    }
+}
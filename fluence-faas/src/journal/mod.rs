@@ -0,0 +1,34 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+mod entry;
+mod host_import;
+mod recorder;
+mod replay;
+
+pub use entry::JournalEntry;
+pub use entry::JournalArguments;
+pub use entry::JournalResult;
+pub use entry::JournalValue;
+pub use entry::HostResponse;
+pub use replay::Divergence;
+pub use replay::ReplayDivergence;
+pub use replay::ReplayReport;
+
+pub(crate) use entry::into_ivalues;
+pub(crate) use entry::read_journal;
+pub(crate) use host_import::journaled_host_import;
+pub(crate) use recorder::Journal;
@@ -0,0 +1,223 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::Divergence;
+use super::HostResponse;
+use super::JournalArguments;
+use super::JournalEntry;
+use super::JournalResult;
+use super::JournalValue;
+use crate::FaaSError;
+use crate::IValue;
+use crate::JsonOptions;
+use crate::Result;
+
+use fluence_sdk_main::CallParameters;
+
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+/// State of the call journal shared between FaaS and host imports of all its modules.
+pub(crate) enum Journal {
+    Disabled,
+
+    /// Calls are written to the file, host responses are collected for the current call.
+    Recording {
+        file: File,
+        host_responses: Vec<HostResponse>,
+    },
+
+    /// Host imports return recorded responses of the current call instead of being executed.
+    Replaying {
+        host_responses: VecDeque<HostResponse>,
+        divergences: Vec<Divergence>,
+    },
+}
+
+impl Default for Journal {
+    fn default() -> Self {
+        Journal::Disabled
+    }
+}
+
+impl Journal {
+    pub(crate) fn recording(journal_path: &Path) -> Result<Self> {
+        let file = File::create(journal_path)
+            .map_err(|e| FaaSError::IOError(format!("{:?}: {}", journal_path, e)))?;
+
+        Ok(Journal::Recording {
+            file,
+            host_responses: Vec::new(),
+        })
+    }
+
+    pub(crate) fn replaying(host_responses: Vec<HostResponse>) -> Self {
+        Journal::Replaying {
+            host_responses: host_responses.into(),
+            divergences: Vec::new(),
+        }
+    }
+
+    pub(crate) fn is_disabled(&self) -> bool {
+        matches!(self, Journal::Disabled)
+    }
+
+    pub(crate) fn is_recording(&self) -> bool {
+        matches!(self, Journal::Recording { .. })
+    }
+
+    /// Writes a finished call with host responses collected during it, if the journal is recording.
+    pub(crate) fn record_call(
+        &mut self,
+        module_name: &str,
+        function_name: &str,
+        arguments: JournalArguments,
+        call_parameters: CallParameters,
+        json_options: Option<JsonOptions>,
+        result: JournalResult,
+    ) {
+        let (file, host_responses) = match self {
+            Journal::Recording {
+                file,
+                host_responses,
+            } => (file, host_responses),
+            _ => return,
+        };
+
+        let entry = JournalEntry {
+            module_name: module_name.to_string(),
+            function_name: function_name.to_string(),
+            arguments,
+            call_parameters,
+            json_options,
+            host_responses: std::mem::take(host_responses),
+            result,
+        };
+
+        // a failed journal write shouldn't fail the call itself
+        let write_result = serde_json::to_string(&entry)
+            .map_err(|e| e.to_string())
+            .and_then(|line| writeln!(file, "{}", line).map_err(|e| e.to_string()));
+        if let Err(e) = write_result {
+            log::warn!(
+                "call of {}.{} hasn't been journaled: {}",
+                module_name,
+                function_name,
+                e
+            );
+        }
+    }
+
+    pub(crate) fn record_host_response(&mut self, response: HostResponse) {
+        if let Journal::Recording { host_responses, .. } = self {
+            host_responses.push(response);
+        }
+    }
+
+    /// Returns a recorded response for this host import call, if the journal is replaying.
+    /// A call that doesn't match the next recorded one is a divergence, the recorded
+    /// response is left in place then, so that it's reported as missing.
+    pub(crate) fn replay_host_response(
+        &mut self,
+        module_name: &str,
+        import_name: &str,
+        arguments: &[IValue],
+    ) -> Option<std::result::Result<Option<IValue>, Divergence>> {
+        let (host_responses, divergences) = match self {
+            Journal::Replaying {
+                host_responses,
+                divergences,
+            } => (host_responses, divergences),
+            _ => return None,
+        };
+
+        let host_import_divergence = |reason: String| Divergence::HostImport {
+            module_name: module_name.to_string(),
+            import_name: import_name.to_string(),
+            reason,
+        };
+
+        let divergence = match host_responses.front() {
+            None => Some(Divergence::UnrecordedHostImportCall {
+                module_name: module_name.to_string(),
+                import_name: import_name.to_string(),
+            }),
+            Some(response)
+                if response.module_name != module_name || response.import_name != import_name =>
+            {
+                Some(Divergence::UnexpectedHostImportCall {
+                    module_name: module_name.to_string(),
+                    import_name: import_name.to_string(),
+                    expected_module_name: response.module_name.clone(),
+                    expected_import_name: response.import_name.clone(),
+                })
+            }
+            Some(_) => None,
+        };
+        if let Some(divergence) = divergence {
+            divergences.push(divergence.clone());
+            return Some(Err(divergence));
+        }
+
+        // the front response is present and matches the call
+        let response = host_responses.pop_front()?;
+        let arguments = arguments
+            .iter()
+            .map(Into::into)
+            .collect::<Vec<JournalValue>>();
+        if response.arguments != arguments {
+            divergences.push(host_import_divergence(format!(
+                "called with {:?}, but {:?} has been recorded",
+                arguments, response.arguments
+            )));
+        }
+
+        let result = response
+            .result
+            .map(IValue::try_from)
+            .transpose()
+            .map_err(|e| host_import_divergence(format!("recorded response is malformed: {}", e)));
+        if let Err(divergence) = &result {
+            divergences.push(divergence.clone());
+        }
+
+        Some(result)
+    }
+
+    /// Finishes replaying of a call, returns divergences found in host import calls.
+    pub(crate) fn finish_replay(&mut self) -> Vec<Divergence> {
+        match std::mem::take(self) {
+            Journal::Replaying {
+                host_responses,
+                mut divergences,
+            } => {
+                if !host_responses.is_empty() {
+                    divergences.push(Divergence::MissingHostImportCalls(
+                        host_responses.into_iter().collect(),
+                    ));
+                }
+                divergences
+            }
+            journal => {
+                *self = journal;
+                Vec::new()
+            }
+        }
+    }
+}
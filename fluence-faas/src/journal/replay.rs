@@ -0,0 +1,132 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::HostResponse;
+use super::JournalResult;
+
+use serde::Serialize;
+
+/// Difference between a recorded call and its replay.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum Divergence {
+    /// The call returned another result.
+    Result {
+        expected: JournalResult,
+        actual: JournalResult,
+    },
+
+    /// A host import has been called in a different way than it was recorded.
+    HostImport {
+        module_name: String,
+        import_name: String,
+        reason: String,
+    },
+
+    /// A host import has been called when no more calls have been recorded.
+    UnrecordedHostImportCall {
+        module_name: String,
+        import_name: String,
+    },
+
+    /// A host import has been called instead of the recorded one.
+    UnexpectedHostImportCall {
+        module_name: String,
+        import_name: String,
+        expected_module_name: String,
+        expected_import_name: String,
+    },
+
+    /// These recorded host import calls haven't been made during the replay.
+    MissingHostImportCalls(Vec<HostResponse>),
+}
+
+/// Divergence found in a journal entry with the given number (starting from 0).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReplayDivergence {
+    pub entry_id: usize,
+    pub module_name: String,
+    pub function_name: String,
+    pub divergence: Divergence,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct ReplayReport {
+    pub replayed_calls: usize,
+    pub divergences: Vec<ReplayDivergence>,
+}
+
+impl ReplayReport {
+    /// Returns true if all calls have been replayed exactly as they were recorded.
+    pub fn is_consistent(&self) -> bool {
+        self.divergences.is_empty()
+    }
+}
+
+impl std::fmt::Display for Divergence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Divergence::Result { expected, actual } => write!(
+                f,
+                "result {:?} differs from the recorded {:?}",
+                actual, expected
+            ),
+            Divergence::HostImport {
+                module_name,
+                import_name,
+                reason,
+            } => write!(
+                f,
+                "host import {}.{} diverged: {}",
+                module_name, import_name, reason
+            ),
+            Divergence::UnrecordedHostImportCall {
+                module_name,
+                import_name,
+            } => write!(
+                f,
+                "host import {}.{} has been called, but the call hasn't been recorded",
+                module_name, import_name
+            ),
+            Divergence::UnexpectedHostImportCall {
+                module_name,
+                import_name,
+                expected_module_name,
+                expected_import_name,
+            } => write!(
+                f,
+                "host import {}.{} has been called instead of the recorded {}.{}",
+                module_name, import_name, expected_module_name, expected_import_name
+            ),
+            Divergence::MissingHostImportCalls(responses) => {
+                write!(f, "recorded host import calls haven't been made:")?;
+                for response in responses {
+                    write!(f, " {}.{}", response.module_name, response.import_name)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for ReplayDivergence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "entry {} ({}.{}): {}",
+            self.entry_id, self.module_name, self.function_name, self.divergence
+        )
+    }
+}
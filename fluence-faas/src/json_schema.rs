@@ -0,0 +1,277 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::ByteArrayEncoding;
+use crate::FaaSModuleInterface;
+use crate::IType;
+use crate::JsonOptions;
+use crate::faas_interface::itype_text_view;
+
+use fce::RecordTypes;
+use serde_json::json;
+use serde_json::Value as JValue;
+
+/// JSON Schema draft used by generated schemas.
+pub const JSON_SCHEMA_DRAFT: &str = "http://json-schema.org/draft-07/schema#";
+const ARGUMENT_RECORDS_PATH: &str = "#/definitions/arguments/";
+const RESULT_RECORDS_PATH: &str = "#/definitions/results/";
+const FUNCTIONS_PATH: &str = "#/definitions/functions/";
+
+/// Builds a JSON Schema (draft 7) document describing JSON arguments and results of every
+/// export function of a module in the same way as they are accepted and returned by
+/// `call_with_json` with the supplied options.
+///
+/// The document describes an object with a property for each function, its value is
+/// an object with arguments and result of the function. Schemas of functions are
+/// in definitions/functions, schemas of records in definitions/arguments and
+/// definitions/results, so they could be referenced separately.
+///
+/// Arguments could be passed as an object by argument names, as a positional array,
+/// as a bare value for one-argument functions and as null for functions without arguments.
+/// The implicit flattening of one-element arrays into scalars isn't described.
+pub fn module_json_schema(
+    module_name: &str,
+    module_interface: &FaaSModuleInterface<'_>,
+    options: &JsonOptions,
+) -> JValue {
+    let record_types = module_interface.record_types;
+
+    let mut argument_records = serde_json::Map::new();
+    let mut result_records = serde_json::Map::new();
+    for record_type in record_types.values() {
+        let fields = record_type
+            .fields
+            .iter()
+            .map(|field| (field.name.as_str(), &field.ty))
+            .collect::<Vec<_>>();
+
+        argument_records.insert(
+            record_type.name.clone(),
+            arguments_schema(&fields, record_types, options, false),
+        );
+        result_records.insert(
+            record_type.name.clone(),
+            object_schema(&fields, |ty| result_schema(ty, record_types, options)),
+        );
+    }
+
+    let functions = module_interface
+        .function_signatures
+        .iter()
+        .map(|signature| {
+            let arguments = signature
+                .arguments
+                .iter()
+                .map(|arg| (arg.name.as_str(), &arg.ty))
+                .collect::<Vec<_>>();
+
+            let result = match signature.outputs.first() {
+                Some(output) => result_schema(output, record_types, options),
+                None => json!({ "type": "null" }),
+            };
+
+            let schema = json!({
+                "type": "object",
+                "properties": {
+                    "arguments": arguments_schema(&arguments, record_types, options, true),
+                    "result": result,
+                },
+                "required": ["arguments", "result"],
+                "additionalProperties": false,
+            });
+
+            (signature.name.to_string(), schema)
+        })
+        .collect::<serde_json::Map<_, _>>();
+
+    let properties = functions
+        .keys()
+        .map(|name| {
+            let reference = json!({ "$ref": format!("{}{}", FUNCTIONS_PATH, name) });
+            (name.clone(), reference)
+        })
+        .collect::<serde_json::Map<_, _>>();
+
+    json!({
+        "$schema": JSON_SCHEMA_DRAFT,
+        "title": module_name,
+        "type": "object",
+        "properties": properties,
+        "additionalProperties": false,
+        "definitions": {
+            "arguments": argument_records,
+            "results": result_records,
+            "functions": functions,
+        },
+    })
+}
+
+/// Describes arguments of a function or fields of a record, they could be passed
+/// as an object or as a positional array.
+fn arguments_schema(
+    arguments: &[(&str, &IType)],
+    record_types: &RecordTypes,
+    options: &JsonOptions,
+    is_function: bool,
+) -> JValue {
+    let schema_of = |ty: &IType| argument_schema(ty, record_types, options);
+
+    let items = arguments
+        .iter()
+        .map(|(_, ty)| schema_of(ty))
+        .collect::<Vec<_>>();
+    let mut forms = vec![
+        object_schema(arguments, &schema_of),
+        json!({
+            "type": "array",
+            "items": items,
+            "minItems": arguments.len(),
+            "maxItems": arguments.len(),
+        }),
+    ];
+
+    if is_function {
+        match arguments {
+            [] => forms.push(json!({ "type": "null" })),
+            // a bare value is considered as the only argument if it isn't an array or an object
+            [(_, ty)] => forms.push(json!({
+                "allOf": [
+                    schema_of(ty),
+                    { "not": { "type": ["array", "object", "null"] } },
+                ]
+            })),
+            _ => {}
+        }
+    }
+
+    json!({ "oneOf": forms })
+}
+
+fn object_schema(fields: &[(&str, &IType)], field_schema: impl Fn(&IType) -> JValue) -> JValue {
+    let properties = fields
+        .iter()
+        .map(|(name, ty)| (name.to_string(), field_schema(ty)))
+        .collect::<serde_json::Map<_, _>>();
+    let required = fields.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    })
+}
+
+/// Describes values accepted as an argument of the given type.
+fn argument_schema(ty: &IType, record_types: &RecordTypes, options: &JsonOptions) -> JValue {
+    match ty {
+        IType::S64 | IType::U64 | IType::I64 if options.int64_as_string => json!({
+            "anyOf": [integer_schema(ty), decimal_string_schema()]
+        }),
+        // byte arrays are accepted as arrays of numbers and in any encoding regardless of options
+        IType::Array(value_type) if **value_type == IType::U8 => json!({
+            "anyOf": [
+                { "type": "array", "items": integer_schema(value_type) },
+                base64_schema(),
+                hex_schema(),
+            ]
+        }),
+        IType::Array(value_type) => json!({
+            "type": "array",
+            "items": argument_schema(value_type, record_types, options),
+        }),
+        IType::Record(_) => record_reference(ARGUMENT_RECORDS_PATH, ty, record_types),
+        ty => scalar_schema(ty),
+    }
+}
+
+/// Describes values returned as a result of the given type.
+fn result_schema(ty: &IType, record_types: &RecordTypes, options: &JsonOptions) -> JValue {
+    match ty {
+        IType::S64 | IType::U64 | IType::I64 if options.int64_as_string => decimal_string_schema(),
+        IType::Array(value_type) if **value_type == IType::U8 => {
+            match options.byte_array_encoding {
+                ByteArrayEncoding::Array => {
+                    json!({ "type": "array", "items": integer_schema(value_type) })
+                }
+                ByteArrayEncoding::Base64 => base64_schema(),
+                ByteArrayEncoding::Hex => hex_schema(),
+            }
+        }
+        IType::Array(value_type) => json!({
+            "type": "array",
+            "items": result_schema(value_type, record_types, options),
+        }),
+        IType::Record(_) => record_reference(RESULT_RECORDS_PATH, ty, record_types),
+        ty => scalar_schema(ty),
+    }
+}
+
+fn scalar_schema(ty: &IType) -> JValue {
+    match ty {
+        IType::S8
+        | IType::S16
+        | IType::S32
+        | IType::S64
+        | IType::U8
+        | IType::U16
+        | IType::U32
+        | IType::U64
+        | IType::I32
+        | IType::I64 => integer_schema(ty),
+        IType::F32 | IType::F64 => json!({ "type": "number" }),
+        IType::String => json!({ "type": "string" }),
+        // there is no JSON representation for other types, so nothing is accepted
+        IType::Array(_) | IType::Record(_) | IType::Anyref => json!(false),
+    }
+}
+
+fn integer_schema(ty: &IType) -> JValue {
+    let (minimum, maximum) = match ty {
+        IType::S8 => (json!(i8::MIN), json!(i8::MAX)),
+        IType::S16 => (json!(i16::MIN), json!(i16::MAX)),
+        IType::S32 | IType::I32 => (json!(i32::MIN), json!(i32::MAX)),
+        IType::S64 | IType::I64 => (json!(i64::MIN), json!(i64::MAX)),
+        IType::U8 => (json!(u8::MIN), json!(u8::MAX)),
+        IType::U16 => (json!(u16::MIN), json!(u16::MAX)),
+        IType::U32 => (json!(u32::MIN), json!(u32::MAX)),
+        // only U64 remains among integer types
+        _ => (json!(u64::MIN), json!(u64::MAX)),
+    };
+
+    json!({
+        "type": "integer",
+        "minimum": minimum,
+        "maximum": maximum,
+    })
+}
+
+fn decimal_string_schema() -> JValue {
+    json!({ "type": "string", "pattern": "^-?[0-9]+$" })
+}
+
+fn base64_schema() -> JValue {
+    json!({ "type": "string", "contentEncoding": "base64" })
+}
+
+fn hex_schema() -> JValue {
+    json!({ "type": "string", "pattern": "^0x([0-9a-fA-F]{2})*$" })
+}
+
+fn record_reference(definitions_path: &str, ty: &IType, record_types: &RecordTypes) -> JValue {
+    let record_name = itype_text_view(ty, record_types);
+    json!({ "$ref": format!("{}{}", definitions_path, record_name) })
+}
@@ -24,36 +24,71 @@
     unreachable_patterns
 )]
 
+mod call_interceptor;
 mod config;
 mod errors;
 mod logger;
 mod faas;
 mod faas_interface;
+mod journal;
+mod json_schema;
+mod memoization;
+mod metrics;
 mod misc;
+mod module_store;
 mod raw_toml_config;
+mod rust_bindings;
+mod typed_call;
 
 pub(crate) type Result<T> = std::result::Result<T, FaaSError>;
 
 pub use faas::FluenceFaaS;
+pub use faas::PreparedModule;
+pub use call_interceptor::CallContext;
+pub use call_interceptor::CallInterceptor;
 pub use faas_interface::FaaSInterface;
 pub use faas_interface::itype_text_view;
+pub use json_schema::module_json_schema;
+pub use json_schema::JSON_SCHEMA_DRAFT;
+pub use rust_bindings::module_rust_bindings;
+pub use journal::JournalEntry;
+pub use journal::JournalArguments;
+pub use journal::JournalResult;
+pub use journal::JournalValue;
+pub use journal::HostResponse;
+pub use journal::Divergence;
+pub use journal::ReplayDivergence;
+pub use journal::ReplayReport;
+pub use memoization::MemoizationStats;
+pub use module_store::ModuleStore;
+pub use module_store::StoredModule;
+pub use misc::BinaryEncoding;
 
 pub use config::FaaSConfig;
 pub use config::FaaSModuleConfig;
 pub use config::FaaSWASIConfig;
+pub use config::PureFunctions;
+pub use config::JsonOptions;
+pub use config::ByteArrayEncoding;
 
 pub use raw_toml_config::TomlFaaSConfig;
 pub use raw_toml_config::TomlFaaSModuleConfig;
 pub use raw_toml_config::TomlFaaSNamedModuleConfig;
 pub use raw_toml_config::TomlWASIConfig;
+pub use raw_toml_config::TomlWasmFeatures;
 pub use raw_toml_config::from_toml_faas_config;
 pub use raw_toml_config::from_toml_module_config;
 pub use raw_toml_config::from_toml_named_module_config;
 pub use raw_toml_config::from_toml_wasi_config;
+pub use raw_toml_config::from_toml_wasm_features;
 
 pub use errors::FaaSError;
+pub use errors::JsonArgumentError;
+pub use errors::JsonArgumentErrorKind;
+pub use errors::JsonKind;
 
 // Re-exports from FCE
+pub use fce::FCEError;
 pub use fce::IValue;
 pub use fce::IRecordType;
 pub use fce::IFunctionArg;
@@ -66,6 +101,14 @@ pub use fce::HostImportDescriptor;
 pub use fce::HostImportError;
 pub use fce::to_interface_value;
 pub use fce::from_interface_values;
+pub use fce::module_signature;
+pub use fce::sign_module;
+pub use fce::signing_public_key;
+pub use fce::verify_module_signature;
+pub use fce::ModuleSignatureConfig;
+pub use fce::WasmFeatures;
+pub use fce::FeatureViolation;
+pub use fce::SIGNATURE_SECTION_NAME;
 pub use fce::ne_vec;
 
 pub use fluence_sdk_main::CallParameters;
@@ -16,6 +16,26 @@
 
 use wasmer_core::vm::Ctx;
 use wasmer_core::memory::ptr::{Array, WasmPtr};
+use once_cell::sync::Lazy;
+use once_cell::sync::OnceCell;
+use parking_lot::Mutex;
+use tracing_core::callsite::Callsite;
+use tracing_core::callsite::Identifier;
+use tracing_core::field::FieldSet;
+use tracing_core::field::Value;
+use tracing_core::metadata::Kind;
+use tracing_core::subscriber::Interest;
+use tracing_core::Event;
+use tracing_core::Metadata;
+
+use std::collections::HashMap;
+
+const MESSAGE_FIELD_NAME: &str = "message";
+
+/// Metadata of events emitted by modules, tracing needs it to be static,
+/// so it's created once for each module and level and never freed.
+static MODULE_CALLSITES: Lazy<Mutex<HashMap<(String, log::Level), &'static ModuleCallsite>>> =
+    Lazy::new(<_>::default);
 
 pub(crate) fn log_utf8_string_closure(
     logging_mask: i32,
@@ -39,6 +59,8 @@ pub(crate) fn log_utf8_string(
     let msg = read_string(ctx, msg_offset, msg_size);
 
     match msg {
+        // inside a call span the message is emitted as an event to be correlated with it
+        Some(msg) if !tracing::Span::current().is_none() => trace_string(module, level, msg),
         Some(msg) => log::logger().log(
             &log::Record::builder()
                 .args(format_args!("{}", msg))
@@ -51,6 +73,80 @@ pub(crate) fn log_utf8_string(
     }
 }
 
+/// Emits an event with the module as its target and the level the module asked for.
+fn trace_string(module: &str, level: log::Level, msg: &str) {
+    let metadata = ModuleCallsite::get(module, level).metadata();
+    let enabled = tracing_core::dispatcher::get_default(|dispatch| dispatch.enabled(metadata));
+    if !enabled {
+        return;
+    }
+
+    let fields = metadata.fields();
+    let message_field = fields
+        .field(MESSAGE_FIELD_NAME)
+        .expect("metadata of module callsites always has the message field");
+    let message = format_args!("{}", msg);
+    Event::dispatch(
+        metadata,
+        &fields.value_set(&[(&message_field, Some(&message as &dyn Value))]),
+    );
+}
+
+/// A callsite created at runtime, since targets of events are known only after modules are loaded.
+struct ModuleCallsite {
+    metadata: OnceCell<Metadata<'static>>,
+}
+
+impl ModuleCallsite {
+    fn get(module: &str, level: log::Level) -> &'static Self {
+        let mut callsites = MODULE_CALLSITES.lock();
+        if let Some(callsite) = callsites.get(&(module.to_string(), level)) {
+            return callsite;
+        }
+
+        let callsite: &'static Self = Box::leak(Box::new(Self {
+            metadata: OnceCell::new(),
+        }));
+        let target: &'static str = Box::leak(module.to_string().into_boxed_str());
+        let metadata = Metadata::new(
+            "module log event",
+            target,
+            tracing_level(level),
+            None,
+            None,
+            Some(target),
+            FieldSet::new(&[MESSAGE_FIELD_NAME], Identifier(callsite)),
+            Kind::EVENT,
+        );
+        let _ = callsite.metadata.set(metadata);
+        tracing_core::callsite::register(callsite);
+
+        callsites.insert((module.to_string(), level), callsite);
+        callsite
+    }
+}
+
+impl Callsite for ModuleCallsite {
+    // interest isn't cached, subscribers are asked on each event instead
+    fn set_interest(&self, _interest: Interest) {}
+
+    fn metadata(&self) -> &Metadata<'_> {
+        self.metadata
+            .get()
+            .expect("metadata is set before the callsite is registered")
+    }
+}
+
+fn tracing_level(level: log::Level) -> tracing_core::Level {
+    match level {
+        log::Level::Error => tracing_core::Level::ERROR,
+        log::Level::Warn => tracing_core::Level::WARN,
+        log::Level::Info => tracing_core::Level::INFO,
+        log::Level::Debug => tracing_core::Level::DEBUG,
+        log::Level::Trace => tracing_core::Level::TRACE,
+    }
+}
+
 #[inline]
 fn read_string(ctx: &Ctx, offset: i32, size: i32) -> Option<&str> {
     let wasm_ptr = WasmPtr::<u8, Array>::new(offset as _);
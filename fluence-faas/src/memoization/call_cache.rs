@@ -0,0 +1,173 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::call_key::CallKey;
+use super::lru_cache::LruCache;
+use super::purity::check_purity;
+use crate::config::PureFunctions;
+use crate::IValue;
+use crate::Result;
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+const DEFAULT_CACHE_SIZE: usize = 1024;
+
+/// Statistics of the memoization cache.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MemoizationStats {
+    /// Calls returned from the cache.
+    pub hits: u64,
+
+    /// Calls of pure functions that have been executed and cached.
+    pub misses: u64,
+
+    /// Results removed from the cache to make room for new ones.
+    pub evictions: u64,
+
+    /// Calls of functions marked as pure that have been executed without the cache,
+    /// because their modules import non-deterministic functions.
+    pub bypassed: u64,
+
+    /// Count of results in the cache.
+    pub cached_results: u64,
+}
+
+/// Caches results of calls of pure functions.
+pub(crate) struct CallCache {
+    /// Functions which calls could be memoized by module names.
+    memoized_functions: HashMap<String, PureFunctions>,
+
+    /// Functions marked as pure in modules that import non-deterministic functions.
+    bypassed_functions: HashMap<String, PureFunctions>,
+
+    /// Modules without non-deterministic imports, whether marked as pure or not.
+    pure_modules: HashSet<String>,
+
+    /// Modules are checked for purity only if some of them have pure functions.
+    enabled: bool,
+
+    results: LruCache<CallKey, Vec<IValue>>,
+    stats: MemoizationStats,
+}
+
+impl CallCache {
+    pub(crate) fn new(capacity: Option<usize>, enabled: bool) -> Self {
+        Self {
+            memoized_functions: HashMap::new(),
+            bypassed_functions: HashMap::new(),
+            pure_modules: HashSet::new(),
+            enabled,
+            results: LruCache::new(capacity.unwrap_or(DEFAULT_CACHE_SIZE)),
+            stats: MemoizationStats::default(),
+        }
+    }
+
+    /// Should be called for every module in the order of loading, because imports from other
+    /// modules are allowed only if these modules are pure. Modules registered while
+    /// memoization is disabled aren't checked, so they are considered impure.
+    pub(crate) fn register_module(
+        &mut self,
+        module_name: &str,
+        wasm_bytes: &[u8],
+        pure_functions: PureFunctions,
+    ) -> Result<()> {
+        if pure_functions == PureFunctions::None && !self.enabled {
+            return Ok(());
+        }
+        self.enabled = true;
+
+        match check_purity(wasm_bytes, &self.pure_modules)? {
+            Ok(()) => {
+                self.pure_modules.insert(module_name.to_string());
+                if pure_functions != PureFunctions::None {
+                    self.memoized_functions
+                        .insert(module_name.to_string(), pure_functions);
+                }
+            }
+            Err(import_name) if pure_functions != PureFunctions::None => {
+                log::warn!(
+                    "module {} is marked as pure, but imports {}, so its calls won't be memoized",
+                    module_name,
+                    import_name
+                );
+                self.bypassed_functions
+                    .insert(module_name.to_string(), pure_functions);
+            }
+            Err(_) => {}
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn unregister_module(&mut self, module_name: &str) {
+        self.memoized_functions.remove(module_name);
+        self.bypassed_functions.remove(module_name);
+        self.pure_modules.remove(module_name);
+        // results of other modules could depend on this one
+        self.results.clear();
+    }
+
+    /// Returns a key of the call if it could be memoized.
+    pub(crate) fn call_key(
+        &mut self,
+        module_name: &str,
+        function_name: &str,
+        arguments: &[IValue],
+    ) -> Option<CallKey> {
+        let is_marked = |functions: &HashMap<String, PureFunctions>| {
+            functions
+                .get(module_name)
+                .map_or(false, |functions| functions.contains(function_name))
+        };
+
+        if is_marked(&self.memoized_functions) {
+            return Some(CallKey::new(module_name, function_name, arguments));
+        }
+
+        if is_marked(&self.bypassed_functions) {
+            self.stats.bypassed += 1;
+        }
+
+        None
+    }
+
+    pub(crate) fn get(&mut self, key: &CallKey) -> Option<Vec<IValue>> {
+        match self.results.get(key) {
+            Some(result) => {
+                self.stats.hits += 1;
+                Some(result.clone())
+            }
+            None => {
+                self.stats.misses += 1;
+                None
+            }
+        }
+    }
+
+    pub(crate) fn put(&mut self, key: CallKey, result: Vec<IValue>) {
+        if self.results.put(key, result) {
+            self.stats.evictions += 1;
+        }
+    }
+
+    pub(crate) fn stats(&self) -> MemoizationStats {
+        MemoizationStats {
+            cached_results: self.results.len() as u64,
+            ..self.stats
+        }
+    }
+}
@@ -0,0 +1,92 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::IValue;
+
+/// Canonical encoding of a call, calls with equal keys are guaranteed to have equal
+/// module names, function names and arguments.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct CallKey(Vec<u8>);
+
+impl CallKey {
+    pub(super) fn new(module_name: &str, function_name: &str, arguments: &[IValue]) -> Self {
+        let mut encoded = Vec::new();
+        encode_bytes(module_name.as_bytes(), &mut encoded);
+        encode_bytes(function_name.as_bytes(), &mut encoded);
+        encode_len(arguments.len(), &mut encoded);
+        for argument in arguments {
+            encode_ivalue(argument, &mut encoded);
+        }
+
+        Self(encoded)
+    }
+}
+
+fn encode_ivalue(value: &IValue, encoded: &mut Vec<u8>) {
+    match value {
+        IValue::S8(v) => encode_tagged(0, &v.to_le_bytes(), encoded),
+        IValue::S16(v) => encode_tagged(1, &v.to_le_bytes(), encoded),
+        IValue::S32(v) => encode_tagged(2, &v.to_le_bytes(), encoded),
+        IValue::S64(v) => encode_tagged(3, &v.to_le_bytes(), encoded),
+        IValue::U8(v) => encode_tagged(4, &v.to_le_bytes(), encoded),
+        IValue::U16(v) => encode_tagged(5, &v.to_le_bytes(), encoded),
+        IValue::U32(v) => encode_tagged(6, &v.to_le_bytes(), encoded),
+        IValue::U64(v) => encode_tagged(7, &v.to_le_bytes(), encoded),
+        IValue::I32(v) => encode_tagged(8, &v.to_le_bytes(), encoded),
+        IValue::I64(v) => encode_tagged(9, &v.to_le_bytes(), encoded),
+        // all NaNs are considered equal
+        IValue::F32(v) if v.is_nan() => {
+            encode_tagged(10, &f32::NAN.to_bits().to_le_bytes(), encoded)
+        }
+        IValue::F32(v) => encode_tagged(10, &v.to_bits().to_le_bytes(), encoded),
+        IValue::F64(v) if v.is_nan() => {
+            encode_tagged(11, &f64::NAN.to_bits().to_le_bytes(), encoded)
+        }
+        IValue::F64(v) => encode_tagged(11, &v.to_bits().to_le_bytes(), encoded),
+        IValue::String(v) => {
+            encoded.push(12);
+            encode_bytes(v.as_bytes(), encoded);
+        }
+        IValue::Array(values) => {
+            encoded.push(13);
+            encode_len(values.len(), encoded);
+            values
+                .iter()
+                .for_each(|value| encode_ivalue(value, encoded));
+        }
+        IValue::Record(values) => {
+            encoded.push(14);
+            encode_len(values.len(), encoded);
+            values
+                .iter()
+                .for_each(|value| encode_ivalue(value, encoded));
+        }
+    }
+}
+
+fn encode_tagged(tag: u8, bytes: &[u8], encoded: &mut Vec<u8>) {
+    encoded.push(tag);
+    encoded.extend_from_slice(bytes);
+}
+
+fn encode_bytes(bytes: &[u8], encoded: &mut Vec<u8>) {
+    encode_len(bytes.len(), encoded);
+    encoded.extend_from_slice(bytes);
+}
+
+fn encode_len(len: usize, encoded: &mut Vec<u8>) {
+    encoded.extend_from_slice(&(len as u64).to_le_bytes());
+}
@@ -0,0 +1,94 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Size-bounded map evicting the least recently used entries.
+pub(super) struct LruCache<K, V> {
+    capacity: usize,
+    /// Values with the tick of their last usage.
+    entries: HashMap<K, (V, u64)>,
+    /// Keys ordered by their last usage.
+    usage_order: BTreeMap<u64, K>,
+    tick: u64,
+}
+
+impl<K: Hash + Eq + Clone, V> LruCache<K, V> {
+    pub(super) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::new(),
+            usage_order: BTreeMap::new(),
+            tick: 0,
+        }
+    }
+
+    pub(super) fn get(&mut self, key: &K) -> Option<&V> {
+        let tick = self.next_tick();
+        let (value, last_usage) = self.entries.get_mut(key)?;
+
+        if let Some(key) = self.usage_order.remove(last_usage) {
+            self.usage_order.insert(tick, key);
+        }
+        *last_usage = tick;
+
+        Some(value)
+    }
+
+    /// Inserts a value, returns true if another entry has been evicted to make room for it.
+    pub(super) fn put(&mut self, key: K, value: V) -> bool {
+        let tick = self.next_tick();
+
+        if let Some((_, last_usage)) = self.entries.remove(&key) {
+            self.usage_order.remove(&last_usage);
+        }
+
+        let evicted = self.entries.len() >= self.capacity && self.evict_oldest();
+
+        self.usage_order.insert(tick, key.clone());
+        self.entries.insert(key, (value, tick));
+
+        evicted
+    }
+
+    pub(super) fn clear(&mut self) {
+        self.entries.clear();
+        self.usage_order.clear();
+    }
+
+    pub(super) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn evict_oldest(&mut self) -> bool {
+        let oldest_tick = match self.usage_order.keys().next() {
+            Some(tick) => *tick,
+            None => return false,
+        };
+
+        match self.usage_order.remove(&oldest_tick) {
+            Some(key) => self.entries.remove(&key).is_some(),
+            None => false,
+        }
+    }
+
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+}
@@ -0,0 +1,69 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::FaaSError;
+use crate::Result;
+
+use parity_wasm::elements::External;
+use parity_wasm::elements::Module;
+
+use std::collections::HashSet;
+
+/// Host imports that don't affect results of functions.
+const ALLOWED_HOST_IMPORTS: &[&str] = &["log_utf8_string"];
+
+/// WASI imports that don't affect results of functions, writes are allowed to stdout
+/// and stderr only, because files can't be opened without path_open.
+const ALLOWED_WASI_IMPORTS: &[&str] = &[
+    "fd_write",
+    "proc_exit",
+    "environ_get",
+    "environ_sizes_get",
+    "args_get",
+    "args_sizes_get",
+];
+
+/// Checks that a module doesn't import functions making results of its functions
+/// non-deterministic, returns the name of the first such import otherwise.
+/// Functions of other modules are allowed only if these modules are pure themselves.
+pub(super) fn check_purity(
+    wasm_bytes: &[u8],
+    pure_modules: &HashSet<String>,
+) -> Result<std::result::Result<(), String>> {
+    let module: Module = parity_wasm::deserialize_buffer(wasm_bytes)
+        .map_err(|e| FaaSError::InstantiationError(format!("module can't be parsed: {}", e)))?;
+
+    let import_entries = match module.import_section() {
+        Some(section) => section.entries(),
+        None => return Ok(Ok(())),
+    };
+
+    let impure_import = import_entries
+        .iter()
+        .filter(|entry| matches!(entry.external(), External::Function(_)))
+        .find(|entry| match entry.module() {
+            "host" => !ALLOWED_HOST_IMPORTS.contains(&entry.field()),
+            namespace if namespace.starts_with("wasi") => {
+                !ALLOWED_WASI_IMPORTS.contains(&entry.field())
+            }
+            module_name => !pure_modules.contains(module_name),
+        });
+
+    match impure_import {
+        Some(entry) => Ok(Err(format!("{}.{}", entry.module(), entry.field()))),
+        None => Ok(Ok(())),
+    }
+}
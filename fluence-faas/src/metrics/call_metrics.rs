@@ -0,0 +1,172 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::FaaSError;
+use crate::IValue;
+
+use fce::FCECallContext;
+use fce::FCECallInterceptor;
+use fce::FCEError;
+use parking_lot::Mutex;
+
+use std::collections::BTreeMap;
+use std::time::Instant;
+
+/// Upper bounds of latency histogram buckets in seconds, the same as default Prometheus ones.
+pub(super) const LATENCY_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Metrics collected for one function of a module.
+#[derive(Debug, Default, Clone)]
+pub(super) struct FunctionMetrics {
+    pub(super) calls: u64,
+    /// Count of errors by FaaSError kind.
+    pub(super) errors: BTreeMap<&'static str, u64>,
+    pub(super) latency: LatencyHistogram,
+}
+
+#[derive(Debug, Default, Clone)]
+pub(super) struct LatencyHistogram {
+    /// Non-cumulative counts of observations for each of LATENCY_BUCKETS.
+    pub(super) buckets: [u64; LATENCY_BUCKETS.len()],
+    pub(super) sum: f64,
+    pub(super) count: u64,
+}
+
+impl LatencyHistogram {
+    fn observe(&mut self, seconds: f64) {
+        if let Some(position) = LATENCY_BUCKETS.iter().position(|&bound| seconds <= bound) {
+            self.buckets[position] += 1;
+        }
+        self.sum += seconds;
+        self.count += 1;
+    }
+}
+
+pub(super) type FunctionsMetrics = BTreeMap<(String, String), FunctionMetrics>;
+
+#[derive(Default)]
+struct MetricsState {
+    /// Start times of the calls being executed, nested calls are pushed on the top.
+    started_calls: Vec<Instant>,
+    functions: FunctionsMetrics,
+}
+
+/// Collects metrics of all calls of module functions, including nested ones.
+#[derive(Default)]
+pub(crate) struct CallMetrics {
+    state: Mutex<MetricsState>,
+}
+
+impl CallMetrics {
+    /// Records errors occurred in FaaS outside of the engine, engine errors are recorded
+    /// by the interceptor itself, except the ones returned before a call reaches it.
+    pub(crate) fn record_faas_error(
+        &self,
+        module_name: &str,
+        function_name: &str,
+        error: &FaaSError,
+    ) {
+        match error {
+            FaaSError::EngineError(FCEError::NoSuchModule(_))
+            | FaaSError::EngineError(FCEError::NoSuchFunction(_)) => {}
+            FaaSError::EngineError(_) => return,
+            _ => {}
+        }
+
+        let mut state = self.state.lock();
+        let function = function_metrics(&mut state.functions, module_name, function_name);
+        *function.errors.entry(error_kind(error)).or_default() += 1;
+    }
+
+    pub(super) fn functions(&self) -> FunctionsMetrics {
+        self.state.lock().functions.clone()
+    }
+}
+
+impl FCECallInterceptor for CallMetrics {
+    fn before_call(&self, context: &FCECallContext<'_>) -> std::result::Result<(), String> {
+        let mut state = self.state.lock();
+        state.started_calls.push(Instant::now());
+
+        let function = function_metrics(
+            &mut state.functions,
+            context.module_name,
+            context.function_name,
+        );
+        function.calls += 1;
+
+        Ok(())
+    }
+
+    fn after_call(
+        &self,
+        context: &FCECallContext<'_>,
+        result: std::result::Result<&[IValue], &FCEError>,
+    ) {
+        let mut state = self.state.lock();
+        let elapsed = state
+            .started_calls
+            .pop()
+            .map(|started| started.elapsed().as_secs_f64());
+
+        let function = function_metrics(
+            &mut state.functions,
+            context.module_name,
+            context.function_name,
+        );
+        if let Some(elapsed) = elapsed {
+            function.latency.observe(elapsed);
+        }
+        if result.is_err() {
+            *function.errors.entry("EngineError").or_default() += 1;
+        }
+    }
+}
+
+fn function_metrics<'m>(
+    functions: &'m mut FunctionsMetrics,
+    module_name: &str,
+    function_name: &str,
+) -> &'m mut FunctionMetrics {
+    functions
+        .entry((module_name.to_string(), function_name.to_string()))
+        .or_default()
+}
+
+fn error_kind(error: &FaaSError) -> &'static str {
+    match error {
+        FaaSError::ConfigParseError(_) => "ConfigParseError",
+        FaaSError::InstantiationError(_) => "InstantiationError",
+        FaaSError::IOError(_) => "IOError",
+        FaaSError::MissingFunctionError(_) => "MissingFunctionError",
+        FaaSError::MissingArgumentError(_) => "MissingArgumentError",
+        FaaSError::NoSuchModule(_) => "NoSuchModule",
+        FaaSError::JsonArgumentsDeserializationError(_) => "JsonArgumentsDeserializationError",
+        FaaSError::JsonOutputSerializationError(_) => "JsonOutputSerializationError",
+        FaaSError::ParseConfigError(_) => "ParseConfigError",
+        FaaSError::BinaryArgumentsDecodingError(_) => "BinaryArgumentsDecodingError",
+        FaaSError::BinaryOutputEncodingError(_) => "BinaryOutputEncodingError",
+        FaaSError::TypedArgumentsError(_) => "TypedArgumentsError",
+        FaaSError::TypedResultError(_) => "TypedResultError",
+        FaaSError::JournalError(_) => "JournalError",
+        FaaSError::ModuleStoreError(_) => "ModuleStoreError",
+        FaaSError::InvalidModuleHash(_) => "InvalidModuleHash",
+        FaaSError::ModuleIntegrityError { .. } => "ModuleIntegrityError",
+        FaaSError::EngineError(_) => "EngineError",
+    }
+}
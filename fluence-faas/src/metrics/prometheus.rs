@@ -0,0 +1,241 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::CallMetrics;
+use super::call_metrics::LATENCY_BUCKETS;
+
+use crate::memoization::MemoizationStats;
+
+use fce::HostImportsStats;
+
+use std::fmt::Write;
+
+/// Metrics of a loaded module gathered from the engine at the rendering time.
+pub(crate) struct ModuleMetrics<'m> {
+    pub(crate) name: &'m str,
+    pub(crate) memory_pages: u32,
+    pub(crate) host_imports: HostImportsStats,
+}
+
+/// Renders all metrics in the Prometheus text exposition format.
+pub(crate) fn render_prometheus(
+    call_metrics: &CallMetrics,
+    modules: &[ModuleMetrics<'_>],
+    memoization: MemoizationStats,
+) -> String {
+    let functions = call_metrics.functions();
+    let mut output = String::new();
+
+    write_header(
+        &mut output,
+        "fce_function_calls_total",
+        "counter",
+        "Total number of function calls.",
+    );
+    for ((module_name, function_name), metrics) in &functions {
+        let labels = function_labels(module_name, function_name);
+        write_sample(
+            &mut output,
+            "fce_function_calls_total",
+            &labels,
+            metrics.calls,
+        );
+    }
+
+    write_header(
+        &mut output,
+        "fce_function_errors_total",
+        "counter",
+        "Total number of failed function calls by error kind.",
+    );
+    for ((module_name, function_name), metrics) in &functions {
+        for (kind, count) in &metrics.errors {
+            let labels = format!(
+                r#"{},kind="{}""#,
+                function_labels(module_name, function_name),
+                kind
+            );
+            write_sample(&mut output, "fce_function_errors_total", &labels, count);
+        }
+    }
+
+    write_header(
+        &mut output,
+        "fce_function_call_duration_seconds",
+        "histogram",
+        "Duration of function calls in seconds.",
+    );
+    for ((module_name, function_name), metrics) in &functions {
+        let labels = function_labels(module_name, function_name);
+        let histogram = &metrics.latency;
+
+        let mut cumulative_count = 0;
+        for (bound, count) in LATENCY_BUCKETS.iter().zip(histogram.buckets.iter()) {
+            cumulative_count += count;
+            let bucket_labels = format!(r#"{},le="{}""#, labels, bound);
+            write_sample(
+                &mut output,
+                "fce_function_call_duration_seconds_bucket",
+                &bucket_labels,
+                cumulative_count,
+            );
+        }
+        let bucket_labels = format!(r#"{},le="+Inf""#, labels);
+        write_sample(
+            &mut output,
+            "fce_function_call_duration_seconds_bucket",
+            &bucket_labels,
+            histogram.count,
+        );
+        write_sample(
+            &mut output,
+            "fce_function_call_duration_seconds_sum",
+            &labels,
+            histogram.sum,
+        );
+        write_sample(
+            &mut output,
+            "fce_function_call_duration_seconds_count",
+            &labels,
+            histogram.count,
+        );
+    }
+
+    write_header(
+        &mut output,
+        "fce_module_memory_pages",
+        "gauge",
+        "Current size of module memory in Wasm pages.",
+    );
+    for module in modules {
+        let labels = module_labels(module.name);
+        write_sample(
+            &mut output,
+            "fce_module_memory_pages",
+            &labels,
+            module.memory_pages,
+        );
+    }
+
+    write_header(
+        &mut output,
+        "fce_host_imports_lifted_bytes_total",
+        "counter",
+        "Total number of bytes passed from a module to host imports.",
+    );
+    for module in modules {
+        let labels = module_labels(module.name);
+        let lifted_bytes = module.host_imports.lifted_bytes;
+        write_sample(
+            &mut output,
+            "fce_host_imports_lifted_bytes_total",
+            &labels,
+            lifted_bytes,
+        );
+    }
+
+    write_header(
+        &mut output,
+        "fce_host_imports_lowered_bytes_total",
+        "counter",
+        "Total number of bytes passed from host imports to a module.",
+    );
+    for module in modules {
+        let labels = module_labels(module.name);
+        let lowered_bytes = module.host_imports.lowered_bytes;
+        write_sample(
+            &mut output,
+            "fce_host_imports_lowered_bytes_total",
+            &labels,
+            lowered_bytes,
+        );
+    }
+
+    let memoization_counters = [
+        (
+            "fce_memoization_hits_total",
+            "Total number of calls of pure functions returned from the cache.",
+            memoization.hits,
+        ),
+        (
+            "fce_memoization_misses_total",
+            "Total number of calls of pure functions executed and cached.",
+            memoization.misses,
+        ),
+        (
+            "fce_memoization_evictions_total",
+            "Total number of results evicted from the cache.",
+            memoization.evictions,
+        ),
+        (
+            "fce_memoization_bypassed_total",
+            "Total number of calls of functions marked as pure executed without the cache.",
+            memoization.bypassed,
+        ),
+    ];
+    for (name, help, value) in memoization_counters.iter() {
+        write_header(&mut output, name, "counter", help);
+        write_sample(&mut output, name, "", value);
+    }
+
+    write_header(
+        &mut output,
+        "fce_memoization_cached_results",
+        "gauge",
+        "Current number of results in the cache.",
+    );
+    write_sample(
+        &mut output,
+        "fce_memoization_cached_results",
+        "",
+        memoization.cached_results,
+    );
+
+    output
+}
+
+fn write_header(output: &mut String, name: &str, metric_type: &str, help: &str) {
+    // writing to String can't fail
+    let _ = writeln!(output, "# HELP {} {}", name, help);
+    let _ = writeln!(output, "# TYPE {} {}", name, metric_type);
+}
+
+fn write_sample(output: &mut String, name: &str, labels: &str, value: impl std::fmt::Display) {
+    if labels.is_empty() {
+        let _ = writeln!(output, "{} {}", name, value);
+    } else {
+        let _ = writeln!(output, "{}{{{}}} {}", name, labels, value);
+    }
+}
+
+fn function_labels(module_name: &str, function_name: &str) -> String {
+    format!(
+        r#"module="{}",function="{}""#,
+        escape_label_value(module_name),
+        escape_label_value(function_name)
+    )
+}
+
+fn module_labels(module_name: &str) -> String {
+    format!(r#"module="{}""#, escape_label_value(module_name))
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', r"\\")
+        .replace('"', r#"\""#)
+        .replace('\n', r"\n")
+}
@@ -0,0 +1,166 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::binary_value::BinaryValue;
+use super::ivalues_decoding::decode_arguments;
+use super::ivalues_decoding::ConvResult;
+use super::ivalues_decoding::ValueDecoder;
+use super::ivalues_decoding::ValuePath;
+use super::ivalues_decoding::ValueShape;
+use crate::IValue;
+use crate::IType;
+use crate::JsonArgumentErrorKind as ErrorKind;
+use crate::JsonKind;
+use crate::Result;
+use crate::FaaSError::BinaryArgumentsDecodingError as ArgDecError;
+use crate::faas_interface::itype_text_view;
+
+use fce::RecordTypes;
+
+use std::convert::TryFrom;
+use std::iter::ExactSizeIterator;
+
+/// Convert a binary value to an array of ivalues according to the supplied argument types,
+/// arguments could be passed as a map by names, an array or a single value.
+pub(crate) fn binary_to_ivalues<'a>(
+    value: BinaryValue,
+    arg_types: impl Iterator<Item = (&'a String, &'a IType)> + ExactSizeIterator,
+    record_types: &RecordTypes,
+) -> Result<Vec<IValue>> {
+    decode_arguments(&BinaryDecoder, value, arg_types, record_types)
+        .map_err(|e| ArgDecError(e.to_string()))
+}
+
+struct BinaryDecoder;
+
+impl ValueDecoder for BinaryDecoder {
+    type Value = BinaryValue;
+
+    fn kind(&self, value: &BinaryValue) -> JsonKind {
+        match value {
+            BinaryValue::Nil => JsonKind::Null,
+            BinaryValue::Bool(_) => JsonKind::Bool,
+            BinaryValue::Integer(_) | BinaryValue::F32(_) | BinaryValue::F64(_) => JsonKind::Number,
+            BinaryValue::String(_) | BinaryValue::Binary(_) => JsonKind::String,
+            BinaryValue::Array(_) => JsonKind::Array,
+            BinaryValue::Map(_) => JsonKind::Object,
+        }
+    }
+
+    fn shape(&self, value: BinaryValue) -> ValueShape<BinaryValue> {
+        match value {
+            BinaryValue::Nil => ValueShape::Null,
+            BinaryValue::Map(entries) => ValueShape::Map(entries),
+            BinaryValue::Array(values) => ValueShape::Array(values),
+            value => ValueShape::Other(value),
+        }
+    }
+
+    fn decode_primitive(
+        &self,
+        value: BinaryValue,
+        ty: &IType,
+        record_types: &RecordTypes,
+        path: &ValuePath<'_>,
+    ) -> ConvResult<IValue> {
+        let expected = || itype_text_view(ty, record_types);
+        let received = self.kind(&value);
+        let invalid_value = |reason| {
+            path.error(ErrorKind::InvalidValue {
+                expected: expected(),
+                received,
+                reason,
+            })
+        };
+
+        macro_rules! to_integer(
+            ($value:expr, $ty:ident) => {
+                match $value {
+                    BinaryValue::Integer(value) => TryFrom::try_from(value)
+                        .map(IValue::$ty)
+                        .map_err(|_| invalid_value(format!("{} is out of range", value))),
+                    _ => Err(path.error(ErrorKind::TypeMismatch {
+                        expected: expected(),
+                        received,
+                    })),
+                }
+            }
+        );
+
+        // floats are converted only if they keep their values, like integers are range checked
+        let lossy = |value: &dyn std::fmt::Display| {
+            invalid_value(format!("{} can't be represented exactly", value))
+        };
+
+        match (ty, value) {
+            (IType::S8, value) => to_integer!(value, S8),
+            (IType::S16, value) => to_integer!(value, S16),
+            (IType::S32, value) => to_integer!(value, S32),
+            (IType::S64, value) => to_integer!(value, S64),
+            (IType::U8, value) => to_integer!(value, U8),
+            (IType::U16, value) => to_integer!(value, U16),
+            (IType::U32, value) => to_integer!(value, U32),
+            (IType::U64, value) => to_integer!(value, U64),
+            (IType::I32, value) => to_integer!(value, I32),
+            (IType::I64, value) => to_integer!(value, I64),
+            (IType::F32, BinaryValue::F32(value)) => Ok(IValue::F32(value)),
+            (IType::F32, BinaryValue::F64(value)) => {
+                if value.is_nan() || value as f32 as f64 == value {
+                    Ok(IValue::F32(value as f32))
+                } else {
+                    Err(lossy(&value))
+                }
+            }
+            (IType::F32, BinaryValue::Integer(value)) => {
+                if value as f32 as i128 == value {
+                    Ok(IValue::F32(value as f32))
+                } else {
+                    Err(lossy(&value))
+                }
+            }
+            (IType::F64, BinaryValue::F32(value)) => Ok(IValue::F64(value as f64)),
+            (IType::F64, BinaryValue::F64(value)) => Ok(IValue::F64(value)),
+            (IType::F64, BinaryValue::Integer(value)) => {
+                if value as f64 as i128 == value {
+                    Ok(IValue::F64(value as f64))
+                } else {
+                    Err(lossy(&value))
+                }
+            }
+            (IType::String, BinaryValue::String(value)) => Ok(IValue::String(value)),
+            (IType::Anyref, _) => Err(path.error(ErrorKind::UnsupportedType {
+                expected: expected(),
+            })),
+            _ => Err(path.error(ErrorKind::TypeMismatch {
+                expected: expected(),
+                received,
+            })),
+        }
+    }
+
+    fn decode_byte_array(
+        &self,
+        value: BinaryValue,
+        _path: &ValuePath<'_>,
+        _expected: &dyn Fn() -> String,
+    ) -> ConvResult<std::result::Result<Vec<u8>, BinaryValue>> {
+        match value {
+            // byte arrays are passed as native binary strings
+            BinaryValue::Binary(bytes) => Ok(Ok(bytes)),
+            value => Ok(Err(value)),
+        }
+    }
+}
@@ -0,0 +1,206 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::FaaSError::BinaryArgumentsDecodingError as ArgDecError;
+use crate::FaaSError::BinaryOutputEncodingError as OutputEncError;
+use crate::Result;
+
+use std::collections::BTreeMap;
+
+/// Binary encodings supported for call arguments and results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryEncoding {
+    MessagePack,
+    Cbor,
+}
+
+/// Value of any supported binary encoding.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum BinaryValue {
+    Nil,
+    Bool(bool),
+    Integer(i128),
+    F32(f32),
+    F64(f64),
+    String(String),
+    Binary(Vec<u8>),
+    Array(Vec<BinaryValue>),
+    Map(Vec<(String, BinaryValue)>),
+}
+
+impl BinaryEncoding {
+    /// Decodes a value, empty input is considered as Nil.
+    pub(crate) fn decode(self, bytes: &[u8]) -> Result<BinaryValue> {
+        if bytes.is_empty() {
+            return Ok(BinaryValue::Nil);
+        }
+
+        match self {
+            BinaryEncoding::MessagePack => {
+                let value = rmpv::decode::read_value(&mut &bytes[..])
+                    .map_err(|e| ArgDecError(format!("malformed MessagePack: {}", e)))?;
+                from_msgpack(value)
+            }
+            BinaryEncoding::Cbor => {
+                let value = serde_cbor::from_slice(bytes)
+                    .map_err(|e| ArgDecError(format!("malformed CBOR: {}", e)))?;
+                from_cbor(value)
+            }
+        }
+    }
+
+    pub(crate) fn encode(self, value: BinaryValue) -> Result<Vec<u8>> {
+        match self {
+            BinaryEncoding::MessagePack => {
+                let mut bytes = Vec::new();
+                rmpv::encode::write_value(&mut bytes, &to_msgpack(value))
+                    .map_err(|e| OutputEncError(format!("MessagePack encoding failed: {}", e)))?;
+                Ok(bytes)
+            }
+            BinaryEncoding::Cbor => serde_cbor::to_vec(&to_cbor(value))
+                .map_err(|e| OutputEncError(format!("CBOR encoding failed: {}", e))),
+        }
+    }
+}
+
+fn from_msgpack(value: rmpv::Value) -> Result<BinaryValue> {
+    use rmpv::Value;
+
+    let value = match value {
+        Value::Nil => BinaryValue::Nil,
+        Value::Boolean(value) => BinaryValue::Bool(value),
+        Value::Integer(value) => match (value.as_i64(), value.as_u64()) {
+            (Some(value), _) => BinaryValue::Integer(value as i128),
+            (None, Some(value)) => BinaryValue::Integer(value as i128),
+            (None, None) => return Err(ArgDecError(format!("invalid integer {}", value))),
+        },
+        Value::F32(value) => BinaryValue::F32(value),
+        Value::F64(value) => BinaryValue::F64(value),
+        Value::String(value) => match value.into_str() {
+            Some(value) => BinaryValue::String(value),
+            None => return Err(ArgDecError(String::from("string isn't valid UTF-8"))),
+        },
+        Value::Binary(value) => BinaryValue::Binary(value),
+        Value::Array(values) => {
+            let values = values
+                .into_iter()
+                .map(from_msgpack)
+                .collect::<Result<Vec<_>>>()?;
+            BinaryValue::Array(values)
+        }
+        Value::Map(entries) => {
+            let entries = entries
+                .into_iter()
+                .map(|(key, value)| {
+                    let key = match key {
+                        Value::String(key) => key.into_str(),
+                        _ => None,
+                    }
+                    .ok_or_else(|| ArgDecError(String::from("map keys should be strings")))?;
+                    Ok((key, from_msgpack(value)?))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            BinaryValue::Map(entries)
+        }
+        Value::Ext(type_id, _) => {
+            return Err(ArgDecError(format!(
+                "MessagePack extension type {} isn't supported",
+                type_id
+            )))
+        }
+    };
+
+    Ok(value)
+}
+
+fn to_msgpack(value: BinaryValue) -> rmpv::Value {
+    use rmpv::Value;
+
+    match value {
+        BinaryValue::Nil => Value::Nil,
+        BinaryValue::Bool(value) => Value::Boolean(value),
+        BinaryValue::Integer(value) if value < 0 => Value::from(value as i64),
+        BinaryValue::Integer(value) => Value::from(value as u64),
+        BinaryValue::F32(value) => Value::F32(value),
+        BinaryValue::F64(value) => Value::F64(value),
+        BinaryValue::String(value) => Value::String(value.into()),
+        BinaryValue::Binary(value) => Value::Binary(value),
+        BinaryValue::Array(values) => Value::Array(values.into_iter().map(to_msgpack).collect()),
+        BinaryValue::Map(entries) => Value::Map(
+            entries
+                .into_iter()
+                .map(|(key, value)| (Value::String(key.into()), to_msgpack(value)))
+                .collect(),
+        ),
+    }
+}
+
+fn from_cbor(value: serde_cbor::Value) -> Result<BinaryValue> {
+    use serde_cbor::Value;
+
+    let value = match value {
+        Value::Null => BinaryValue::Nil,
+        Value::Bool(value) => BinaryValue::Bool(value),
+        Value::Integer(value) => BinaryValue::Integer(value),
+        Value::Float(value) => BinaryValue::F64(value),
+        Value::Text(value) => BinaryValue::String(value),
+        Value::Bytes(value) => BinaryValue::Binary(value),
+        Value::Array(values) => {
+            let values = values
+                .into_iter()
+                .map(from_cbor)
+                .collect::<Result<Vec<_>>>()?;
+            BinaryValue::Array(values)
+        }
+        Value::Map(entries) => {
+            let entries = entries
+                .into_iter()
+                .map(|(key, value)| match key {
+                    Value::Text(key) => Ok((key, from_cbor(value)?)),
+                    _ => Err(ArgDecError(String::from("map keys should be strings"))),
+                })
+                .collect::<Result<Vec<_>>>()?;
+            BinaryValue::Map(entries)
+        }
+        // tags only add semantics to values, so they're just skipped
+        Value::Tag(_, value) => from_cbor(*value)?,
+        // serde_cbor::Value has a hidden variant
+        _ => return Err(ArgDecError(String::from("unsupported CBOR value"))),
+    };
+
+    Ok(value)
+}
+
+fn to_cbor(value: BinaryValue) -> serde_cbor::Value {
+    use serde_cbor::Value;
+
+    match value {
+        BinaryValue::Nil => Value::Null,
+        BinaryValue::Bool(value) => Value::Bool(value),
+        BinaryValue::Integer(value) => Value::Integer(value),
+        BinaryValue::F32(value) => Value::Float(value as f64),
+        BinaryValue::F64(value) => Value::Float(value),
+        BinaryValue::String(value) => Value::Text(value),
+        BinaryValue::Binary(value) => Value::Bytes(value),
+        BinaryValue::Array(values) => Value::Array(values.into_iter().map(to_cbor).collect()),
+        BinaryValue::Map(entries) => Value::Map(
+            entries
+                .into_iter()
+                .map(|(key, value)| (Value::Text(key), to_cbor(value)))
+                .collect::<BTreeMap<_, _>>(),
+        ),
+    }
+}
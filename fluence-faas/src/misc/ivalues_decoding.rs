@@ -0,0 +1,303 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::IValue;
+use crate::IType;
+use crate::JsonArgumentError;
+use crate::JsonArgumentErrorKind as ErrorKind;
+use crate::JsonKind;
+use crate::faas_interface::itype_text_view;
+
+use fce::RecordTypes;
+use wasmer_wit::NEVec;
+
+use std::iter::ExactSizeIterator;
+
+pub(super) type ConvResult<T> = std::result::Result<T, JsonArgumentError>;
+
+/// Path to a value inside arguments, rendered only in case of an error.
+#[derive(Clone, Copy)]
+pub(super) enum ValuePath<'p> {
+    Root,
+    Field(&'p ValuePath<'p>, &'p str),
+    Index(&'p ValuePath<'p>, usize),
+}
+
+impl<'p> ValuePath<'p> {
+    fn field(&'p self, name: &'p str) -> Self {
+        ValuePath::Field(self, name)
+    }
+
+    fn index(&'p self, index: usize) -> Self {
+        ValuePath::Index(self, index)
+    }
+
+    pub(super) fn error(&self, kind: ErrorKind) -> JsonArgumentError {
+        JsonArgumentError {
+            path: self.render(),
+            kind,
+        }
+    }
+
+    fn render(&self) -> String {
+        match self {
+            ValuePath::Root => String::from("args"),
+            ValuePath::Field(parent, name) => format!("{}.{}", parent.render(), name),
+            ValuePath::Index(parent, index) => format!("{}[{}]", parent.render(), index),
+        }
+    }
+}
+
+/// Structure of a value, the type-driven conversion needs to know it to walk composite values.
+pub(super) enum ValueShape<V> {
+    Null,
+    Map(Vec<(String, V)>),
+    Array(Vec<V>),
+    Other(V),
+}
+
+/// Values of a self-describing format (JSON, MessagePack or CBOR) that could be converted
+/// to IValues according to function signatures and record types.
+pub(super) trait ValueDecoder {
+    type Value;
+
+    /// Kind of the value reported in errors, values of binary formats are named as JSON ones.
+    fn kind(&self, value: &Self::Value) -> JsonKind;
+
+    fn shape(&self, value: Self::Value) -> ValueShape<Self::Value>;
+
+    /// Converts a value of a type that is neither an array nor a record.
+    fn decode_primitive(
+        &self,
+        value: Self::Value,
+        ty: &IType,
+        record_types: &RecordTypes,
+        path: &ValuePath<'_>,
+    ) -> ConvResult<IValue>;
+
+    /// Returns bytes of a byte array passed in a compact form or the value back otherwise.
+    fn decode_byte_array(
+        &self,
+        value: Self::Value,
+        path: &ValuePath<'_>,
+        expected: &dyn Fn() -> String,
+    ) -> ConvResult<std::result::Result<Vec<u8>, Self::Value>>;
+}
+
+/// Converts function arguments passed as a map by names, an array or a single value.
+pub(super) fn decode_arguments<'a, D: ValueDecoder>(
+    decoder: &D,
+    value: D::Value,
+    arg_types: impl Iterator<Item = (&'a String, &'a IType)> + ExactSizeIterator,
+    record_types: &RecordTypes,
+) -> ConvResult<Vec<IValue>> {
+    let path = ValuePath::Root;
+
+    match decoder.shape(value) {
+        ValueShape::Map(entries) => decode_map(decoder, entries, arg_types, record_types, &path),
+        ValueShape::Array(values) => {
+            let arg_types = arg_types.map(|arg| arg.1);
+            decode_array(decoder, values, arg_types, record_types, &path)
+        }
+        ValueShape::Null if arg_types.len() == 0 => Ok(vec![]),
+        ValueShape::Null => Err(path.error(ErrorKind::CountMismatch {
+            expected: arg_types.len(),
+            provided: 0,
+        })),
+        ValueShape::Other(value) => {
+            let mut arg_types = arg_types;
+            if arg_types.len() != 1 {
+                return Err(path.error(ErrorKind::CountMismatch {
+                    expected: arg_types.len(),
+                    provided: 1,
+                }));
+            }
+
+            // unwrap is safe here because iterator size's been checked
+            let arg_type = arg_types.next().unwrap().1;
+            let ivalue = decode_value(decoder, value, arg_type, record_types, &path)?;
+
+            Ok(vec![ivalue])
+        }
+    }
+}
+
+/// Converts a map to values of the supplied arguments or record fields.
+fn decode_map<'a, D: ValueDecoder>(
+    decoder: &D,
+    mut entries: Vec<(String, D::Value)>,
+    arg_types: impl Iterator<Item = (&'a String, &'a IType)>,
+    record_types: &RecordTypes,
+    path: &ValuePath<'_>,
+) -> ConvResult<Vec<IValue>> {
+    let mut iargs = Vec::new();
+
+    for (arg_name, arg_type) in arg_types {
+        let arg_path = path.field(arg_name);
+        let position = entries
+            .iter()
+            .position(|(name, _)| name == arg_name)
+            .ok_or_else(|| {
+                arg_path.error(ErrorKind::MissingValue {
+                    expected: itype_text_view(arg_type, record_types),
+                })
+            })?;
+        let (_, value) = entries.remove(position);
+        let iarg = decode_value(decoder, value, arg_type, record_types, &arg_path)?;
+        iargs.push(iarg);
+    }
+
+    // the order of entries is kept, so the first unexpected value is reported deterministically
+    if let Some((name, value)) = entries.first() {
+        return Err(path.field(name).error(ErrorKind::UnexpectedValue {
+            received: decoder.kind(value),
+        }));
+    }
+
+    Ok(iargs)
+}
+
+/// Converts an array to values of the supplied arguments or record fields.
+fn decode_array<'a, D: ValueDecoder>(
+    decoder: &D,
+    values: Vec<D::Value>,
+    arg_types: impl Iterator<Item = &'a IType> + ExactSizeIterator,
+    record_types: &RecordTypes,
+    path: &ValuePath<'_>,
+) -> ConvResult<Vec<IValue>> {
+    if values.len() != arg_types.len() {
+        return Err(path.error(ErrorKind::CountMismatch {
+            expected: arg_types.len(),
+            provided: values.len(),
+        }));
+    }
+
+    values
+        .into_iter()
+        .zip(arg_types)
+        .enumerate()
+        .map(|(id, (value, arg_type))| {
+            decode_value(decoder, value, arg_type, record_types, &path.index(id))
+        })
+        .collect()
+}
+
+/// Converts one value according to the supplied type.
+fn decode_value<D: ValueDecoder>(
+    decoder: &D,
+    value: D::Value,
+    ty: &IType,
+    record_types: &RecordTypes,
+    path: &ValuePath<'_>,
+) -> ConvResult<IValue> {
+    match ty {
+        IType::Array(value_type) => {
+            let expected = || itype_text_view(ty, record_types);
+            let value = if **value_type == IType::U8 {
+                match decoder.decode_byte_array(value, path, &expected)? {
+                    Ok(bytes) => {
+                        return Ok(IValue::Array(bytes.into_iter().map(IValue::U8).collect()))
+                    }
+                    Err(value) => value,
+                }
+            } else {
+                value
+            };
+
+            let values = match decoder.shape(value) {
+                ValueShape::Array(values) => values,
+                ValueShape::Null => {
+                    return Err(path.error(ErrorKind::TypeMismatch {
+                        expected: expected(),
+                        received: JsonKind::Null,
+                    }))
+                }
+                ValueShape::Map(_) => {
+                    return Err(path.error(ErrorKind::TypeMismatch {
+                        expected: expected(),
+                        received: JsonKind::Object,
+                    }))
+                }
+                ValueShape::Other(value) => {
+                    return Err(path.error(ErrorKind::TypeMismatch {
+                        expected: expected(),
+                        received: decoder.kind(&value),
+                    }))
+                }
+            };
+
+            let values = values
+                .into_iter()
+                .enumerate()
+                .map(|(id, value)| {
+                    decode_value(decoder, value, value_type, record_types, &path.index(id))
+                })
+                .collect::<ConvResult<Vec<_>>>()?;
+
+            Ok(IValue::Array(values))
+        }
+        IType::Record(record_type_id) => {
+            let fields = decode_record(decoder, value, *record_type_id, record_types, path)?;
+            Ok(IValue::Record(fields))
+        }
+        ty => decoder.decode_primitive(value, ty, record_types, path),
+    }
+}
+
+/// Converts a map or an array of fields to a record.
+fn decode_record<D: ValueDecoder>(
+    decoder: &D,
+    value: D::Value,
+    record_type_id: u64,
+    record_types: &RecordTypes,
+    path: &ValuePath<'_>,
+) -> ConvResult<NEVec<IValue>> {
+    let record_type = record_types
+        .get(&record_type_id)
+        .ok_or_else(|| path.error(ErrorKind::UnknownRecordType(record_type_id)))?;
+
+    let type_mismatch = |received| {
+        path.error(ErrorKind::TypeMismatch {
+            expected: record_type.name.clone(),
+            received,
+        })
+    };
+
+    let fields = match decoder.shape(value) {
+        ValueShape::Map(entries) => decode_map(
+            decoder,
+            entries,
+            record_type
+                .fields
+                .iter()
+                .map(|field| (&field.name, &field.ty)),
+            record_types,
+            path,
+        )?,
+        ValueShape::Array(values) => decode_array(
+            decoder,
+            values,
+            record_type.fields.iter().map(|field| &field.ty),
+            record_types,
+            path,
+        )?,
+        ValueShape::Null => return Err(type_mismatch(JsonKind::Null)),
+        ValueShape::Other(value) => return Err(type_mismatch(decoder.kind(&value))),
+    };
+
+    // records always have fields, it's checked by the interface types parser
+    Ok(NEVec::new(fields).unwrap())
+}
@@ -0,0 +1,113 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::binary_value::BinaryValue;
+use crate::IValue;
+use crate::IType;
+use crate::Result;
+use crate::FaaSError::BinaryOutputEncodingError as OutputEncError;
+
+use fce::RecordTypes;
+
+/// Convert results of a call to a binary value according to the function output types.
+pub(crate) fn ivalues_to_binary(
+    mut ivalues: Vec<IValue>,
+    outputs: &[IType],
+    record_types: &RecordTypes,
+) -> Result<BinaryValue> {
+    if outputs.len() != ivalues.len() {
+        return Err(OutputEncError(format!(
+            "resulted values {:?} and function signature {:?} aren't compatible",
+            ivalues, outputs
+        )));
+    }
+
+    match ivalues.len() {
+        0 => Ok(BinaryValue::Nil),
+        1 => ivalue_to_binary(ivalues.remove(0), &outputs[0], record_types),
+        _ => Err(OutputEncError(String::from(
+            "multi-values aren't supported now - more then one result values aren't possible",
+        ))),
+    }
+}
+
+fn ivalue_to_binary(
+    ivalue: IValue,
+    output: &IType,
+    record_types: &RecordTypes,
+) -> Result<BinaryValue> {
+    match (ivalue, output) {
+        (IValue::S8(value), IType::S8) => Ok(BinaryValue::Integer(value as i128)),
+        (IValue::S16(value), IType::S16) => Ok(BinaryValue::Integer(value as i128)),
+        (IValue::S32(value), IType::S32) => Ok(BinaryValue::Integer(value as i128)),
+        (IValue::S64(value), IType::S64) => Ok(BinaryValue::Integer(value as i128)),
+        (IValue::U8(value), IType::U8) => Ok(BinaryValue::Integer(value as i128)),
+        (IValue::U16(value), IType::U16) => Ok(BinaryValue::Integer(value as i128)),
+        (IValue::U32(value), IType::U32) => Ok(BinaryValue::Integer(value as i128)),
+        (IValue::U64(value), IType::U64) => Ok(BinaryValue::Integer(value as i128)),
+        (IValue::I32(value), IType::I32) => Ok(BinaryValue::Integer(value as i128)),
+        (IValue::I64(value), IType::I64) => Ok(BinaryValue::Integer(value as i128)),
+        (IValue::F32(value), IType::F32) => Ok(BinaryValue::F32(value)),
+        (IValue::F64(value), IType::F64) => Ok(BinaryValue::F64(value)),
+        (IValue::String(value), IType::String) => Ok(BinaryValue::String(value)),
+        // byte arrays are returned as native binary strings
+        (IValue::Array(values), IType::Array(value_type)) if **value_type == IType::U8 => values
+            .into_iter()
+            .map(|value| match value {
+                IValue::U8(byte) => Ok(byte),
+                value => Err(OutputEncError(format!("value {:?} isn't a byte", value))),
+            })
+            .collect::<Result<Vec<_>>>()
+            .map(BinaryValue::Binary),
+        (IValue::Array(values), IType::Array(value_type)) => values
+            .into_iter()
+            .map(|value| ivalue_to_binary(value, value_type, record_types))
+            .collect::<Result<Vec<_>>>()
+            .map(BinaryValue::Array),
+        (IValue::Record(field_values), IType::Record(record_id)) => {
+            let record_type = record_types.get(record_id).ok_or_else(|| {
+                OutputEncError(format!(
+                    "record id {} wasn't found in module record types",
+                    record_id
+                ))
+            })?;
+            let field_types = &record_type.fields;
+
+            if field_values.len() != field_types.len() {
+                return Err(OutputEncError(format!(
+                    "output record {:?} isn't compatible to output record fields {:?}",
+                    field_values, field_types
+                )));
+            }
+
+            let fields = field_values
+                .into_vec()
+                .into_iter()
+                .zip(field_types.iter())
+                .map(|(value, field)| {
+                    let value = ivalue_to_binary(value, &field.ty, record_types)?;
+                    Ok((field.name.clone(), value))
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            Ok(BinaryValue::Map(fields))
+        }
+        (ivalue, itype) => Err(OutputEncError(format!(
+            "value {:?} is incompatible to type {:?}",
+            ivalue, itype
+        ))),
+    }
+}
@@ -14,8 +14,10 @@
  * limitations under the License.
  */
 
+use crate::ByteArrayEncoding;
 use crate::IValue;
 use crate::IType;
+use crate::JsonOptions;
 use crate::Result;
 use crate::errors::FaaSError::JsonOutputSerializationError as OutputDeError;
 
@@ -26,6 +28,7 @@ pub(crate) fn ivalues_to_json(
     mut ivalues: Vec<IValue>,
     outputs: &[IType],
     record_types: &RecordTypes,
+    options: &JsonOptions,
 ) -> Result<JValue> {
     if outputs.len() != ivalues.len() {
         return Err(OutputDeError(format!(
@@ -35,14 +38,24 @@ pub(crate) fn ivalues_to_json(
     }
     match ivalues.len() {
         0 => Ok(JValue::Null),
-        1 => ivalue_to_json(ivalues.remove(0), outputs.first().unwrap(), record_types),
+        1 => ivalue_to_json(
+            ivalues.remove(0),
+            outputs.first().unwrap(),
+            record_types,
+            options,
+        ),
         _ => unimplemented!(
             "multi-values aren't supported now - more then one result values aren't possible"
         ),
     }
 }
 
-fn ivalue_to_json(ivalue: IValue, output: &IType, record_types: &RecordTypes) -> Result<JValue> {
+fn ivalue_to_json(
+    ivalue: IValue,
+    output: &IType,
+    record_types: &RecordTypes,
+    options: &JsonOptions,
+) -> Result<JValue> {
     use serde_json::json;
 
     // clone here needed because binding by-value and by-ref in the same pattern in unstable
@@ -50,20 +63,29 @@ fn ivalue_to_json(ivalue: IValue, output: &IType, record_types: &RecordTypes) ->
         (IValue::S8(value), IType::S8) => Ok(json!(value)),
         (IValue::S16(value), IType::S16) => Ok(json!(value)),
         (IValue::S32(value), IType::S32) => Ok(json!(value)),
+        (IValue::S64(value), IType::S64) if options.int64_as_string => Ok(json!(value.to_string())),
         (IValue::S64(value), IType::S64) => Ok(json!(value)),
         (IValue::U8(value), IType::U8) => Ok(json!(value)),
         (IValue::U16(value), IType::U16) => Ok(json!(value)),
         (IValue::U32(value), IType::U32) => Ok(json!(value)),
+        (IValue::U64(value), IType::U64) if options.int64_as_string => Ok(json!(value.to_string())),
         (IValue::U64(value), IType::U64) => Ok(json!(value)),
         (IValue::I32(value), IType::I32) => Ok(json!(value)),
+        (IValue::I64(value), IType::I64) if options.int64_as_string => Ok(json!(value.to_string())),
         (IValue::I64(value), IType::I64) => Ok(json!(value)),
         (IValue::F32(value), IType::F32) => Ok(json!(value)),
         (IValue::F64(value), IType::F64) => Ok(json!(value)),
         (IValue::String(value), IType::String) => Ok(json!(value)),
+        (IValue::Array(value), IType::Array(array_ty))
+            if *array_ty == IType::U8
+                && options.byte_array_encoding != ByteArrayEncoding::Array =>
+        {
+            encode_byte_array(value, options.byte_array_encoding)
+        }
         (IValue::Array(value), IType::Array(array_ty)) => {
             let result: Result<Vec<_>> = value
                 .into_iter()
-                .map(|v| ivalue_to_json(v, &array_ty, record_types))
+                .map(|v| ivalue_to_json(v, &array_ty, record_types, options))
                 .collect();
 
             Ok(JValue::Array(result?))
@@ -88,7 +110,8 @@ fn ivalue_to_json(ivalue: IValue, output: &IType, record_types: &RecordTypes) ->
             let mut result = serde_json::Map::with_capacity(field_values.len());
 
             for (field_value, field_type) in field_values.into_iter().zip(field_types.iter()) {
-                let json_field_value = ivalue_to_json(field_value, &field_type.ty, record_types)?;
+                let json_field_value =
+                    ivalue_to_json(field_value, &field_type.ty, record_types, options)?;
                 result.insert(field_type.name.clone(), json_field_value);
             }
 
@@ -100,3 +123,23 @@ fn ivalue_to_json(ivalue: IValue, output: &IType, record_types: &RecordTypes) ->
         ))),
     }
 }
+
+fn encode_byte_array(values: Vec<IValue>, encoding: ByteArrayEncoding) -> Result<JValue> {
+    let bytes = values
+        .into_iter()
+        .map(|value| match value {
+            IValue::U8(byte) => Ok(byte),
+            value => Err(OutputDeError(format!("value {:?} isn't a byte", value))),
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let encoded = match encoding {
+        ByteArrayEncoding::Base64 => base64::encode(bytes),
+        ByteArrayEncoding::Hex => format!("0x{}", hex::encode(bytes)),
+        ByteArrayEncoding::Array => {
+            return Ok(JValue::Array(bytes.into_iter().map(JValue::from).collect()))
+        }
+    };
+
+    Ok(JValue::String(encoded))
+}
@@ -14,216 +14,204 @@
  * limitations under the License.
  */
 
+use super::ivalues_decoding::decode_arguments;
+use super::ivalues_decoding::ConvResult;
+use super::ivalues_decoding::ValueDecoder;
+use super::ivalues_decoding::ValuePath;
+use super::ivalues_decoding::ValueShape;
 use crate::IValue;
 use crate::IType;
+use crate::JsonArgumentErrorKind as ErrorKind;
+use crate::JsonKind;
+use crate::JsonOptions;
 use crate::Result;
-use crate::FaaSError::JsonArgumentsDeserializationError as ArgDeError;
+use crate::faas_interface::itype_text_view;
 
 use fce::RecordTypes;
 use serde_json::Value as JValue;
-use wasmer_wit::NEVec;
 
-use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::iter::ExactSizeIterator;
 
+/// Integral floats above 2^53 could be rounded from other integers, so they aren't accepted.
+const MAX_SAFE_INTEGER: f64 = 9007199254740992.0;
+
 /// Convert json to an array of ivalues according to the supplied argument types.
 pub(crate) fn json_to_ivalues<'a, 'b>(
     json_args: JValue,
     arg_types: impl Iterator<Item = (&'a String, &'a IType)> + ExactSizeIterator,
     record_types: &'b RecordTypes,
+    options: &JsonOptions,
 ) -> Result<Vec<IValue>> {
-    let ivalues = match json_args {
-        JValue::Object(json_map) => json_map_to_ivalues(json_map, arg_types, &record_types)?,
-        JValue::Array(json_array) => {
-            json_array_to_ivalues(json_array, arg_types.map(|arg| arg.1), &record_types)?
-        }
-        JValue::Null => json_null_to_ivalues(arg_types)?,
-        json_value => json_value_to_ivalues(json_value, arg_types)?,
-    };
+    let decoder = JsonDecoder { options };
+    let ivalues = decode_arguments(&decoder, json_args, arg_types, record_types)?;
 
     Ok(ivalues)
 }
 
-/// Convert json map to an array of ivalues according to the supplied argument types.
-fn json_map_to_ivalues<'a, 'b>(
-    mut json_map: serde_json::Map<String, JValue>,
-    arg_types: impl Iterator<Item = (&'a String, &'a IType)>,
-    record_types: &'b RecordTypes,
-) -> Result<Vec<IValue>> {
-    let mut iargs = Vec::new();
-
-    for (arg_name, arg_type) in arg_types {
-        let json_value = json_map
-            .remove(arg_name)
-            .ok_or_else(|| ArgDeError(format!("missing argument with name {}", arg_name)))?;
-        let iarg = jvalue_to_ivalue(json_value, arg_type, record_types)?;
-        iargs.push(iarg);
-    }
-
-    if !json_map.is_empty() {
-        return Err(ArgDeError(format!(
-            "function requires {} arguments, {} provided",
-            iargs.len(),
-            iargs.len() + json_map.len()
-        )));
-    }
-
-    Ok(iargs)
+struct JsonDecoder<'o> {
+    options: &'o JsonOptions,
 }
 
-/// Convert json array to an array of ivalues according to the supplied argument types.
-fn json_array_to_ivalues<'a, 'b>(
-    json_array: Vec<JValue>,
-    arg_types: impl Iterator<Item = &'a IType> + ExactSizeIterator,
-    record_types: &'b RecordTypes,
-) -> Result<Vec<IValue>> {
-    if json_array.len() != arg_types.len() {
-        return Err(ArgDeError(format!(
-            "function requires {} arguments, {} provided",
-            arg_types.len(),
-            json_array.len()
-        )));
-    }
-
-    let iargs = json_array
-        .into_iter()
-        .zip(arg_types)
-        .map(|(json_value, arg_type)| jvalue_to_ivalue(json_value, arg_type, record_types))
-        .collect::<Result<Vec<_>>>()?;
-
-    Ok(iargs)
-}
+impl ValueDecoder for JsonDecoder<'_> {
+    type Value = JValue;
 
-/// Convert json value (Number, String or Bool) to an array of ivalues according to the supplied argument types.
-fn json_value_to_ivalues<'a>(
-    json_value: JValue,
-    mut arg_types: impl Iterator<Item = (&'a String, &'a IType)> + ExactSizeIterator,
-) -> Result<Vec<IValue>> {
-    if arg_types.len() != 1 {
-        return Err(ArgDeError(format!(
-            "called function has the following signature: '{:?}', and it isn't suitable for an argument '{:?}' provided",
-            arg_types.collect::<Vec<_>>(),
-            json_value,
-        )));
+    fn kind(&self, value: &JValue) -> JsonKind {
+        JsonKind::of(value)
     }
 
-    // unwrap is safe here because iterator size's been checked
-    let arg_type = arg_types.next().unwrap().1;
-    let ivalue = jvalue_to_ivalue(json_value, arg_type, &HashMap::new())?;
-
-    Ok(vec![ivalue])
-}
-
-/// Convert json Null to an empty array of ivalues.
-fn json_null_to_ivalues<'a>(
-    arg_types: impl Iterator<Item = (&'a String, &'a IType)> + ExactSizeIterator,
-) -> Result<Vec<IValue>> {
-    if arg_types.len() != 0 {
-        return Err(ArgDeError(format!(
-            "the called function has the following signature: {:?}, but no arguments is provided",
-            arg_types.collect::<Vec<_>>()
-        )));
+    fn shape(&self, value: JValue) -> ValueShape<JValue> {
+        match value {
+            JValue::Null => ValueShape::Null,
+            JValue::Object(json_map) => ValueShape::Map(json_map.into_iter().collect()),
+            JValue::Array(json_array) => ValueShape::Array(json_array),
+            json_value => ValueShape::Other(json_value),
+        }
     }
 
-    Ok(vec![])
-}
-
-/// Convert one JValue to an array of ivalues according to the supplied argument type.
-fn jvalue_to_ivalue(jvalue: JValue, ty: &IType, record_types: &RecordTypes) -> Result<IValue> {
-    macro_rules! to_ivalue(
-        ($json_value:expr, $ty:ident) => {
-            {
-                let value = match $json_value {
-                    // if there is an array with only one element try to implicitly flatten it,
-                    // this is needed mostly because jsonpath lib returns Vec<&JValue> and
-                    // could be changed in future
-                    JValue::Array(mut json_array) if json_array.len() == 1 => {
-                        serde_json::from_value(json_array.remove(0))
-                    },
-                    jvalue => serde_json::from_value(jvalue),
-                }.map_err(|e|
-                    ArgDeError(format!("error {:?} occurred while deserialize output result to a json value",e))
-                )?;
-
-                Ok(IValue::$ty(value))
+    fn decode_primitive(
+        &self,
+        jvalue: JValue,
+        ty: &IType,
+        record_types: &RecordTypes,
+        path: &ValuePath<'_>,
+    ) -> ConvResult<IValue> {
+        let options = self.options;
+        let expected = || itype_text_view(ty, record_types);
+        let type_mismatch = |received| {
+            path.error(ErrorKind::TypeMismatch {
+                expected: expected(),
+                received,
+            })
+        };
+
+        let invalid_value = |received, reason| {
+            path.error(ErrorKind::InvalidValue {
+                expected: expected(),
+                received,
+                reason,
+            })
+        };
+
+        macro_rules! to_ivalue(
+            ($json_value:expr, $ty:ident, $json_kind:ident) => {
+                {
+                    let json_value = flatten_single_value($json_value);
+                    let received = JsonKind::of(&json_value);
+                    if received != JsonKind::$json_kind {
+                        return Err(type_mismatch(received));
+                    }
+
+                    let value = serde_json::from_value(json_value)
+                        .map_err(|e| invalid_value(received, e.to_string()))?;
+
+                    Ok(IValue::$ty(value))
+                }
             }
-        }
-    );
-
-    match ty {
-        IType::S8 => to_ivalue!(jvalue, S8),
-        IType::S16 => to_ivalue!(jvalue, S16),
-        IType::S32 => to_ivalue!(jvalue, S32),
-        IType::S64 => to_ivalue!(jvalue, S64),
-        IType::U8 => to_ivalue!(jvalue, U8),
-        IType::U16 => to_ivalue!(jvalue, U16),
-        IType::U32 => to_ivalue!(jvalue, U32),
-        IType::U64 => to_ivalue!(jvalue, U64),
-        IType::F32 => to_ivalue!(jvalue, F32),
-        IType::F64 => to_ivalue!(jvalue, F64),
-        IType::String => to_ivalue!(jvalue, String),
-        IType::Array(value_type) => {
-            let value = match jvalue {
-                JValue::Array(json_array) => {
-                    let iargs = json_array
-                        .into_iter()
-                        .map(|json_value| jvalue_to_ivalue(json_value, value_type, record_types))
-                        .collect::<Result<Vec<_>>>()?;
-
-                    Ok(iargs)
+        );
+
+        // integers are converted without serde coercions to check their ranges strictly
+        macro_rules! to_integer(
+            ($json_value:expr, $ty:ident, $accepts_string:expr) => {
+                {
+                    let json_value = flatten_single_value($json_value);
+                    let received = JsonKind::of(&json_value);
+                    let value = match json_value {
+                        JValue::Number(number) => number
+                            .as_i64()
+                            .map(i128::from)
+                            .or_else(|| number.as_u64().map(i128::from))
+                            // like in JSON Schema, numbers with zero fractional part are integers,
+                            // but only while floats represent all integers around them exactly
+                            .or_else(|| {
+                                number
+                                    .as_f64()
+                                    .filter(|number| {
+                                        number.fract() == 0.0 && number.abs() <= MAX_SAFE_INTEGER
+                                    })
+                                    .map(|number| number as i128)
+                            })
+                            .ok_or_else(|| {
+                                invalid_value(received, format!("{} isn't an integer", number))
+                            })?,
+                        JValue::String(string) if $accepts_string => {
+                            string.parse::<i128>().map_err(|e| {
+                                let reason = format!("{} isn't a decimal integer: {}", string, e);
+                                invalid_value(received, reason)
+                            })?
+                        }
+                        _ => return Err(type_mismatch(received)),
+                    };
+
+                    let value = TryFrom::try_from(value).map_err(|_| {
+                        invalid_value(received, format!("{} is out of range", value))
+                    })?;
+
+                    Ok(IValue::$ty(value))
                 }
-                _ => Err(ArgDeError(format!(
-                    "expected array of {:?} types, got {:?}",
-                    value_type, jvalue
-                ))),
-            }?;
-
-            Ok(IValue::Array(value))
+            }
+        );
+
+        match ty {
+            IType::S8 => to_integer!(jvalue, S8, false),
+            IType::S16 => to_integer!(jvalue, S16, false),
+            IType::S32 => to_integer!(jvalue, S32, false),
+            IType::S64 => to_integer!(jvalue, S64, options.int64_as_string),
+            IType::U8 => to_integer!(jvalue, U8, false),
+            IType::U16 => to_integer!(jvalue, U16, false),
+            IType::U32 => to_integer!(jvalue, U32, false),
+            IType::U64 => to_integer!(jvalue, U64, options.int64_as_string),
+            IType::I32 => to_integer!(jvalue, I32, false),
+            IType::I64 => to_integer!(jvalue, I64, options.int64_as_string),
+            IType::F32 => to_ivalue!(jvalue, F32, Number),
+            IType::F64 => to_ivalue!(jvalue, F64, Number),
+            IType::String => to_ivalue!(jvalue, String, String),
+            // arrays and records are walked by decode_value, so only anyrefs are left here
+            _ => Err(path.error(ErrorKind::UnsupportedType {
+                expected: expected(),
+            })),
         }
-        IType::I32 => to_ivalue!(jvalue, I32),
-        IType::I64 => to_ivalue!(jvalue, I64),
-        IType::Record(record_type_id) => {
-            let value = json_record_type_to_ivalue(jvalue, record_type_id, &record_types)?;
-            Ok(IValue::Record(value))
+    }
+
+    fn decode_byte_array(
+        &self,
+        jvalue: JValue,
+        path: &ValuePath<'_>,
+        expected: &dyn Fn() -> String,
+    ) -> ConvResult<std::result::Result<Vec<u8>, JValue>> {
+        match jvalue {
+            // byte arrays could be passed compactly in any encoding regardless of options
+            JValue::String(encoded) => {
+                let bytes = decode_byte_array(&encoded).map_err(|reason| {
+                    path.error(ErrorKind::InvalidValue {
+                        expected: expected(),
+                        received: JsonKind::String,
+                        reason,
+                    })
+                })?;
+                Ok(Ok(bytes))
+            }
+            jvalue => Ok(Err(jvalue)),
         }
-        IType::Anyref => Err(ArgDeError(String::from("anyrefs aren't supported now"))),
     }
 }
 
-#[allow(clippy::ptr_arg)]
-/// Convert JValue of array or object types to an IValue record type.
-// TODO: after introducing new Record type wrapper change the result type
-fn json_record_type_to_ivalue(
-    json_value: JValue,
-    record_type_id: &u64,
-    record_types: &RecordTypes,
-) -> Result<NEVec<IValue>> {
-    let record_type = record_types.get(record_type_id).ok_or_else(|| {
-        ArgDeError(format!(
-            "record with type id `{}` wasn't found",
-            record_type_id
-        ))
-    })?;
-
+/// If there is an array with only one element try to implicitly flatten it,
+/// this is needed mostly because jsonpath lib returns Vec<&JValue> and
+/// could be changed in future.
+fn flatten_single_value(json_value: JValue) -> JValue {
     match json_value {
-        JValue::Object(json_map) => Ok(NEVec::new(json_map_to_ivalues(
-            json_map,
-            record_type
-                .fields
-                .iter()
-                .map(|field| (&field.name, &field.ty)),
-            record_types,
-        )?)
-        .unwrap()),
-        JValue::Array(json_array) => Ok(NEVec::new(json_array_to_ivalues(
-            json_array,
-            record_type.fields.iter().map(|field| (&field.ty)),
-            record_types,
-        )?)
-        .unwrap()),
-        _ => Err(ArgDeError(format!(
-            "record with type id `{}` should be encoded as array or map of fields",
-            record_type_id
-        ))),
+        JValue::Array(mut json_array) if json_array.len() == 1 => json_array.remove(0),
+        json_value => json_value,
+    }
+}
+
+/// Strings prefixed with 0x are decoded as hex, other ones as base64.
+fn decode_byte_array(encoded: &str) -> std::result::Result<Vec<u8>, String> {
+    match encoded.strip_prefix("0x") {
+        Some(hex_string) => {
+            hex::decode(hex_string).map_err(|e| format!("invalid hex byte array: {}", e))
+        }
+        None => base64::decode(encoded).map_err(|e| format!("invalid base64 byte array: {}", e)),
     }
 }
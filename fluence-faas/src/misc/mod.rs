@@ -14,11 +14,18 @@
  * limitations under the License.
  */
 
+mod binary_to_ivalues;
+mod binary_value;
+mod ivalues_to_binary;
 mod ivalues_to_json;
+mod ivalues_decoding;
 mod json_to_ivalues;
 mod modules_load_strategy;
 mod utils;
 
+pub(crate) use binary_to_ivalues::binary_to_ivalues;
+pub use binary_value::BinaryEncoding;
+pub(crate) use ivalues_to_binary::ivalues_to_binary;
 pub(crate) use ivalues_to_json::ivalues_to_json;
 pub(crate) use json_to_ivalues::json_to_ivalues;
 pub(crate) use modules_load_strategy::ModulesLoadStrategy;
@@ -17,12 +17,15 @@
 use crate::Result;
 use crate::config::FaaSModuleConfig;
 use crate::errors::FaaSError;
+use crate::journal::journaled_host_import;
+use crate::journal::Journal;
 use crate::logger::log_utf8_string_closure;
 use crate::logger::LoggerFilter;
 use crate::logger::WASM_LOG_ENV_NAME;
 
 use fce::FCEModuleConfig;
 use fce::HostImportDescriptor;
+use fce::ModuleSignatureConfig;
 use wasmer_core::import::ImportObject;
 use wasmer_core::import::Namespace;
 use wasmer_core::vm::Ctx;
@@ -30,10 +33,11 @@ use wasmer_runtime::func;
 use wasmer_wit::IValue;
 use wasmer_wit::IType;
 
+use parking_lot::Mutex;
+
 use std::collections::HashMap;
-use std::cell::RefCell;
 use std::path::PathBuf;
-use std::rc::Rc;
+use std::sync::Arc;
 use std::ops::Deref;
 
 pub(crate) fn create_host_import(host_cmd: String) -> HostImportDescriptor {
@@ -65,10 +69,10 @@ pub(crate) fn create_host_import(host_cmd: String) -> HostImportDescriptor {
 }
 
 fn create_call_parameters_import(
-    call_parameters: Rc<RefCell<fluence_sdk_main::CallParameters>>,
+    call_parameters: Arc<Mutex<fluence_sdk_main::CallParameters>>,
 ) -> HostImportDescriptor {
     let call_parameters_closure = move |_ctx: &mut Ctx, _args: Vec<IValue>| {
-        let result = crate::to_interface_value(call_parameters.borrow().deref()).unwrap();
+        let result = crate::to_interface_value(call_parameters.lock().deref()).unwrap();
         Some(result)
     };
 
@@ -84,11 +88,23 @@ fn create_call_parameters_import(
 pub(crate) fn make_fce_config(
     module_name: String,
     faas_module_config: Option<FaaSModuleConfig>,
-    call_parameters: Rc<RefCell<fluence_sdk_main::CallParameters>>,
+    call_parameters: Arc<Mutex<fluence_sdk_main::CallParameters>>,
+    journal: Arc<Mutex<Journal>>,
     logger_filter: &LoggerFilter<'_>,
+    trusted_keys: &[[u8; 32]],
 ) -> Result<FCEModuleConfig> {
     let mut fce_module_config = FCEModuleConfig::default();
 
+    // a module without a config still should be signed if there are trusted keys
+    if !trusted_keys.is_empty() {
+        fce_module_config.signature_verification = Some(ModuleSignatureConfig {
+            trusted_keys: trusted_keys.to_vec(),
+            detached_signature: faas_module_config
+                .as_ref()
+                .and_then(|config| config.signature.clone()),
+        });
+    }
+
     let faas_module_config = match faas_module_config {
         Some(faas_module_config) => faas_module_config,
         None => return Ok(fce_module_config),
@@ -97,6 +113,8 @@ pub(crate) fn make_fce_config(
     if let Some(mem_pages_count) = faas_module_config.mem_pages_count {
         fce_module_config.mem_pages_count = mem_pages_count;
     }
+    fce_module_config.wasm_features = faas_module_config.wasm_features;
+    fce_module_config.max_stack_height = faas_module_config.max_stack_height;
 
     if let Some(wasi) = faas_module_config.wasi {
         fce_module_config.wasi_envs = wasi.envs;
@@ -118,7 +136,20 @@ pub(crate) fn make_fce_config(
         fce_module_config.wasi_envs.extend(mapped_dirs);
     };
 
-    fce_module_config.host_imports = faas_module_config.host_imports;
+    // call parameters are journaled with the call itself, so only other imports are wrapped
+    fce_module_config.host_imports = faas_module_config
+        .host_imports
+        .into_iter()
+        .map(|(import_name, descriptor)| {
+            let descriptor = journaled_host_import(
+                descriptor,
+                module_name.clone(),
+                import_name.clone(),
+                journal.clone(),
+            );
+            (import_name, descriptor)
+        })
+        .collect();
     fce_module_config.host_imports.insert(
         String::from("get_call_parameters"),
         create_call_parameters_import(call_parameters),
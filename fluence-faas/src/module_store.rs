@@ -0,0 +1,205 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::FaaSError;
+use crate::Result;
+use crate::TomlFaaSModuleConfig;
+
+use std::io::ErrorKind;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+const HASH_HEX_LEN: usize = 64;
+const WASM_EXTENSION: &str = "wasm";
+const CONFIG_EXTENSION: &str = "json";
+
+/// A Wasm module with the config it was last saved with in a module store.
+#[derive(Debug, Clone)]
+pub struct StoredModule {
+    pub hash: String,
+    pub wasm: Vec<u8>,
+    pub config: TomlFaaSModuleConfig,
+}
+
+/// Local content-addressed storage of modules: each module is saved as <hash>.wasm
+/// along with its config in <hash>.json, where hash is a hex blake3 hash of the Wasm.
+/// Modules shared by several services are stored only once, and their bytes are checked
+/// against the hash on every load. A config isn't a part of the module identity, so services
+/// should keep their own module configs rather than rely on the stored one.
+#[derive(Debug, Clone)]
+pub struct ModuleStore {
+    root: PathBuf,
+}
+
+impl ModuleStore {
+    pub fn new<P: Into<PathBuf>>(root: P) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Returns a hash by which the module is addressed in a store.
+    pub fn hash(wasm: &[u8]) -> String {
+        blake3::hash(wasm).to_hex().to_string()
+    }
+
+    /// Save a module with its config and return its hash. If the module has been
+    /// already saved, only its config is replaced, but a corrupted module file is rewritten.
+    /// The replaced config is lost for everyone sharing the module.
+    pub fn put(&self, wasm: &[u8], config: &TomlFaaSModuleConfig) -> Result<String> {
+        std::fs::create_dir_all(&self.root)
+            .map_err(|e| FaaSError::IOError(format!("{:?}: {}", self.root, e)))?;
+
+        let hash = Self::hash(wasm);
+        let wasm_path = self.path(&hash, WASM_EXTENSION);
+        if !self.is_intact(&hash) {
+            write_atomically(&wasm_path, wasm)?;
+        }
+
+        let config = serde_json::to_vec_pretty(config).map_err(|e| {
+            FaaSError::ModuleStoreError(format!("config of module {} is malformed: {}", hash, e))
+        })?;
+        write_atomically(&self.path(&hash, CONFIG_EXTENSION), &config)?;
+
+        Ok(hash)
+    }
+
+    /// Returns true if a module with such hash is saved in the store.
+    pub fn contains(&self, hash: &str) -> bool {
+        check_hash(hash).is_ok() && self.path(hash, WASM_EXTENSION).exists()
+    }
+
+    /// Load a module with its config, bytes of the module are verified against the hash.
+    pub fn load(&self, hash: &str) -> Result<StoredModule> {
+        let wasm = self.load_wasm(hash)?;
+
+        let config_path = self.path(hash, CONFIG_EXTENSION);
+        let config = match std::fs::read(&config_path) {
+            Ok(config) => serde_json::from_slice(&config).map_err(|e| {
+                FaaSError::ModuleStoreError(format!("config {:?} is malformed: {}", config_path, e))
+            })?,
+            // a module could be put to the store dir by hand without a config
+            Err(e) if e.kind() == ErrorKind::NotFound => TomlFaaSModuleConfig::default(),
+            Err(e) => return Err(FaaSError::IOError(format!("{:?}: {}", config_path, e))),
+        };
+
+        Ok(StoredModule {
+            hash: hash.to_string(),
+            wasm,
+            config,
+        })
+    }
+
+    /// Load only bytes of a module, they are verified against the hash.
+    pub fn load_wasm(&self, hash: &str) -> Result<Vec<u8>> {
+        check_hash(hash)?;
+
+        let wasm_path = self.path(hash, WASM_EXTENSION);
+        let wasm = std::fs::read(&wasm_path).map_err(|e| match e.kind() {
+            ErrorKind::NotFound => FaaSError::ModuleStoreError(format!(
+                "module with hash {} isn't found in {:?}",
+                hash, self.root
+            )),
+            _ => FaaSError::IOError(format!("{:?}: {}", wasm_path, e)),
+        })?;
+
+        let actual_hash = Self::hash(&wasm);
+        if actual_hash != hash {
+            return Err(FaaSError::ModuleIntegrityError {
+                hash: hash.to_string(),
+                actual_hash,
+            });
+        }
+
+        Ok(wasm)
+    }
+
+    /// Returns hashes of all stored modules.
+    pub fn list(&self) -> Result<Vec<String>> {
+        let dir_entries = match std::fs::read_dir(&self.root) {
+            Ok(dir_entries) => dir_entries,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(FaaSError::IOError(format!("{:?}: {}", self.root, e))),
+        };
+
+        let mut hashes = vec![];
+        for entry in dir_entries {
+            let path = entry?.path();
+            if path.extension().map_or(true, |e| e != WASM_EXTENSION) {
+                continue;
+            }
+
+            let hash = path.file_stem().map(|s| s.to_string_lossy().to_string());
+            if let Some(hash) = hash.filter(|hash| check_hash(hash).is_ok()) {
+                hashes.push(hash);
+            }
+        }
+        hashes.sort();
+
+        Ok(hashes)
+    }
+
+    /// Returns true if a module file with such hash exists and its bytes match the hash.
+    fn is_intact(&self, hash: &str) -> bool {
+        match self.load_wasm(hash) {
+            Ok(_) => true,
+            Err(FaaSError::ModuleIntegrityError { hash, actual_hash }) => {
+                log::warn!(
+                    "module with hash {} is corrupted, its actual hash is {}, it'll be rewritten",
+                    hash,
+                    actual_hash
+                );
+                false
+            }
+            Err(_) => false,
+        }
+    }
+
+    fn path(&self, hash: &str, extension: &str) -> PathBuf {
+        self.root.join(format!("{}.{}", hash, extension))
+    }
+}
+
+fn check_hash(hash: &str) -> Result<()> {
+    let is_lower_hex = |c: char| c.is_ascii_digit() || ('a'..='f').contains(&c);
+    if hash.len() != HASH_HEX_LEN || !hash.chars().all(is_lower_hex) {
+        return Err(FaaSError::InvalidModuleHash(hash.to_string()));
+    }
+
+    Ok(())
+}
+
+/// Writes to a temporary file next to the target one first, so a crash can't leave
+/// a partially written module or config. Each target file has its own temporary file.
+fn write_atomically(path: &Path, data: &[u8]) -> Result<()> {
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_path);
+
+    let write = || -> std::io::Result<()> {
+        let mut tmp_file = std::fs::File::create(&tmp_path)?;
+        tmp_file.write_all(data)?;
+        tmp_file.sync_all()?;
+        std::fs::rename(&tmp_path, path)?;
+
+        // the rename itself is persisted by syncing the store dir
+        match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => std::fs::File::open(dir)?.sync_all(),
+            _ => Ok(()),
+        }
+    };
+
+    write().map_err(|e| FaaSError::IOError(format!("{:?}: {}", path, e)))
+}
@@ -17,6 +17,7 @@
 use crate::FaaSError;
 use crate::Result;
 use crate::config::*;
+use crate::WasmFeatures;
 
 use serde_derive::Serialize;
 use serde_derive::Deserialize;
@@ -30,11 +31,30 @@ use std::path::PathBuf;
 An example of the config:
 
 modules_dir = "wasm/artifacts/wasm_modules"
+# modules with a hash are loaded from the module store instead of modules_dir
+module_store_dir = "wasm/store"
+# hex ed25519 public keys, if specified, only modules signed by them are loaded
+trusted_keys = ["3b6a27bcceb6a42d62a3a8d02a6f0d73653215771de243a63ac048a18b59da29"]
+# byte arrays in JSON results: "array" (default), "base64" or "hex"
+json_byte_array_encoding = "base64"
+# u64, i64 and s64 values in JSON are decimal strings
+json_int64_as_string = true
 
 [[module]]
     name = "ipfs_node.wasm"
     mem_pages_count = 100
     logger_enabled = true
+    stateless = false
+    # or pure = true to mark all functions of the module
+    pure_functions = ["get_address"]
+    # deep recursion traps instead of exhausting the native stack
+    max_stack_height = 65536
+    # a hex detached signature, the embedded one is checked if it isn't specified
+    signature = "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b"
+
+[[module]]
+    name = "curl_adapter"
+    hash = "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262"
 
     [module.mounted_binaries]
     mysql = "/usr/bin/mysql"
@@ -45,6 +65,15 @@ modules_dir = "wasm/artifacts/wasm_modules"
     preopened_files = ["/Users/user/tmp"]
     mapped_dirs = {"tmp" = "/Users/user/tmp"}
 
+    # modules using not allowed features are rejected on loading
+    [module.features]
+    floats = false
+    max_globals = 64
+    max_tables = 1
+    simd = false
+    threads = false
+    bulk_memory = false
+
 [default]
     mem_pages_count = 100
     logger_enabled = true
@@ -62,9 +91,21 @@ modules_dir = "wasm/artifacts/wasm_modules"
 #[derive(Deserialize, Serialize, Debug, Clone, Default)]
 pub struct TomlFaaSConfig {
     pub modules_dir: Option<String>,
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub module_store_dir: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub trusted_keys: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub module: Vec<TomlFaaSNamedModuleConfig>,
     pub default: Option<TomlFaaSModuleConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub journal_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memoization_cache_size: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub json_byte_array_encoding: Option<ByteArrayEncoding>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub json_int64_as_string: Option<bool>,
 }
 
 impl TomlFaaSConfig {
@@ -89,6 +130,8 @@ impl TryInto<FaaSConfig> for TomlFaaSConfig {
 #[derive(Deserialize, Serialize, Debug, Clone, Default)]
 pub struct TomlFaaSNamedModuleConfig {
     pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
     #[serde(flatten)]
     pub config: TomlFaaSModuleConfig,
 }
@@ -116,6 +159,15 @@ pub struct TomlFaaSModuleConfig {
     pub wasi: Option<TomlWASIConfig>,
     pub mounted_binaries: Option<toml::value::Table>,
     pub logging_mask: Option<i32>,
+    pub stateless: Option<bool>,
+    pub pure: Option<bool>,
+    pub pure_functions: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_stack_height: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub features: Option<TomlWasmFeatures>,
 }
 
 impl TomlFaaSNamedModuleConfig {
@@ -125,11 +177,22 @@ impl TomlFaaSNamedModuleConfig {
     {
         Self {
             name: name.into(),
+            hash: None,
             config: <_>::default(),
         }
     }
 }
 
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct TomlWasmFeatures {
+    pub floats: Option<bool>,
+    pub max_globals: Option<u32>,
+    pub max_tables: Option<u32>,
+    pub simd: Option<bool>,
+    pub threads: Option<bool>,
+    pub bulk_memory: Option<bool>,
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone, Default)]
 pub struct TomlWASIConfig {
     pub preopened_files: Option<Vec<String>>,
@@ -139,6 +202,23 @@ pub struct TomlWASIConfig {
 
 /// Prepare config after parsing it from TOML.
 pub fn from_toml_faas_config(config: TomlFaaSConfig) -> Result<FaaSConfig> {
+    let module_hashes = config
+        .module
+        .iter()
+        .filter_map(|module| Some((module.name.clone(), module.hash.clone()?)))
+        .collect::<HashMap<_, _>>();
+    if !module_hashes.is_empty() && config.module_store_dir.is_none() {
+        return Err(FaaSError::ConfigParseError(String::from(
+            "module_store_dir should be specified to load modules by hashes",
+        )));
+    }
+
+    let trusted_keys = config
+        .trusted_keys
+        .iter()
+        .map(|key| parse_trusted_key(key))
+        .collect::<Result<Vec<_>>>()?;
+
     let modules_config = config
         .module
         .into_iter()
@@ -147,10 +227,28 @@ pub fn from_toml_faas_config(config: TomlFaaSConfig) -> Result<FaaSConfig> {
 
     let default_modules_config = config.default.map(from_toml_module_config).transpose()?;
 
+    let memoization_cache_size = match config.memoization_cache_size {
+        Some(0) => {
+            return Err(FaaSError::ConfigParseError(String::from(
+                "memoization_cache_size should be greater than 0",
+            )))
+        }
+        size => size,
+    };
+
     Ok(FaaSConfig {
         modules_dir: config.modules_dir.map(PathBuf::from),
         modules_config,
+        module_store_dir: config.module_store_dir.map(PathBuf::from),
+        module_hashes,
+        trusted_keys,
         default_modules_config,
+        journal_path: config.journal_path.map(PathBuf::from),
+        memoization_cache_size,
+        json_options: JsonOptions {
+            byte_array_encoding: config.json_byte_array_encoding.unwrap_or_default(),
+            int64_as_string: config.json_int64_as_string.unwrap_or_default(),
+        },
     })
 }
 
@@ -176,16 +274,71 @@ pub fn from_toml_module_config(config: TomlFaaSModuleConfig) -> Result<FaaSModul
         host_cli_imports.insert(import_name, crate::misc::create_host_import(host_cmd));
     }
 
+    let pure_functions = match (config.pure, config.pure_functions) {
+        (Some(true), Some(_)) => {
+            return Err(FaaSError::ConfigParseError(String::from(
+                "pure and pure_functions can't be specified together",
+            )))
+        }
+        (Some(true), None) => PureFunctions::All,
+        (_, Some(functions)) => PureFunctions::Only(functions.into_iter().collect()),
+        (_, None) => PureFunctions::None,
+    };
+
+    let signature = config
+        .signature
+        .map(|signature| {
+            hex::decode(&signature).map_err(|e| {
+                FaaSError::ConfigParseError(format!("signature {} isn't hex: {}", signature, e))
+            })
+        })
+        .transpose()?;
+
     let wasi = config.wasi.map(from_toml_wasi_config).transpose()?;
+    let wasm_features = config
+        .features
+        .map(from_toml_wasm_features)
+        .unwrap_or_default();
+
     Ok(FaaSModuleConfig {
         mem_pages_count: config.mem_pages_count,
         logger_enabled: config.logger_enabled.unwrap_or(true),
         host_imports: host_cli_imports,
         wasi,
         logging_mask: config.logging_mask.unwrap_or(i32::max_value()),
+        stateless: config.stateless.unwrap_or(false),
+        pure_functions,
+        signature,
+        wasm_features,
+        max_stack_height: config.max_stack_height,
     })
 }
 
+pub fn from_toml_wasm_features(features: TomlWasmFeatures) -> WasmFeatures {
+    let default_features = WasmFeatures::default();
+
+    WasmFeatures {
+        floats: features.floats.unwrap_or(default_features.floats),
+        max_globals: features.max_globals.or(default_features.max_globals),
+        max_tables: features.max_tables.or(default_features.max_tables),
+        simd: features.simd.unwrap_or(default_features.simd),
+        threads: features.threads.unwrap_or(default_features.threads),
+        bulk_memory: features.bulk_memory.unwrap_or(default_features.bulk_memory),
+    }
+}
+
+fn parse_trusted_key(key: &str) -> Result<[u8; 32]> {
+    let mut key_bytes = [0u8; 32];
+    hex::decode_to_slice(key, &mut key_bytes).map_err(|e| {
+        FaaSError::ConfigParseError(format!(
+            "trusted key {} should be a hex ed25519 public key: {}",
+            key, e
+        ))
+    })?;
+
+    Ok(key_bytes)
+}
+
 pub fn from_toml_wasi_config(wasi: TomlWASIConfig) -> Result<FaaSWASIConfig> {
     let to_vec = |elem: (String, toml::Value)| -> Result<(Vec<u8>, Vec<u8>)> {
         let to = elem
@@ -236,6 +389,7 @@ mod tests {
     fn serialize_named() {
         let config = TomlFaaSNamedModuleConfig {
             name: "name".to_string(),
+            hash: None,
             config: TomlFaaSModuleConfig {
                 mem_pages_count: Some(100),
                 logger_enabled: Some(false),
@@ -246,6 +400,12 @@ mod tests {
                 }),
                 mounted_binaries: None,
                 logging_mask: None,
+                stateless: None,
+                pure: None,
+                pure_functions: None,
+                signature: None,
+                max_stack_height: None,
+                features: None,
             },
         };
 
@@ -0,0 +1,382 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::FaaSModuleInterface;
+use crate::IType;
+use crate::faas_interface::itype_text_view;
+
+use fce::RecordTypes;
+
+/// Rust keywords that can't be used as identifiers without the raw prefix.
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in", "let", "loop",
+    "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "static",
+    "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use", "virtual",
+    "where", "while", "yield",
+];
+
+/// Keywords that can't be raw identifiers, so a suffix is appended to them.
+const NON_RAW_KEYWORDS: &[&str] = &["crate", "self", "Self", "super", "_"];
+
+/// Methods of the generated client, export functions with such names get a suffix.
+const CLIENT_METHODS: &[&str] = &["new", "with_call_parameters"];
+
+/// Generates a Rust source file with a struct for every record of a module and a client
+/// whose methods call export functions of the module through `FluenceFaaS::call_with_ivalues`.
+///
+/// The generated file is intended to be used as a module of a host crate depending on
+/// fluence-faas, records are converted to and from IValues with `into_ivalue`/`from_ivalue`.
+pub fn module_rust_bindings(
+    module_name: &str,
+    module_interface: &FaaSModuleInterface<'_>,
+) -> String {
+    let record_types = module_interface.record_types;
+
+    let mut code = format!(
+        "// Generated from the interface of the `{}` module, don't edit it manually.\n\n",
+        module_name
+    );
+    code.push_str("#![allow(dead_code, unused_imports, clippy::all)]\n\n");
+    code.push_str("use fluence_faas::CallParameters;\n");
+    code.push_str("use fluence_faas::FaaSError;\n");
+    code.push_str("use fluence_faas::FluenceFaaS;\n");
+    code.push_str("use fluence_faas::IValue;\n\n");
+    code.push_str(&format!(
+        "pub const MODULE_NAME: &str = {:?};\n",
+        module_name
+    ));
+
+    let mut records = record_types.values().collect::<Vec<_>>();
+    records.sort_by(|lhs, rhs| lhs.name.cmp(&rhs.name));
+    for record in records {
+        let fields = record
+            .fields
+            .iter()
+            .map(|field| (field.name.as_str(), &field.ty))
+            .collect::<Vec<_>>();
+        code.push('\n');
+        code.push_str(&record_bindings(&record.name, &fields, record_types));
+    }
+
+    code.push('\n');
+    code.push_str(&client_bindings(module_name, module_interface));
+    code.push('\n');
+    code.push_str(HELPERS);
+
+    code
+}
+
+fn record_bindings(name: &str, fields: &[(&str, &IType)], record_types: &RecordTypes) -> String {
+    let mut code = String::from("#[derive(Debug, Clone, PartialEq)]\n");
+    code.push_str(&format!("pub struct {} {{\n", name));
+    for (field_name, ty) in fields {
+        code.push_str(&format!(
+            "    pub {}: {},\n",
+            identifier(field_name),
+            rust_type(ty, record_types)
+        ));
+    }
+    code.push_str("}\n\n");
+
+    code.push_str(&format!("impl {} {{\n", name));
+    code.push_str("    pub fn into_ivalue(self) -> IValue {\n");
+    code.push_str("        let fields = vec![\n");
+    for (field_name, ty) in fields {
+        let field = format!("self.{}", identifier(field_name));
+        code.push_str(&format!(
+            "            {},\n",
+            to_ivalue(ty, &field, record_types)
+        ));
+    }
+    code.push_str("        ];\n\n");
+    code.push_str("        IValue::Record(\n");
+    code.push_str("            fluence_faas::ne_vec::NEVec::new(fields)\n");
+    code.push_str("                .expect(\"records always have at least one field\"),\n");
+    code.push_str("        )\n");
+    code.push_str("    }\n\n");
+
+    code.push_str("    pub fn from_ivalue(value: IValue) -> Result<Self, FaaSError> {\n");
+    code.push_str("        let mut fields = match value {\n");
+    code.push_str("            IValue::Record(fields) => fields.into_vec().into_iter(),\n");
+    code.push_str(&format!(
+        "            value => return Err(unexpected_value({:?}, value)),\n",
+        name
+    ));
+    code.push_str("        };\n\n");
+    code.push_str("        let record = Self {\n");
+    for (field_name, ty) in fields {
+        code.push_str(&format!(
+            "            {}: next_field(&mut fields, {:?}, {:?})\n",
+            identifier(field_name),
+            name,
+            field_name
+        ));
+        code.push_str(&format!(
+            "                .and_then(|value| {})?,\n",
+            from_ivalue(ty, "value", record_types)
+        ));
+    }
+    code.push_str("        };\n\n");
+    code.push_str("        Ok(record)\n");
+    code.push_str("    }\n");
+    code.push_str("}\n");
+
+    code
+}
+
+fn client_bindings(module_name: &str, module_interface: &FaaSModuleInterface<'_>) -> String {
+    let record_types = module_interface.record_types;
+    let client_name = format!("{}Client", camel_case(module_name));
+
+    let mut code = String::from("/// Calls export functions of the module with typed arguments.\n");
+    code.push_str(&format!("pub struct {}<'f> {{\n", client_name));
+    code.push_str("    faas: &'f mut FluenceFaaS,\n");
+    code.push_str("    call_parameters: CallParameters,\n");
+    code.push_str("}\n\n");
+
+    code.push_str(&format!("impl<'f> {}<'f> {{\n", client_name));
+    code.push_str("    pub fn new(faas: &'f mut FluenceFaaS) -> Self {\n");
+    code.push_str("        Self {\n");
+    code.push_str("            faas,\n");
+    code.push_str("            call_parameters: <_>::default(),\n");
+    code.push_str("        }\n");
+    code.push_str("    }\n\n");
+    code.push_str(
+        "    pub fn with_call_parameters(mut self, call_parameters: CallParameters) -> Self {\n",
+    );
+    code.push_str("        self.call_parameters = call_parameters;\n");
+    code.push_str("        self\n");
+    code.push_str("    }\n");
+
+    let mut signatures = module_interface
+        .function_signatures
+        .iter()
+        .collect::<Vec<_>>();
+    signatures.sort_by(|lhs, rhs| lhs.name.cmp(&rhs.name));
+    let function_names = signatures
+        .iter()
+        .map(|signature| signature.name.as_str())
+        .collect::<Vec<_>>();
+    for signature in signatures {
+        let arguments = signature
+            .arguments
+            .iter()
+            .map(|arg| {
+                format!(
+                    ", {}: {}",
+                    identifier(&arg.name),
+                    rust_type(&arg.ty, record_types)
+                )
+            })
+            .collect::<String>();
+        let output = match signature.outputs.as_slice() {
+            [] => String::from("()"),
+            [ty] => rust_type(ty, record_types),
+            _ => String::from("Vec<IValue>"),
+        };
+
+        let method_name = method_name(&signature.name, &function_names);
+        code.push('\n');
+        if method_name != identifier(&signature.name) {
+            code.push_str(&format!(
+                "    /// Calls the export function `{}`.\n",
+                signature.name
+            ));
+        }
+        code.push_str(&format!(
+            "    pub fn {}(&mut self{}) -> Result<{}, FaaSError> {{\n",
+            method_name, arguments, output
+        ));
+        code.push_str("        let arguments = vec![\n");
+        for arg in signature.arguments.iter() {
+            code.push_str(&format!(
+                "            {},\n",
+                to_ivalue(&arg.ty, &identifier(&arg.name), record_types)
+            ));
+        }
+        code.push_str("        ];\n\n");
+        if signature.outputs.is_empty() {
+            code.push_str("        self.faas.call_with_ivalues(\n");
+        } else {
+            code.push_str("        let outputs = self.faas.call_with_ivalues(\n");
+        }
+        code.push_str("            MODULE_NAME,\n");
+        code.push_str(&format!("            {:?},\n", signature.name.as_str()));
+        code.push_str("            &arguments,\n");
+        code.push_str("            self.call_parameters.clone(),\n");
+        code.push_str("        )?;\n\n");
+        match signature.outputs.as_slice() {
+            [] => code.push_str("        Ok(())\n"),
+            [ty] => {
+                code.push_str("        let value = single_output(outputs)?;\n");
+                code.push_str(&format!(
+                    "        {}\n",
+                    from_ivalue(ty, "value", record_types)
+                ));
+            }
+            _ => code.push_str("        Ok(outputs)\n"),
+        }
+        code.push_str("    }\n");
+    }
+    code.push_str("}\n");
+
+    code
+}
+
+fn rust_type(ty: &IType, record_types: &RecordTypes) -> String {
+    let ty = match ty {
+        IType::S8 => "i8",
+        IType::S16 => "i16",
+        IType::S32 | IType::I32 => "i32",
+        IType::S64 | IType::I64 => "i64",
+        IType::U8 => "u8",
+        IType::U16 => "u16",
+        IType::U32 => "u32",
+        IType::U64 => "u64",
+        IType::F32 => "f32",
+        IType::F64 => "f64",
+        IType::String => "String",
+        IType::Anyref => "IValue",
+        IType::Array(value_type) => return format!("Vec<{}>", rust_type(value_type, record_types)),
+        IType::Record(_) => return itype_text_view(ty, record_types),
+    };
+
+    ty.to_string()
+}
+
+/// Returns an expression that converts a consumed value of the given type to IValue.
+fn to_ivalue(ty: &IType, value: &str, record_types: &RecordTypes) -> String {
+    match ty {
+        IType::Array(value_type) => format!(
+            "IValue::Array({}.into_iter().map(|value| {}).collect())",
+            value,
+            to_ivalue(value_type, "value", record_types)
+        ),
+        IType::Record(_) => format!("{}.into_ivalue()", value),
+        IType::Anyref => value.to_string(),
+        ty => format!("IValue::{}({})", scalar_variant(ty), value),
+    }
+}
+
+/// Returns an expression of type `Result<T, FaaSError>` that converts IValue to a value
+/// of the given type.
+fn from_ivalue(ty: &IType, value: &str, record_types: &RecordTypes) -> String {
+    let expected = itype_text_view(ty, record_types);
+
+    match ty {
+        IType::Array(value_type) => format!(
+            "match {} {{ IValue::Array(values) => values.into_iter().map(|value| {}).collect::<Result<Vec<_>, FaaSError>>(), value => Err(unexpected_value({:?}, value)) }}",
+            value,
+            from_ivalue(value_type, "value", record_types),
+            expected
+        ),
+        IType::Record(_) => format!("{}::from_ivalue({})", expected, value),
+        IType::Anyref => format!("Ok({})", value),
+        ty => format!(
+            "match {} {{ IValue::{}(value) => Ok(value), value => Err(unexpected_value({:?}, value)) }}",
+            value, scalar_variant(ty), expected
+        ),
+    }
+}
+
+/// Returns the name of the IValue variant holding values of a scalar type.
+fn scalar_variant(ty: &IType) -> &'static str {
+    match ty {
+        IType::S8 => "S8",
+        IType::S16 => "S16",
+        IType::S32 => "S32",
+        IType::S64 => "S64",
+        IType::U8 => "U8",
+        IType::U16 => "U16",
+        IType::U32 => "U32",
+        IType::U64 => "U64",
+        IType::F32 => "F32",
+        IType::F64 => "F64",
+        IType::String => "String",
+        IType::I32 => "I32",
+        IType::I64 => "I64",
+        // composite values and anyrefs are converted by the callers
+        IType::Array(_) | IType::Record(_) | IType::Anyref => unreachable!(),
+    }
+}
+
+fn identifier(name: &str) -> String {
+    if KEYWORDS.contains(&name) {
+        format!("r#{}", name)
+    } else if NON_RAW_KEYWORDS.contains(&name) {
+        format!("{}_", name)
+    } else {
+        name.to_string()
+    }
+}
+
+/// Returns a name of the client method calling the function, it differs from the function
+/// name if the latter is taken by the client itself.
+fn method_name(function_name: &str, function_names: &[&str]) -> String {
+    if !CLIENT_METHODS.contains(&function_name) {
+        return identifier(function_name);
+    }
+
+    // the suffix is extended until the name doesn't clash with other functions
+    let mut name = format!("{}_", function_name);
+    while CLIENT_METHODS.contains(&name.as_str()) || function_names.contains(&name.as_str()) {
+        name.push('_');
+    }
+
+    identifier(&name)
+}
+
+fn camel_case(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+const HELPERS: &str = r#"fn unexpected_value(expected: &str, value: IValue) -> FaaSError {
+    FaaSError::TypedResultError(format!("expected {}, got {:?}", expected, value))
+}
+
+fn next_field(
+    fields: &mut impl Iterator<Item = IValue>,
+    record_name: &str,
+    field_name: &str,
+) -> Result<IValue, FaaSError> {
+    fields.next().ok_or_else(|| {
+        FaaSError::TypedResultError(format!(
+            "field {} of record {} is missing",
+            field_name, record_name
+        ))
+    })
+}
+
+fn single_output(mut outputs: Vec<IValue>) -> Result<IValue, FaaSError> {
+    match outputs.len() {
+        1 => Ok(outputs.remove(0)),
+        _ => Err(FaaSError::TypedResultError(format!(
+            "expected exactly one output, got {:?}",
+            outputs
+        ))),
+    }
+}
+"#;
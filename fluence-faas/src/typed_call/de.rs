@@ -0,0 +1,329 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::ConversionError;
+use crate::IType;
+use crate::IValue;
+use crate::itype_text_view;
+
+use fce::RecordTypes;
+use serde::de;
+use serde::de::DeserializeOwned;
+use serde::de::IntoDeserializer;
+use serde::forward_to_deserialize_any;
+
+type DeResult<T> = std::result::Result<T, ConversionError>;
+
+/// Converts outputs of a function to a Rust value, a unit is expected for functions
+/// without outputs.
+pub(crate) fn from_outputs<T: DeserializeOwned>(
+    mut outputs: Vec<IValue>,
+    output_types: &[IType],
+    record_types: &RecordTypes,
+) -> DeResult<T> {
+    if outputs.len() != output_types.len() {
+        return Err(ConversionError::new(format!(
+            "resulted values {:?} aren't compatible with output types {:?}",
+            outputs, output_types
+        )));
+    }
+
+    match outputs.len() {
+        0 => T::deserialize(().into_deserializer()),
+        1 => T::deserialize(IValueDeserializer {
+            value: outputs.remove(0),
+            ty: &output_types[0],
+            record_types,
+        }),
+        _ => Err(ConversionError::new(
+            "multi-values aren't supported now - more then one result values aren't possible",
+        )),
+    }
+}
+
+/// Deserializes IValue of the given type, types are needed to know names of record fields.
+struct IValueDeserializer<'a> {
+    value: IValue,
+    ty: &'a IType,
+    record_types: &'a RecordTypes,
+}
+
+impl<'a> IValueDeserializer<'a> {
+    fn incompatible(&self) -> ConversionError {
+        ConversionError::new(format!(
+            "value {:?} is incompatible with type {}",
+            self.value,
+            itype_text_view(self.ty, self.record_types)
+        ))
+    }
+
+    fn record_fields(&self) -> Option<Vec<(&'a str, &'a IType)>> {
+        let record_type = match self.ty {
+            IType::Record(record_type_id) => self.record_types.get(record_type_id)?,
+            _ => return None,
+        };
+
+        let fields = record_type
+            .fields
+            .iter()
+            .map(|field| (field.name.as_str(), &field.ty))
+            .collect();
+
+        Some(fields)
+    }
+
+    fn visit_array<'de, V: de::Visitor<'de>>(self, visitor: V) -> DeResult<V::Value> {
+        let value_type = match self.ty {
+            IType::Array(value_type) => value_type,
+            _ => return Err(self.incompatible()),
+        };
+
+        match self.value {
+            IValue::Array(values) => {
+                let types = values.iter().map(|_| &**value_type).collect();
+                visitor.visit_seq(SeqAccess::new(values, types, self.record_types))
+            }
+            _ => Err(self.incompatible()),
+        }
+    }
+
+    fn visit_record<'de, V: de::Visitor<'de>>(self, visitor: V) -> DeResult<V::Value> {
+        let fields = match self.record_fields() {
+            Some(fields) => fields,
+            None => return Err(self.incompatible()),
+        };
+
+        match self.value {
+            IValue::Record(values) => visitor.visit_map(RecordAccess::new(
+                values.into_vec(),
+                fields,
+                self.record_types,
+            )),
+            _ => Err(self.incompatible()),
+        }
+    }
+}
+
+impl<'de, 'a> de::Deserializer<'de> for IValueDeserializer<'a> {
+    type Error = ConversionError;
+
+    fn deserialize_any<V: de::Visitor<'de>>(self, visitor: V) -> DeResult<V::Value> {
+        match self.value {
+            IValue::S8(value) => visitor.visit_i8(value),
+            IValue::S16(value) => visitor.visit_i16(value),
+            IValue::S32(value) => visitor.visit_i32(value),
+            IValue::S64(value) => visitor.visit_i64(value),
+            IValue::U8(value) => visitor.visit_u8(value),
+            IValue::U16(value) => visitor.visit_u16(value),
+            IValue::U32(value) => visitor.visit_u32(value),
+            IValue::U64(value) => visitor.visit_u64(value),
+            IValue::I32(value) => visitor.visit_i32(value),
+            IValue::I64(value) => visitor.visit_i64(value),
+            IValue::F32(value) => visitor.visit_f32(value),
+            IValue::F64(value) => visitor.visit_f64(value),
+            IValue::String(value) => visitor.visit_string(value),
+            IValue::Array(_) => self.visit_array(visitor),
+            IValue::Record(_) => self.visit_record(visitor),
+        }
+    }
+
+    // bools are passed as integers by Fluence SDK
+    fn deserialize_bool<V: de::Visitor<'de>>(self, visitor: V) -> DeResult<V::Value> {
+        let value = match self.value {
+            IValue::S8(value) => value as i128,
+            IValue::S16(value) => value as i128,
+            IValue::S32(value) | IValue::I32(value) => value as i128,
+            IValue::S64(value) | IValue::I64(value) => value as i128,
+            IValue::U8(value) => value as i128,
+            IValue::U16(value) => value as i128,
+            IValue::U32(value) => value as i128,
+            IValue::U64(value) => value as i128,
+            _ => return self.deserialize_any(visitor),
+        };
+
+        visitor.visit_bool(value != 0)
+    }
+
+    fn deserialize_bytes<V: de::Visitor<'de>>(self, visitor: V) -> DeResult<V::Value> {
+        self.deserialize_byte_buf(visitor)
+    }
+
+    fn deserialize_byte_buf<V: de::Visitor<'de>>(self, visitor: V) -> DeResult<V::Value> {
+        let bytes = match &self.value {
+            IValue::Array(values) => values
+                .iter()
+                .map(|value| match value {
+                    IValue::U8(byte) => Some(*byte),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>(),
+            _ => None,
+        };
+
+        match bytes {
+            Some(bytes) => visitor.visit_byte_buf(bytes),
+            None => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_option<V: de::Visitor<'de>>(self, visitor: V) -> DeResult<V::Value> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> DeResult<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    // records could be deserialized to tuples in the order of their fields
+    fn deserialize_tuple<V: de::Visitor<'de>>(self, _len: usize, visitor: V) -> DeResult<V::Value> {
+        let fields = match self.value {
+            IValue::Record(_) => self.record_fields(),
+            _ => None,
+        };
+        let types = match fields {
+            Some(fields) => fields.into_iter().map(|(_, ty)| ty).collect(),
+            None => return self.deserialize_any(visitor),
+        };
+
+        match self.value {
+            IValue::Record(values) => {
+                visitor.visit_seq(SeqAccess::new(values.into_vec(), types, self.record_types))
+            }
+            _ => Err(self.incompatible()),
+        }
+    }
+
+    fn deserialize_tuple_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> DeResult<V::Value> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    forward_to_deserialize_any! {
+        i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        unit unit_struct seq map struct enum identifier ignored_any
+    }
+}
+
+struct SeqAccess<'a> {
+    values: std::iter::Zip<std::vec::IntoIter<IValue>, std::vec::IntoIter<&'a IType>>,
+    record_types: &'a RecordTypes,
+    index: usize,
+}
+
+impl<'a> SeqAccess<'a> {
+    fn new(values: Vec<IValue>, types: Vec<&'a IType>, record_types: &'a RecordTypes) -> Self {
+        Self {
+            values: values.into_iter().zip(types.into_iter()),
+            record_types,
+            index: 0,
+        }
+    }
+}
+
+impl<'de, 'a> de::SeqAccess<'de> for SeqAccess<'a> {
+    type Error = ConversionError;
+
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> DeResult<Option<T::Value>> {
+        let (value, ty) = match self.values.next() {
+            Some(element) => element,
+            None => return Ok(None),
+        };
+
+        let index = self.index;
+        self.index += 1;
+
+        let deserializer = IValueDeserializer {
+            value,
+            ty,
+            record_types: self.record_types,
+        };
+        seed.deserialize(deserializer)
+            .map(Some)
+            .map_err(|e| e.at_index(index))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.values.len())
+    }
+}
+
+/// Provides record fields by their names.
+struct RecordAccess<'a> {
+    fields: std::iter::Zip<std::vec::IntoIter<IValue>, std::vec::IntoIter<(&'a str, &'a IType)>>,
+    current: Option<(IValue, &'a str, &'a IType)>,
+    record_types: &'a RecordTypes,
+}
+
+impl<'a> RecordAccess<'a> {
+    fn new(
+        values: Vec<IValue>,
+        fields: Vec<(&'a str, &'a IType)>,
+        record_types: &'a RecordTypes,
+    ) -> Self {
+        Self {
+            fields: values.into_iter().zip(fields.into_iter()),
+            current: None,
+            record_types,
+        }
+    }
+}
+
+impl<'de, 'a> de::MapAccess<'de> for RecordAccess<'a> {
+    type Error = ConversionError;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> DeResult<Option<K::Value>> {
+        let (value, (name, ty)) = match self.fields.next() {
+            Some(field) => field,
+            None => return Ok(None),
+        };
+
+        self.current = Some((value, name, ty));
+        seed.deserialize(name.to_string().into_deserializer())
+            .map(Some)
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(&mut self, seed: V) -> DeResult<V::Value> {
+        let (value, name, ty) = self
+            .current
+            .take()
+            .ok_or_else(|| ConversionError::new("value is requested before its key"))?;
+
+        let deserializer = IValueDeserializer {
+            value,
+            ty,
+            record_types: self.record_types,
+        };
+        seed.deserialize(deserializer).map_err(|e| e.at_field(name))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.fields.len())
+    }
+}
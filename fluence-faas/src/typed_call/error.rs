@@ -0,0 +1,87 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fmt;
+
+/// An error of conversion between Rust values and IValues, the path to the failed value
+/// is collected while the error propagates up.
+#[derive(Debug)]
+pub(crate) struct ConversionError {
+    /// Path segments from the failed value to the root.
+    segments: Vec<PathSegment>,
+    message: String,
+}
+
+#[derive(Debug)]
+enum PathSegment {
+    Field(String),
+    Index(usize),
+}
+
+impl ConversionError {
+    pub(crate) fn new(message: impl fmt::Display) -> Self {
+        Self {
+            segments: vec![],
+            message: message.to_string(),
+        }
+    }
+
+    pub(crate) fn at_field(mut self, name: &str) -> Self {
+        self.segments.push(PathSegment::Field(name.to_string()));
+        self
+    }
+
+    pub(crate) fn at_index(mut self, index: usize) -> Self {
+        self.segments.push(PathSegment::Index(index));
+        self
+    }
+
+    /// Renders the error with the path starting with root, e.g. `args.user.tags[3]: ...`.
+    pub(crate) fn render(&self, root: &str) -> String {
+        let mut path = root.to_string();
+        for segment in self.segments.iter().rev() {
+            match segment {
+                PathSegment::Field(name) => {
+                    path.push('.');
+                    path.push_str(name);
+                }
+                PathSegment::Index(index) => path.push_str(&format!("[{}]", index)),
+            }
+        }
+
+        format!("{}: {}", path, self.message)
+    }
+}
+
+impl std::error::Error for ConversionError {}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render("value"))
+    }
+}
+
+impl serde::ser::Error for ConversionError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self::new(msg)
+    }
+}
+
+impl serde::de::Error for ConversionError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self::new(msg)
+    }
+}
@@ -0,0 +1,626 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::ConversionError;
+use crate::IFunctionArg;
+use crate::IType;
+use crate::IValue;
+use crate::faas_interface::itype_text_view;
+
+use fce::RecordTypes;
+use serde::ser;
+use serde::ser::Impossible;
+use serde::Serialize;
+use wasmer_wit::NEVec;
+
+use std::convert::TryFrom;
+
+type SerResult<T> = std::result::Result<T, ConversionError>;
+
+/// Converts arguments of a function to IValues checking them against the function signature.
+/// Arguments could be a struct with fields named as arguments, a tuple with arguments
+/// in the order of the signature, or a unit for functions without arguments.
+pub(crate) fn to_arguments<T: Serialize + ?Sized>(
+    arguments: &T,
+    signature: &[IFunctionArg],
+    record_types: &RecordTypes,
+) -> SerResult<Vec<IValue>> {
+    arguments.serialize(ArgumentsSerializer {
+        signature,
+        record_types,
+    })
+}
+
+struct ArgumentsSerializer<'a> {
+    signature: &'a [IFunctionArg],
+    record_types: &'a RecordTypes,
+}
+
+impl<'a> ArgumentsSerializer<'a> {
+    fn unsupported(&self, kind: &str) -> ConversionError {
+        ConversionError::new(format!(
+            "arguments should be a tuple, a struct or a unit, got {}",
+            kind
+        ))
+    }
+
+    fn positional(self) -> SeqSerializer<'a, Vec<IValue>> {
+        let types = self.signature.iter().map(|arg| &arg.ty).collect();
+        SeqSerializer::new(ElementTypes::Fields(types), self.record_types, Ok)
+    }
+}
+
+/// Rejects values of a kind that can't be serialized by the serializer.
+macro_rules! reject(
+    ($($method:ident($($arg:ty),*) => $kind:expr,)*) => {
+        $(
+            fn $method(self, $(_: $arg),*) -> SerResult<Self::Ok> {
+                Err(self.unsupported($kind))
+            }
+        )*
+    }
+);
+
+impl<'a> ser::Serializer for ArgumentsSerializer<'a> {
+    type Ok = Vec<IValue>;
+    type Error = ConversionError;
+
+    type SerializeSeq = SeqSerializer<'a, Vec<IValue>>;
+    type SerializeTuple = SeqSerializer<'a, Vec<IValue>>;
+    type SerializeTupleStruct = SeqSerializer<'a, Vec<IValue>>;
+    type SerializeTupleVariant = Impossible<Vec<IValue>, ConversionError>;
+    type SerializeMap = Impossible<Vec<IValue>, ConversionError>;
+    type SerializeStruct = StructSerializer<'a, Vec<IValue>>;
+    type SerializeStructVariant = Impossible<Vec<IValue>, ConversionError>;
+
+    reject! {
+        serialize_bool(bool) => "bool",
+        serialize_i8(i8) => "integer",
+        serialize_i16(i16) => "integer",
+        serialize_i32(i32) => "integer",
+        serialize_i64(i64) => "integer",
+        serialize_u8(u8) => "integer",
+        serialize_u16(u16) => "integer",
+        serialize_u32(u32) => "integer",
+        serialize_u64(u64) => "integer",
+        serialize_f32(f32) => "float",
+        serialize_f64(f64) => "float",
+        serialize_char(char) => "char",
+        serialize_str(&str) => "string",
+        serialize_bytes(&[u8]) => "bytes",
+        serialize_none() => "none",
+        serialize_unit_variant(&'static str, u32, &'static str) => "enum",
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> SerResult<Self::Ok> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> SerResult<Self::Ok> {
+        match self.signature.len() {
+            0 => Ok(vec![]),
+            count => Err(ConversionError::new(format!(
+                "expected {} arguments, got unit",
+                count
+            ))),
+        }
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> SerResult<Self::Ok> {
+        self.serialize_unit()
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> SerResult<Self::Ok> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> SerResult<Self::Ok> {
+        Err(self.unsupported("enum"))
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> SerResult<Self::SerializeSeq> {
+        Ok(self.positional())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> SerResult<Self::SerializeTuple> {
+        Ok(self.positional())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> SerResult<Self::SerializeTupleStruct> {
+        Ok(self.positional())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> SerResult<Self::SerializeTupleVariant> {
+        Err(self.unsupported("enum"))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> SerResult<Self::SerializeMap> {
+        Err(self.unsupported("map"))
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> SerResult<Self::SerializeStruct> {
+        let fields = self
+            .signature
+            .iter()
+            .map(|arg| (arg.name.as_str(), &arg.ty))
+            .collect();
+        Ok(StructSerializer::new(fields, self.record_types, Ok))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> SerResult<Self::SerializeStructVariant> {
+        Err(self.unsupported("enum"))
+    }
+}
+
+/// Serializes a value of the given type to IValue.
+struct IValueSerializer<'a> {
+    ty: &'a IType,
+    record_types: &'a RecordTypes,
+}
+
+impl<'a> IValueSerializer<'a> {
+    fn unsupported(&self, kind: &str) -> ConversionError {
+        ConversionError::new(format!(
+            "expected {}, got {}",
+            itype_text_view(self.ty, self.record_types),
+            kind
+        ))
+    }
+
+    fn integer(self, value: i128) -> SerResult<IValue> {
+        macro_rules! to_integer(
+            ($ty:ident) => {
+                TryFrom::try_from(value).map(IValue::$ty).map_err(|_| {
+                    ConversionError::new(format!("{} is out of range of {}", value, stringify!($ty)))
+                })
+            }
+        );
+
+        match self.ty {
+            IType::S8 => to_integer!(S8),
+            IType::S16 => to_integer!(S16),
+            IType::S32 => to_integer!(S32),
+            IType::S64 => to_integer!(S64),
+            IType::U8 => to_integer!(U8),
+            IType::U16 => to_integer!(U16),
+            IType::U32 => to_integer!(U32),
+            IType::U64 => to_integer!(U64),
+            IType::I32 => to_integer!(I32),
+            IType::I64 => to_integer!(I64),
+            _ => Err(self.unsupported("integer")),
+        }
+    }
+
+    fn record_fields(&self, record_type_id: u64) -> SerResult<Vec<(&'a str, &'a IType)>> {
+        let record_type = self.record_types.get(&record_type_id).ok_or_else(|| {
+            ConversionError::new(format!(
+                "record with type id `{}` wasn't found",
+                record_type_id
+            ))
+        })?;
+
+        let fields = record_type
+            .fields
+            .iter()
+            .map(|field| (field.name.as_str(), &field.ty))
+            .collect();
+
+        Ok(fields)
+    }
+
+    fn sequence(self, kind: &str) -> SerResult<SeqSerializer<'a, IValue>> {
+        match self.ty {
+            IType::Array(value_type) => Ok(SeqSerializer::new(
+                ElementTypes::Array(value_type),
+                self.record_types,
+                |values| Ok(IValue::Array(values)),
+            )),
+            IType::Record(record_type_id) => {
+                let types = self
+                    .record_fields(*record_type_id)?
+                    .into_iter()
+                    .map(|(_, ty)| ty)
+                    .collect();
+                Ok(SeqSerializer::new(
+                    ElementTypes::Fields(types),
+                    self.record_types,
+                    into_record,
+                ))
+            }
+            _ => Err(self.unsupported(kind)),
+        }
+    }
+}
+
+impl<'a> ser::Serializer for IValueSerializer<'a> {
+    type Ok = IValue;
+    type Error = ConversionError;
+
+    type SerializeSeq = SeqSerializer<'a, IValue>;
+    type SerializeTuple = SeqSerializer<'a, IValue>;
+    type SerializeTupleStruct = SeqSerializer<'a, IValue>;
+    type SerializeTupleVariant = Impossible<IValue, ConversionError>;
+    type SerializeMap = Impossible<IValue, ConversionError>;
+    type SerializeStruct = StructSerializer<'a, IValue>;
+    type SerializeStructVariant = Impossible<IValue, ConversionError>;
+
+    reject! {
+        serialize_none() => "none",
+        serialize_unit() => "unit",
+        serialize_unit_struct(&'static str) => "unit",
+        serialize_unit_variant(&'static str, u32, &'static str) => "enum",
+    }
+
+    // bools are passed as integers by Fluence SDK
+    fn serialize_bool(self, value: bool) -> SerResult<IValue> {
+        self.integer(value as i128)
+    }
+
+    fn serialize_i8(self, value: i8) -> SerResult<IValue> {
+        self.integer(value as i128)
+    }
+
+    fn serialize_i16(self, value: i16) -> SerResult<IValue> {
+        self.integer(value as i128)
+    }
+
+    fn serialize_i32(self, value: i32) -> SerResult<IValue> {
+        self.integer(value as i128)
+    }
+
+    fn serialize_i64(self, value: i64) -> SerResult<IValue> {
+        self.integer(value as i128)
+    }
+
+    fn serialize_u8(self, value: u8) -> SerResult<IValue> {
+        self.integer(value as i128)
+    }
+
+    fn serialize_u16(self, value: u16) -> SerResult<IValue> {
+        self.integer(value as i128)
+    }
+
+    fn serialize_u32(self, value: u32) -> SerResult<IValue> {
+        self.integer(value as i128)
+    }
+
+    fn serialize_u64(self, value: u64) -> SerResult<IValue> {
+        self.integer(value as i128)
+    }
+
+    fn serialize_f32(self, value: f32) -> SerResult<IValue> {
+        match self.ty {
+            IType::F32 => Ok(IValue::F32(value)),
+            IType::F64 => Ok(IValue::F64(value as f64)),
+            _ => Err(self.unsupported("f32")),
+        }
+    }
+
+    fn serialize_f64(self, value: f64) -> SerResult<IValue> {
+        match self.ty {
+            IType::F64 => Ok(IValue::F64(value)),
+            _ => Err(self.unsupported("f64")),
+        }
+    }
+
+    fn serialize_char(self, value: char) -> SerResult<IValue> {
+        self.serialize_str(value.encode_utf8(&mut [0; 4]))
+    }
+
+    fn serialize_str(self, value: &str) -> SerResult<IValue> {
+        match self.ty {
+            IType::String => Ok(IValue::String(value.to_string())),
+            _ => Err(self.unsupported("string")),
+        }
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> SerResult<IValue> {
+        match self.ty {
+            IType::Array(value_type) if **value_type == IType::U8 => Ok(IValue::Array(
+                value.iter().copied().map(IValue::U8).collect(),
+            )),
+            _ => Err(self.unsupported("bytes")),
+        }
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> SerResult<IValue> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> SerResult<IValue> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> SerResult<IValue> {
+        Err(self.unsupported("enum"))
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> SerResult<Self::SerializeSeq> {
+        self.sequence("sequence")
+    }
+
+    fn serialize_tuple(self, _len: usize) -> SerResult<Self::SerializeTuple> {
+        self.sequence("tuple")
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> SerResult<Self::SerializeTupleStruct> {
+        self.sequence("tuple struct")
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> SerResult<Self::SerializeTupleVariant> {
+        Err(self.unsupported("enum"))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> SerResult<Self::SerializeMap> {
+        Err(self.unsupported("map"))
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> SerResult<Self::SerializeStruct> {
+        match self.ty {
+            IType::Record(record_type_id) => {
+                let fields = self.record_fields(*record_type_id)?;
+                Ok(StructSerializer::new(
+                    fields,
+                    self.record_types,
+                    into_record,
+                ))
+            }
+            _ => Err(self.unsupported("struct")),
+        }
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> SerResult<Self::SerializeStructVariant> {
+        Err(self.unsupported("enum"))
+    }
+}
+
+fn into_record(fields: Vec<IValue>) -> SerResult<IValue> {
+    NEVec::new(fields)
+        .map(IValue::Record)
+        .map_err(|_| ConversionError::new("records without fields aren't supported"))
+}
+
+enum ElementTypes<'a> {
+    /// Elements of an array.
+    Array(&'a IType),
+
+    /// Arguments of a function or fields of a record in their order.
+    Fields(Vec<&'a IType>),
+}
+
+/// Serializes positional values, finish converts them to the result.
+struct SeqSerializer<'a, O> {
+    types: ElementTypes<'a>,
+    record_types: &'a RecordTypes,
+    values: Vec<IValue>,
+    finish: fn(Vec<IValue>) -> SerResult<O>,
+}
+
+impl<'a, O> SeqSerializer<'a, O> {
+    fn new(
+        types: ElementTypes<'a>,
+        record_types: &'a RecordTypes,
+        finish: fn(Vec<IValue>) -> SerResult<O>,
+    ) -> Self {
+        Self {
+            types,
+            record_types,
+            values: vec![],
+            finish,
+        }
+    }
+
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> SerResult<()> {
+        let index = self.values.len();
+        let ty = match &self.types {
+            ElementTypes::Array(ty) => *ty,
+            ElementTypes::Fields(types) => *types.get(index).ok_or_else(|| {
+                ConversionError::new(format!("expected {} values, got more", types.len()))
+            })?,
+        };
+
+        let serializer = IValueSerializer {
+            ty,
+            record_types: self.record_types,
+        };
+        let value = value.serialize(serializer).map_err(|e| e.at_index(index))?;
+        self.values.push(value);
+
+        Ok(())
+    }
+
+    fn complete(self) -> SerResult<O> {
+        if let ElementTypes::Fields(types) = &self.types {
+            if types.len() != self.values.len() {
+                return Err(ConversionError::new(format!(
+                    "expected {} values, got {}",
+                    types.len(),
+                    self.values.len()
+                )));
+            }
+        }
+
+        (self.finish)(self.values)
+    }
+}
+
+impl<'a, O> ser::SerializeSeq for SeqSerializer<'a, O> {
+    type Ok = O;
+    type Error = ConversionError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> SerResult<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> SerResult<O> {
+        self.complete()
+    }
+}
+
+impl<'a, O> ser::SerializeTuple for SeqSerializer<'a, O> {
+    type Ok = O;
+    type Error = ConversionError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> SerResult<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> SerResult<O> {
+        self.complete()
+    }
+}
+
+impl<'a, O> ser::SerializeTupleStruct for SeqSerializer<'a, O> {
+    type Ok = O;
+    type Error = ConversionError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> SerResult<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> SerResult<O> {
+        self.complete()
+    }
+}
+
+/// Serializes named values, finish converts them in the order of fields to the result.
+struct StructSerializer<'a, O> {
+    fields: Vec<(&'a str, &'a IType)>,
+    record_types: &'a RecordTypes,
+    values: Vec<Option<IValue>>,
+    finish: fn(Vec<IValue>) -> SerResult<O>,
+}
+
+impl<'a, O> StructSerializer<'a, O> {
+    fn new(
+        fields: Vec<(&'a str, &'a IType)>,
+        record_types: &'a RecordTypes,
+        finish: fn(Vec<IValue>) -> SerResult<O>,
+    ) -> Self {
+        let values = fields.iter().map(|_| None).collect();
+
+        Self {
+            fields,
+            record_types,
+            values,
+            finish,
+        }
+    }
+}
+
+impl<'a, O> ser::SerializeStruct for StructSerializer<'a, O> {
+    type Ok = O;
+    type Error = ConversionError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> SerResult<()> {
+        let position = self
+            .fields
+            .iter()
+            .position(|(name, _)| *name == key)
+            .ok_or_else(|| ConversionError::new("unexpected value").at_field(key))?;
+
+        let serializer = IValueSerializer {
+            ty: self.fields[position].1,
+            record_types: self.record_types,
+        };
+        let value = value.serialize(serializer).map_err(|e| e.at_field(key))?;
+        self.values[position] = Some(value);
+
+        Ok(())
+    }
+
+    fn end(self) -> SerResult<O> {
+        let record_types = self.record_types;
+        let values = self
+            .values
+            .into_iter()
+            .zip(self.fields.iter())
+            .map(|(value, (name, ty))| {
+                value.ok_or_else(|| {
+                    let expected = itype_text_view(ty, record_types);
+                    ConversionError::new(format!("missing value of type {}", expected))
+                        .at_field(name)
+                })
+            })
+            .collect::<SerResult<Vec<_>>>()?;
+
+        (self.finish)(values)
+    }
+}
@@ -23,7 +23,7 @@ use pretty_assertions::assert_eq;
 use once_cell::sync::Lazy;
 use serde_json::json;
 
-use std::rc::Rc;
+use std::sync::Arc;
 
 static ARG_CONFIG: Lazy<fluence_faas::TomlFaaSConfig> = Lazy::new(|| {
     let mut arguments_passing_config =
@@ -55,9 +55,9 @@ pub fn get_interfaces() {
     let string_type_outputs = vec![IType::String];
 
     let string_type_sign = fluence_faas::FaaSFunctionSignature {
-        name: Rc::new(String::from("string_type")),
-        arguments: Rc::new(string_type_arguments),
-        outputs: Rc::new(string_type_outputs),
+        name: Arc::new(String::from("string_type")),
+        arguments: Arc::new(string_type_arguments),
+        outputs: Arc::new(string_type_outputs),
     };
 
     let bytearray_type_arguments = vec![fluence_faas::IFunctionArg {
@@ -67,9 +67,9 @@ pub fn get_interfaces() {
     let bytearray_type_outputs = vec![IType::Array(Box::new(IType::U8))];
 
     let bytearray_type_sign = fluence_faas::FaaSFunctionSignature {
-        name: Rc::new(String::from("bytearray_type")),
-        arguments: Rc::new(bytearray_type_arguments),
-        outputs: Rc::new(bytearray_type_outputs),
+        name: Arc::new(String::from("bytearray_type")),
+        arguments: Arc::new(bytearray_type_arguments),
+        outputs: Arc::new(bytearray_type_outputs),
     };
 
     let i32_type_arguments = vec![fluence_faas::IFunctionArg {
@@ -79,9 +79,9 @@ pub fn get_interfaces() {
     let i32_type_outputs = vec![IType::S32];
 
     let i32_type_sign = fluence_faas::FaaSFunctionSignature {
-        name: Rc::new(String::from("i32_type")),
-        arguments: Rc::new(i32_type_arguments),
-        outputs: Rc::new(i32_type_outputs),
+        name: Arc::new(String::from("i32_type")),
+        arguments: Arc::new(i32_type_arguments),
+        outputs: Arc::new(i32_type_outputs),
     };
 
     let i64_type_arguments = vec![fluence_faas::IFunctionArg {
@@ -92,9 +92,9 @@ pub fn get_interfaces() {
     let i64_type_outputs = vec![IType::S64];
 
     let i64_type_sign = fluence_faas::FaaSFunctionSignature {
-        name: Rc::new(String::from("i64_type")),
-        arguments: Rc::new(i64_type_arguments),
-        outputs: Rc::new(i64_type_outputs),
+        name: Arc::new(String::from("i64_type")),
+        arguments: Arc::new(i64_type_arguments),
+        outputs: Arc::new(i64_type_outputs),
     };
 
     let u32_type_arguments = vec![fluence_faas::IFunctionArg {
@@ -104,9 +104,9 @@ pub fn get_interfaces() {
     let u32_type_outputs = vec![IType::U32];
 
     let u32_type_sign = fluence_faas::FaaSFunctionSignature {
-        name: Rc::new(String::from("u32_type")),
-        arguments: Rc::new(u32_type_arguments),
-        outputs: Rc::new(u32_type_outputs),
+        name: Arc::new(String::from("u32_type")),
+        arguments: Arc::new(u32_type_arguments),
+        outputs: Arc::new(u32_type_outputs),
     };
 
     let u64_type_arguments = vec![fluence_faas::IFunctionArg {
@@ -116,9 +116,9 @@ pub fn get_interfaces() {
     let u64_type_outputs = vec![IType::U64];
 
     let u64_type_sign = fluence_faas::FaaSFunctionSignature {
-        name: Rc::new(String::from("u64_type")),
-        arguments: Rc::new(u64_type_arguments),
-        outputs: Rc::new(u64_type_outputs),
+        name: Arc::new(String::from("u64_type")),
+        arguments: Arc::new(u64_type_arguments),
+        outputs: Arc::new(u64_type_outputs),
     };
 
     let f32_type_arguments = vec![fluence_faas::IFunctionArg {
@@ -128,9 +128,9 @@ pub fn get_interfaces() {
     let f32_type_outputs = vec![IType::F32];
 
     let f32_type_sign = fluence_faas::FaaSFunctionSignature {
-        name: Rc::new(String::from("f32_type")),
-        arguments: Rc::new(f32_type_arguments),
-        outputs: Rc::new(f32_type_outputs),
+        name: Arc::new(String::from("f32_type")),
+        arguments: Arc::new(f32_type_arguments),
+        outputs: Arc::new(f32_type_outputs),
     };
 
     let f64_type_arguments = vec![fluence_faas::IFunctionArg {
@@ -140,18 +140,18 @@ pub fn get_interfaces() {
     let f64_type_outputs = vec![IType::F64];
 
     let f64_type_sign = fluence_faas::FaaSFunctionSignature {
-        name: Rc::new(String::from("f64_type")),
-        arguments: Rc::new(f64_type_arguments),
-        outputs: Rc::new(f64_type_outputs),
+        name: Arc::new(String::from("f64_type")),
+        arguments: Arc::new(f64_type_arguments),
+        outputs: Arc::new(f64_type_outputs),
     };
 
     let empty_type_arguments = vec![];
     let empty_type_outputs = vec![IType::String];
 
     let empty_type_sign = fluence_faas::FaaSFunctionSignature {
-        name: Rc::new(String::from("empty_type")),
-        arguments: Rc::new(empty_type_arguments),
-        outputs: Rc::new(empty_type_outputs),
+        name: Arc::new(String::from("empty_type")),
+        arguments: Arc::new(empty_type_arguments),
+        outputs: Arc::new(empty_type_outputs),
     };
 
     let bool_type_arguments = vec![fluence_faas::IFunctionArg {
@@ -161,9 +161,9 @@ pub fn get_interfaces() {
     let bool_type_outputs = vec![IType::I32];
 
     let bool_type_sign = fluence_faas::FaaSFunctionSignature {
-        name: Rc::new(String::from("bool_type")),
-        arguments: Rc::new(bool_type_arguments),
-        outputs: Rc::new(bool_type_outputs),
+        name: Arc::new(String::from("bool_type")),
+        arguments: Arc::new(bool_type_arguments),
+        outputs: Arc::new(bool_type_outputs),
     };
 
     let all_types_arguments = vec![
@@ -219,9 +219,9 @@ pub fn get_interfaces() {
     let all_types_outputs = vec![IType::Array(Box::new(IType::U8))];
 
     let all_types_sign = fluence_faas::FaaSFunctionSignature {
-        name: Rc::new(String::from("all_types")),
-        arguments: Rc::new(all_types_arguments),
-        outputs: Rc::new(all_types_outputs),
+        name: Arc::new(String::from("all_types")),
+        arguments: Arc::new(all_types_arguments),
+        outputs: Arc::new(all_types_outputs),
     };
 
     let functions = vec![
@@ -351,6 +351,13 @@ pub fn i32_type() {
     let value = std::i32::MIN;
     let result7 = call_faas!(faas, MODULE_NAME, FUNC_NAME, json!(value));
     assert_eq!(result7, value + 2);
+
+    // like in JSON Schema, numbers with zero fractional part are integers
+    let result8 = call_faas!(faas, MODULE_NAME, FUNC_NAME, json!(1.0));
+    assert_eq!(result8, expected_result);
+
+    let result9 = faas.call_with_json(MODULE_NAME, FUNC_NAME, json!(1.5), <_>::default());
+    assert!(result9.is_err());
 }
 
 #[test]
@@ -383,6 +390,18 @@ pub fn i64_type() {
     let value = std::i64::MIN;
     let result7 = call_faas!(faas, MODULE_NAME, FUNC_NAME, json!(value));
     assert_eq!(result7, value + 2);
+
+    // integral floats are accepted only while they are exact
+    let result8 = call_faas!(faas, MODULE_NAME, FUNC_NAME, json!(9007199254740992.0));
+    assert_eq!(result8, json!(9007199254740994i64));
+
+    let result9 = faas.call_with_json(
+        MODULE_NAME,
+        FUNC_NAME,
+        json!(9007199254740994.0),
+        <_>::default(),
+    );
+    assert!(result9.is_err());
 }
 
 #[test]
@@ -427,6 +446,57 @@ pub fn u64_type() {
     assert_eq!(result4, expected_result);
 }
 
+#[test]
+pub fn int64_as_string() {
+    use fluence_faas::FaaSError;
+    use fluence_faas::JsonArgumentErrorKind;
+    use fluence_faas::JsonOptions;
+
+    let mut config = ARG_CONFIG.clone();
+    config.json_int64_as_string = Some(true);
+    let mut faas = FluenceFaaS::with_raw_config(config)
+        .unwrap_or_else(|e| panic!("can't create Fluence FaaS instance: {:?}", e));
+
+    // it isn't representable as f64, while u64 values above i64::MAX can't be passed by IT
+    let value = (1u64 << 60) + 1;
+    let result1 = call_faas!(
+        faas,
+        MODULE_NAME,
+        "u64_type",
+        json!({ "arg": value.to_string() })
+    );
+    assert_eq!(result1, json!((value + 2).to_string()));
+
+    let result2 = call_faas!(faas, MODULE_NAME, "i64_type", json!(1));
+    assert_eq!(result2, json!("3"));
+
+    let result3 = faas
+        .call_with_json_options(
+            MODULE_NAME,
+            "i64_type",
+            json!(1),
+            <_>::default(),
+            JsonOptions::default(),
+        )
+        .unwrap_or_else(|e| panic!("can't invoke i64_type: {:?}", e));
+    assert_eq!(result3, json!(3));
+
+    let result4 = faas.call_with_json(MODULE_NAME, "u32_type", json!(1u64 << 32), <_>::default());
+    match result4 {
+        Err(FaaSError::JsonArgumentsDeserializationError(error)) => {
+            assert_eq!(error.path, "args");
+            assert!(matches!(
+                error.kind,
+                JsonArgumentErrorKind::InvalidValue { .. }
+            ));
+        }
+        result => panic!("expected an out of range error, got {:?}", result),
+    }
+
+    let result5 = faas.call_with_json(MODULE_NAME, "u32_type", json!("1"), <_>::default());
+    assert!(result5.is_err());
+}
+
 #[test]
 pub fn f32_type() {
     const FUNC_NAME: &'static str = "f32_type";
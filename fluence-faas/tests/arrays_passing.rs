@@ -22,7 +22,7 @@ use fluence_faas::IType;
 use once_cell::sync::Lazy;
 use serde_json::json;
 
-use std::rc::Rc;
+use std::sync::Arc;
 
 static ARG_CONFIG: Lazy<fluence_faas::TomlFaaSConfig> = Lazy::new(|| {
     let mut arrays_passing_config =
@@ -51,9 +51,9 @@ pub fn get_interfaces() {
     let byte_type_outputs = vec![IType::Array(Box::new(IType::U8))];
 
     let byte_type_sign = fluence_faas::FaaSFunctionSignature {
-        name: Rc::new(String::from("byte_type")),
-        arguments: Rc::new(byte_type_arguments),
-        outputs: Rc::new(byte_type_outputs),
+        name: Arc::new(String::from("byte_type")),
+        arguments: Arc::new(byte_type_arguments),
+        outputs: Arc::new(byte_type_outputs),
     };
 
     let inner_arrays_1_arguments = vec![fluence_faas::IFunctionArg {
@@ -67,9 +67,9 @@ pub fn get_interfaces() {
     ))))];
 
     let inner_arrays_1_sign = fluence_faas::FaaSFunctionSignature {
-        name: Rc::new(String::from("inner_arrays_1")),
-        arguments: Rc::new(inner_arrays_1_arguments),
-        outputs: Rc::new(inner_arrays_1_outputs),
+        name: Arc::new(String::from("inner_arrays_1")),
+        arguments: Arc::new(inner_arrays_1_arguments),
+        outputs: Arc::new(inner_arrays_1_outputs),
     };
 
     // save it until record will be refactored in the future
@@ -98,9 +98,9 @@ pub fn get_interfaces() {
     let string_type_outputs = vec![IType::Array(Box::new(IType::String))];
 
     let string_type_sign = fluence_faas::FaaSFunctionSignature {
-        name: Rc::new(String::from("string_type")),
-        arguments: Rc::new(string_type_arguments),
-        outputs: Rc::new(string_type_outputs),
+        name: Arc::new(String::from("string_type")),
+        arguments: Arc::new(string_type_arguments),
+        outputs: Arc::new(string_type_outputs),
     };
 
     let i32_type_arguments = vec![fluence_faas::IFunctionArg {
@@ -110,9 +110,9 @@ pub fn get_interfaces() {
     let i32_type_outputs = vec![IType::Array(Box::new(IType::S32))];
 
     let i32_type_sign = fluence_faas::FaaSFunctionSignature {
-        name: Rc::new(String::from("i32_type")),
-        arguments: Rc::new(i32_type_arguments),
-        outputs: Rc::new(i32_type_outputs),
+        name: Arc::new(String::from("i32_type")),
+        arguments: Arc::new(i32_type_arguments),
+        outputs: Arc::new(i32_type_outputs),
     };
 
     let i64_type_arguments = vec![fluence_faas::IFunctionArg {
@@ -123,9 +123,9 @@ pub fn get_interfaces() {
     let i64_type_outputs = vec![IType::Array(Box::new(IType::S64))];
 
     let i64_type_sign = fluence_faas::FaaSFunctionSignature {
-        name: Rc::new(String::from("i64_type")),
-        arguments: Rc::new(i64_type_arguments),
-        outputs: Rc::new(i64_type_outputs),
+        name: Arc::new(String::from("i64_type")),
+        arguments: Arc::new(i64_type_arguments),
+        outputs: Arc::new(i64_type_outputs),
     };
 
     let u32_type_arguments = vec![fluence_faas::IFunctionArg {
@@ -135,9 +135,9 @@ pub fn get_interfaces() {
     let u32_type_outputs = vec![IType::Array(Box::new(IType::U32))];
 
     let u32_type_sign = fluence_faas::FaaSFunctionSignature {
-        name: Rc::new(String::from("u32_type")),
-        arguments: Rc::new(u32_type_arguments),
-        outputs: Rc::new(u32_type_outputs),
+        name: Arc::new(String::from("u32_type")),
+        arguments: Arc::new(u32_type_arguments),
+        outputs: Arc::new(u32_type_outputs),
     };
 
     let u64_type_arguments = vec![fluence_faas::IFunctionArg {
@@ -147,9 +147,9 @@ pub fn get_interfaces() {
     let u64_type_outputs = vec![IType::Array(Box::new(IType::U64))];
 
     let u64_type_sign = fluence_faas::FaaSFunctionSignature {
-        name: Rc::new(String::from("u64_type")),
-        arguments: Rc::new(u64_type_arguments),
-        outputs: Rc::new(u64_type_outputs),
+        name: Arc::new(String::from("u64_type")),
+        arguments: Arc::new(u64_type_arguments),
+        outputs: Arc::new(u64_type_outputs),
     };
 
     let f32_type_arguments = vec![fluence_faas::IFunctionArg {
@@ -159,9 +159,9 @@ pub fn get_interfaces() {
     let f32_type_outputs = vec![IType::Array(Box::new(IType::F32))];
 
     let f32_type_sign = fluence_faas::FaaSFunctionSignature {
-        name: Rc::new(String::from("f32_type")),
-        arguments: Rc::new(f32_type_arguments),
-        outputs: Rc::new(f32_type_outputs),
+        name: Arc::new(String::from("f32_type")),
+        arguments: Arc::new(f32_type_arguments),
+        outputs: Arc::new(f32_type_outputs),
     };
 
     let f64_type_arguments = vec![fluence_faas::IFunctionArg {
@@ -171,18 +171,18 @@ pub fn get_interfaces() {
     let f64_type_outputs = vec![IType::Array(Box::new(IType::F64))];
 
     let f64_type_sign = fluence_faas::FaaSFunctionSignature {
-        name: Rc::new(String::from("f64_type")),
-        arguments: Rc::new(f64_type_arguments),
-        outputs: Rc::new(f64_type_outputs),
+        name: Arc::new(String::from("f64_type")),
+        arguments: Arc::new(f64_type_arguments),
+        outputs: Arc::new(f64_type_outputs),
     };
 
     let empty_type_arguments = vec![];
     let empty_type_outputs = vec![IType::Array(Box::new(IType::String))];
 
     let empty_type_sign = fluence_faas::FaaSFunctionSignature {
-        name: Rc::new(String::from("empty_type")),
-        arguments: Rc::new(empty_type_arguments),
-        outputs: Rc::new(empty_type_outputs),
+        name: Arc::new(String::from("empty_type")),
+        arguments: Arc::new(empty_type_arguments),
+        outputs: Arc::new(empty_type_outputs),
     };
 
     /*
@@ -497,6 +497,87 @@ pub fn byte_type() {
     assert_eq!(result4, right_result);
 }
 
+#[test]
+pub fn byte_type_compact_encodings() {
+    use fluence_faas::ByteArrayEncoding;
+    use fluence_faas::JsonOptions;
+
+    let mut config = ARG_CONFIG.clone();
+    config.json_byte_array_encoding = Some(ByteArrayEncoding::Hex);
+    let mut faas = FluenceFaaS::with_raw_config(config)
+        .unwrap_or_else(|e| panic!("can't create Fluence FaaS instance: {:?}", e));
+
+    let result1 = call_faas!(
+        faas,
+        "arrays_passing_pure",
+        "byte_type",
+        json!({ "arg": "0x1337" })
+    );
+    assert_eq!(result1, json!("0x1337000102"));
+
+    let base64_options = JsonOptions {
+        byte_array_encoding: ByteArrayEncoding::Base64,
+        ..JsonOptions::default()
+    };
+    let result2 = faas
+        .call_with_json_options(
+            "arrays_passing_pure",
+            "byte_type",
+            json!(["Ezc="]),
+            <_>::default(),
+            base64_options,
+        )
+        .unwrap_or_else(|e| panic!("can't invoke byte_type: {:?}", e));
+    assert_eq!(result2, json!("EzcAAQI="));
+
+    let result3 = faas.call_with_json(
+        "arrays_passing_pure",
+        "byte_type",
+        json!({ "arg": "0x13z7" }),
+        <_>::default(),
+    );
+    assert!(result3.is_err());
+
+    // arguments are accepted in any encoding regardless of options
+    let result4 = call_faas!(
+        faas,
+        "arrays_passing_pure",
+        "byte_type",
+        json!({ "arg": "Ezc=" })
+    );
+    assert_eq!(result4, json!("0x1337000102"));
+
+    let result5 = faas
+        .call_with_json_options(
+            "arrays_passing_pure",
+            "byte_type",
+            json!(["0x1337"]),
+            <_>::default(),
+            base64_options,
+        )
+        .unwrap_or_else(|e| panic!("can't invoke byte_type: {:?}", e));
+    assert_eq!(result5, json!("EzcAAQI="));
+
+    let result6 = faas
+        .call_with_json_options(
+            "arrays_passing_pure",
+            "byte_type",
+            json!(["Ezc="]),
+            <_>::default(),
+            JsonOptions::default(),
+        )
+        .unwrap_or_else(|e| panic!("can't invoke byte_type: {:?}", e));
+    assert_eq!(result6, json!([0x13, 0x37, 0, 1, 2]));
+
+    let result7 = faas.call_with_json(
+        "arrays_passing_pure",
+        "byte_type",
+        json!({ "arg": "Ez!=" }),
+        <_>::default(),
+    );
+    assert!(result7.is_err());
+}
+
 #[test]
 pub fn inner_arrays_1_type() {
     let mut faas = FluenceFaaS::with_raw_config(ARG_CONFIG.clone())
@@ -0,0 +1,132 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+mod common;
+
+use common::create_faas;
+use fluence_faas::BinaryEncoding;
+use fluence_faas::FaaSError;
+
+use pretty_assertions::assert_eq;
+
+use std::collections::BTreeMap;
+
+#[test]
+pub fn message_pack_arguments() {
+    let mut faas = create_faas("greeting");
+
+    let arguments = rmpv::Value::Array(vec![rmpv::Value::from("Fluence")]);
+    let mut encoded_arguments = Vec::new();
+    rmpv::encode::write_value(&mut encoded_arguments, &arguments)
+        .expect("MessagePack encoding should succeed");
+
+    let result = faas
+        .call_with_binary(
+            "greeting",
+            "greeting",
+            BinaryEncoding::MessagePack,
+            &encoded_arguments,
+            <_>::default(),
+        )
+        .unwrap_or_else(|e| panic!("can't invoke greeting: {:?}", e));
+
+    let result = rmpv::decode::read_value(&mut &result[..])
+        .expect("result should be a valid MessagePack value");
+    assert_eq!(result, rmpv::Value::from("Hi, Fluence"));
+}
+
+#[test]
+pub fn cbor_arguments() {
+    let mut faas = create_faas("greeting");
+
+    let mut arguments = BTreeMap::new();
+    arguments.insert("name", "Fluence");
+    let encoded_arguments = serde_cbor::to_vec(&arguments).expect("CBOR encoding should succeed");
+
+    let result = faas
+        .call_with_binary(
+            "greeting",
+            "greeting",
+            BinaryEncoding::Cbor,
+            &encoded_arguments,
+            <_>::default(),
+        )
+        .unwrap_or_else(|e| panic!("can't invoke greeting: {:?}", e));
+
+    let result: String = serde_cbor::from_slice(&result).expect("result should be a CBOR string");
+    assert_eq!(result, "Hi, Fluence");
+}
+
+#[test]
+pub fn incompatible_binary_arguments() {
+    let mut faas = create_faas("greeting");
+
+    let encoded_arguments = serde_cbor::to_vec(&42).expect("CBOR encoding should succeed");
+
+    let result = faas.call_with_binary(
+        "greeting",
+        "greeting",
+        BinaryEncoding::Cbor,
+        &encoded_arguments,
+        <_>::default(),
+    );
+
+    assert!(matches!(
+        result,
+        Err(FaaSError::BinaryArgumentsDecodingError(_))
+    ));
+}
+
+#[test]
+pub fn lossy_float_arguments_are_rejected() {
+    let mut config =
+        fluence_faas::TomlFaaSConfig::load("./tests/wasm_tests/arguments_passing/Config.toml")
+            .expect("toml faas config should be created");
+    config.modules_dir = Some(String::from(
+        "./tests/wasm_tests/arguments_passing/artifacts",
+    ));
+    let mut faas = common::create_faas_with_config(config);
+
+    let mut call_f32_type = |argument: serde_cbor::Value| {
+        let encoded_arguments =
+            serde_cbor::to_vec(&argument).expect("CBOR encoding should succeed");
+        faas.call_with_binary(
+            "arguments_passing_pure",
+            "f32_type",
+            BinaryEncoding::Cbor,
+            &encoded_arguments,
+            <_>::default(),
+        )
+    };
+
+    // CBOR floats are decoded as f64, they're passed only if f32 keeps them exactly
+    let result = call_f32_type(serde_cbor::Value::Float(1.5))
+        .unwrap_or_else(|e| panic!("can't invoke f32_type: {:?}", e));
+    let result: f32 = serde_cbor::from_slice(&result).expect("result should be a CBOR float");
+    assert_eq!(result, 3.5);
+
+    let result = call_f32_type(serde_cbor::Value::Float(0.1));
+    assert!(matches!(
+        result,
+        Err(FaaSError::BinaryArgumentsDecodingError(_))
+    ));
+
+    let result = call_f32_type(serde_cbor::Value::Integer(16_777_217));
+    assert!(matches!(
+        result,
+        Err(FaaSError::BinaryArgumentsDecodingError(_))
+    ));
+}
@@ -0,0 +1,138 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+mod common;
+
+use common::create_faas;
+use fluence_faas::CallContext;
+use fluence_faas::CallInterceptor;
+use fluence_faas::FCEError;
+use fluence_faas::FaaSError;
+use fluence_faas::IValue;
+
+use pretty_assertions::assert_eq;
+
+use std::sync::Arc;
+use std::sync::Mutex;
+
+#[derive(Default)]
+struct RecordingInterceptor {
+    events: Mutex<Vec<String>>,
+    rejected_function: Option<&'static str>,
+}
+
+impl CallInterceptor for RecordingInterceptor {
+    fn before_call(&self, context: &CallContext<'_>) -> Result<(), String> {
+        self.events.lock().unwrap().push(format!(
+            "before {}.{} {}",
+            context.module_name, context.function_name, context.call_parameters.particle_id
+        ));
+
+        match self.rejected_function {
+            Some(function_name) if function_name == context.function_name => {
+                Err(String::from("rejected by test"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn after_call(&self, context: &CallContext<'_>, result: Result<&[IValue], &FCEError>) {
+        self.events.lock().unwrap().push(format!(
+            "after {}.{} {}",
+            context.module_name,
+            context.function_name,
+            result.is_ok()
+        ));
+    }
+}
+
+#[test]
+pub fn nested_calls_are_intercepted() {
+    let mut faas = create_faas("records");
+    let interceptor = Arc::new(RecordingInterceptor::default());
+    faas.add_interceptor(interceptor.clone());
+
+    let call_parameters = fluence_faas::CallParameters {
+        particle_id: String::from("particle_id"),
+        ..<_>::default()
+    };
+
+    faas.call_with_ivalues("records_pure", "invoke", &[], call_parameters)
+        .unwrap_or_else(|e| panic!("can't invoke pure: {:?}", e));
+
+    let events = interceptor.events.lock().unwrap().clone();
+    assert_eq!(
+        events,
+        vec![
+            "before records_pure.invoke particle_id",
+            "before records_effector.mutate_struct particle_id",
+            "after records_effector.mutate_struct true",
+            "after records_pure.invoke true",
+        ]
+    );
+}
+
+#[test]
+pub fn call_could_be_rejected() {
+    let mut faas = create_faas("records");
+    let interceptor = Arc::new(RecordingInterceptor {
+        rejected_function: Some("invoke"),
+        ..<_>::default()
+    });
+    faas.add_interceptor(interceptor.clone());
+
+    let result = faas.call_with_ivalues("records_pure", "invoke", &[], <_>::default());
+    assert!(matches!(
+        result,
+        Err(FaaSError::EngineError(FCEError::CallRejected { .. }))
+    ));
+
+    let events = interceptor.events.lock().unwrap().clone();
+    assert_eq!(
+        events,
+        vec![
+            "before records_pure.invoke ",
+            "after records_pure.invoke false"
+        ]
+    );
+}
+
+#[test]
+pub fn after_call_is_invoked_only_if_before_call_was() {
+    let mut faas = create_faas("records");
+    let rejecting = Arc::new(RecordingInterceptor {
+        rejected_function: Some("invoke"),
+        ..<_>::default()
+    });
+    let skipped = Arc::new(RecordingInterceptor::default());
+    faas.add_interceptor(rejecting.clone());
+    faas.add_interceptor(skipped.clone());
+
+    let result = faas.call_with_ivalues("records_pure", "invoke", &[], <_>::default());
+    assert!(result.is_err());
+
+    // the second interceptor doesn't see the call rejected before it
+    let rejecting_events = rejecting.events.lock().unwrap().clone();
+    let skipped_events = skipped.events.lock().unwrap().clone();
+    assert_eq!(
+        rejecting_events,
+        vec![
+            "before records_pure.invoke ",
+            "after records_pure.invoke false"
+        ]
+    );
+    assert!(skipped_events.is_empty());
+}
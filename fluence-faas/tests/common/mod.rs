@@ -0,0 +1,44 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// every test crate uses only a part of these helpers
+#![allow(dead_code)]
+
+use fluence_faas::FluenceFaaS;
+use fluence_faas::TomlFaaSConfig;
+
+/// Loads the config of an example with modules taken from its artifacts.
+pub fn example_config(example_name: &str) -> TomlFaaSConfig {
+    let config_path = format!("../examples/{}/Config.toml", example_name);
+    let config_raw =
+        std::fs::read(&config_path).unwrap_or_else(|_| panic!("{} should presence", config_path));
+
+    let mut config: TomlFaaSConfig =
+        toml::from_slice(&config_raw).expect("config should be well-formed");
+    config.modules_dir = Some(format!("../examples/{}/artifacts", example_name));
+
+    config
+}
+
+/// Creates FaaS with all modules of an example.
+pub fn create_faas(example_name: &str) -> FluenceFaaS {
+    create_faas_with_config(example_config(example_name))
+}
+
+pub fn create_faas_with_config(config: TomlFaaSConfig) -> FluenceFaaS {
+    FluenceFaaS::with_raw_config(config)
+        .unwrap_or_else(|e| panic!("can't create Fluence FaaS instance: {:?}", e))
+}
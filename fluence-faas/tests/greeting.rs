@@ -20,7 +20,7 @@ use fluence_faas::IValue;
 
 use pretty_assertions::assert_eq;
 
-use std::rc::Rc;
+use std::sync::Arc;
 
 #[test]
 pub fn greeting() {
@@ -81,9 +81,9 @@ pub fn get_interfaces() {
     let output_types = vec![fluence_faas::IType::String];
 
     let greeting_sign = fluence_faas::FaaSFunctionSignature {
-        name: Rc::new(String::from("greeting")),
-        arguments: Rc::new(arguments),
-        outputs: Rc::new(output_types),
+        name: Arc::new(String::from("greeting")),
+        arguments: Arc::new(arguments),
+        outputs: Arc::new(output_types),
     };
 
     let record_types = std::collections::HashMap::new();
@@ -0,0 +1,357 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+mod common;
+
+use fluence_faas::Divergence;
+use fluence_faas::FaaSConfig;
+use fluence_faas::FaaSModuleConfig;
+use fluence_faas::FluenceFaaS;
+use fluence_faas::HostImportDescriptor;
+use fluence_faas::IType;
+use fluence_faas::IValue;
+use fluence_faas::JournalEntry;
+use fluence_faas::JournalResult;
+
+use parity_wasm::builder;
+use parity_wasm::elements::Instruction;
+use parity_wasm::elements::Instructions;
+use parity_wasm::elements::ValueType;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+/// IT of a module with one export invoke(x: s32) -> s32.
+const HOST_IMPORT_WIT: &str = r#"
+(@interface type (func (param $x: s32) (result s32)))   ;; 0
+(@interface type (func (param $x: s32) (result s32)))   ;; 1
+
+(@interface func (type 0)
+  arg.get 0
+  i32.from_s32
+  call-core 0
+  s32.from_i32)
+
+(@interface export "invoke" (func 1))
+
+(@interface implement (func 1) (func 0))
+"#;
+
+/// Builds a module whose invoke(x) passes x to the host import host.record and returns x.
+fn host_import_wasm() -> Vec<u8> {
+    use Instruction::*;
+
+    let mut module = builder::module()
+        .import()
+        .module("host")
+        .field("record")
+        .external()
+        .func(0)
+        .build()
+        // type of the import
+        .functions()
+        .signature()
+        .with_param(ValueType::I32)
+        .build()
+        .bind()
+        .function()
+        .signature()
+        .with_param(ValueType::I32)
+        .with_return_type(Some(ValueType::I32))
+        .build()
+        .body()
+        .with_instructions(Instructions::new(vec![
+            GetLocal(0),
+            Call(0),
+            GetLocal(0),
+            End,
+        ]))
+        .build()
+        .build()
+        // host imports allocate memory for results, even if there are no results
+        .function()
+        .signature()
+        .with_param(ValueType::I32)
+        .with_return_type(Some(ValueType::I32))
+        .build()
+        .body()
+        .with_instructions(Instructions::new(vec![I32Const(0), End]))
+        .build()
+        .build()
+        .memory()
+        .with_min(1)
+        .build()
+        .export()
+        .field("invoke")
+        .internal()
+        .func(1)
+        .build()
+        .export()
+        .field("allocate")
+        .internal()
+        .func(2)
+        .build()
+        .export()
+        .field("memory")
+        .internal()
+        .memory(0)
+        .build()
+        .build();
+
+    let buffer = wasmer_wit::decoders::wat::Buffer::new(HOST_IMPORT_WIT)
+        .unwrap_or_else(|e| panic!("IT of the module should be valid: {:?}", e));
+    let interfaces = wasmer_wit::decoders::wat::parse(&buffer)
+        .unwrap_or_else(|e| panic!("IT of the module should be valid: {:?}", e));
+    let mut wit_bytes = vec![];
+    wasmer_wit::ToBytes::to_bytes(&interfaces, &mut wit_bytes)
+        .expect("IT of the module should be serializable");
+    module.set_custom_section("interface-types", wit_bytes);
+
+    parity_wasm::serialize(module).expect("module should be serializable")
+}
+
+/// Creates FaaS with the host import module, live_calls counts calls of the real host import.
+fn create_host_import_faas(
+    journal_path: Option<&PathBuf>,
+    live_calls: Arc<AtomicUsize>,
+) -> FluenceFaaS {
+    let record = HostImportDescriptor {
+        host_exported_func: Box::new(move |_, _| {
+            live_calls.fetch_add(1, Ordering::SeqCst);
+            None
+        }),
+        argument_types: vec![IType::S32],
+        output_type: None,
+        error_handler: None,
+    };
+
+    let mut module_config = FaaSModuleConfig::default();
+    module_config
+        .host_imports
+        .insert(String::from("record"), record);
+    let config = FaaSConfig {
+        modules_config: vec![(String::from("host_import"), module_config)],
+        journal_path: journal_path.cloned(),
+        ..<_>::default()
+    };
+
+    let mut modules = HashMap::new();
+    modules.insert(String::from("host_import"), host_import_wasm());
+    FluenceFaaS::with_modules(modules, config)
+        .unwrap_or_else(|e| panic!("can't create Fluence FaaS instance: {:?}", e))
+}
+
+fn call_invoke(faas: &mut FluenceFaaS, x: i32) {
+    let result = faas
+        .call_with_ivalues("host_import", "invoke", &[IValue::S32(x)], <_>::default())
+        .unwrap_or_else(|e| panic!("can't invoke host_import: {:?}", e));
+    assert_eq!(result, vec![IValue::S32(x)]);
+}
+
+fn record_host_import_journal(journal_name: &str) -> Vec<JournalEntry> {
+    let journal_path = std::env::temp_dir().join(journal_name);
+    let live_calls = Arc::new(AtomicUsize::new(0));
+    let mut faas = create_host_import_faas(Some(&journal_path), live_calls.clone());
+
+    call_invoke(&mut faas, 1);
+    call_invoke(&mut faas, 2);
+    faas.stop_journal();
+    assert_eq!(live_calls.load(Ordering::SeqCst), 2);
+
+    let journal = std::fs::read_to_string(&journal_path).expect("journal should be readable");
+    journal
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("journal entry should be well-formed"))
+        .collect()
+}
+
+/// Replays entries with a fresh FaaS, returns the report and count of live host import calls.
+fn replay_host_import_journal(
+    journal_name: &str,
+    entries: &[JournalEntry],
+) -> (fluence_faas::ReplayReport, usize) {
+    let journal_path = std::env::temp_dir().join(journal_name);
+    let journal = entries
+        .iter()
+        .map(|entry| serde_json::to_string(entry).unwrap())
+        .collect::<Vec<_>>()
+        .join("\n");
+    std::fs::write(&journal_path, journal).expect("journal should be writable");
+
+    let live_calls = Arc::new(AtomicUsize::new(0));
+    let mut faas = create_host_import_faas(None, live_calls.clone());
+    let report = faas
+        .replay_journal(&journal_path)
+        .unwrap_or_else(|e| panic!("can't replay journal: {:?}", e));
+
+    (report, live_calls.load(Ordering::SeqCst))
+}
+
+fn create_greeting_faas(journal_path: Option<&PathBuf>) -> FluenceFaaS {
+    let mut config = common::example_config("greeting");
+    config.journal_path = journal_path.map(|path| path.to_string_lossy().to_string());
+
+    common::create_faas_with_config(config)
+}
+
+fn record_greeting_journal(journal_name: &str) -> PathBuf {
+    let journal_path = std::env::temp_dir().join(journal_name);
+    let mut faas = create_greeting_faas(Some(&journal_path));
+
+    faas.call_with_json("greeting", "greeting", json!(["Fluence"]), <_>::default())
+        .unwrap_or_else(|e| panic!("can't invoke greeting: {:?}", e));
+    faas.call_with_json(
+        "greeting",
+        "greeting",
+        json!({"name": "FCE"}),
+        <_>::default(),
+    )
+    .unwrap_or_else(|e| panic!("can't invoke greeting: {:?}", e));
+    faas.stop_journal();
+
+    journal_path
+}
+
+#[test]
+pub fn journal_is_replayed() {
+    let journal_path = record_greeting_journal("journal_is_replayed.jsonl");
+
+    let mut faas = create_greeting_faas(None);
+    let report = faas
+        .replay_journal(&journal_path)
+        .unwrap_or_else(|e| panic!("can't replay journal: {:?}", e));
+
+    assert_eq!(report.replayed_calls, 2);
+    assert!(report.is_consistent());
+}
+
+#[test]
+pub fn divergence_is_reported() {
+    let journal_path = record_greeting_journal("divergence_is_reported.jsonl");
+
+    // tamper the result of the second call
+    let journal = std::fs::read_to_string(&journal_path).expect("journal should be readable");
+    let mut entries = journal
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("journal entry should be well-formed"))
+        .collect::<Vec<JournalEntry>>();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[1].result, JournalResult::Json(json!("Hi, FCE")));
+    entries[1].result = JournalResult::Json(json!("Hi, Fluence"));
+
+    let journal = entries
+        .iter()
+        .map(|entry| serde_json::to_string(entry).unwrap())
+        .collect::<Vec<_>>()
+        .join("\n");
+    std::fs::write(&journal_path, journal).expect("journal should be writable");
+
+    let mut faas = create_greeting_faas(None);
+    let report = faas
+        .replay_journal(&journal_path)
+        .unwrap_or_else(|e| panic!("can't replay journal: {:?}", e));
+
+    assert_eq!(report.replayed_calls, 2);
+    assert_eq!(report.divergences.len(), 1);
+    assert_eq!(report.divergences[0].entry_id, 1);
+    assert_eq!(
+        report.divergences[0].divergence,
+        Divergence::Result {
+            expected: JournalResult::Json(json!("Hi, Fluence")),
+            actual: JournalResult::Json(json!("Hi, FCE")),
+        }
+    );
+}
+
+#[test]
+pub fn host_responses_are_replayed() {
+    let entries = record_host_import_journal("host_responses_are_replayed.jsonl");
+    assert_eq!(entries[0].host_responses.len(), 1);
+    assert_eq!(entries[1].host_responses.len(), 1);
+
+    let (report, live_calls) =
+        replay_host_import_journal("host_responses_are_replayed.jsonl", &entries);
+
+    assert_eq!(report.replayed_calls, 2);
+    assert!(report.is_consistent(), "{:?}", report.divergences);
+    assert_eq!(live_calls, 0);
+}
+
+#[test]
+pub fn unrecorded_host_import_call_diverges() {
+    let mut entries = record_host_import_journal("unrecorded_host_import_call.jsonl");
+    entries[1].host_responses.clear();
+
+    let (report, live_calls) =
+        replay_host_import_journal("unrecorded_host_import_call.jsonl", &entries);
+
+    assert_eq!(live_calls, 0);
+    // the module gets no value from the diverged import instead of a trap,
+    // so the call itself completes with the recorded result
+    assert_eq!(report.replayed_calls, 2);
+    assert_eq!(report.divergences.len(), 1);
+    assert_eq!(report.divergences[0].entry_id, 1);
+    assert_eq!(
+        report.divergences[0].divergence,
+        Divergence::UnrecordedHostImportCall {
+            module_name: String::from("host_import"),
+            import_name: String::from("record"),
+        }
+    );
+}
+
+#[test]
+pub fn unexpected_host_import_call_diverges() {
+    let mut entries = record_host_import_journal("unexpected_host_import_call.jsonl");
+    entries[0].host_responses[0].import_name = String::from("store");
+    let recorded_response = entries[0].host_responses[0].clone();
+
+    let (report, live_calls) =
+        replay_host_import_journal("unexpected_host_import_call.jsonl", &entries);
+
+    assert_eq!(live_calls, 0);
+
+    let divergences = report
+        .divergences
+        .iter()
+        .filter(|divergence| divergence.entry_id == 0)
+        .map(|divergence| divergence.divergence.clone())
+        .collect::<Vec<_>>();
+    // the mismatched response isn't consumed, so it's reported as missing
+    assert_eq!(
+        divergences,
+        vec![
+            Divergence::UnexpectedHostImportCall {
+                module_name: String::from("host_import"),
+                import_name: String::from("record"),
+                expected_module_name: String::from("host_import"),
+                expected_import_name: String::from("store"),
+            },
+            Divergence::MissingHostImportCalls(vec![recorded_response]),
+        ]
+    );
+
+    // the next entry is replayed with its own responses
+    assert!(report
+        .divergences
+        .iter()
+        .all(|divergence| divergence.entry_id == 0));
+}
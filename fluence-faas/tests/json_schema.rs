@@ -0,0 +1,136 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+mod common;
+
+use fluence_faas::FluenceFaaS;
+
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+fn create_faas(example_name: &str) -> FluenceFaaS {
+    let mut config = common::example_config(example_name);
+    config.json_int64_as_string = Some(true);
+
+    common::create_faas_with_config(config)
+}
+
+#[test]
+pub fn greeting_schema() {
+    let faas = create_faas("greeting");
+
+    let schema = faas
+        .json_schema("greeting")
+        .unwrap_or_else(|e| panic!("can't build schema: {:?}", e));
+
+    assert_eq!(schema["title"], json!("greeting"));
+    assert_eq!(
+        schema["properties"]["greeting"],
+        json!({ "$ref": "#/definitions/functions/greeting" })
+    );
+    assert_eq!(
+        schema["definitions"]["functions"]["greeting"]["properties"],
+        json!({
+            "arguments": {
+                "oneOf": [
+                    {
+                        "type": "object",
+                        "properties": { "name": { "type": "string" } },
+                        "required": ["name"],
+                        "additionalProperties": false,
+                    },
+                    {
+                        "type": "array",
+                        "items": [{ "type": "string" }],
+                        "minItems": 1,
+                        "maxItems": 1,
+                    },
+                    {
+                        "allOf": [
+                            { "type": "string" },
+                            { "not": { "type": ["array", "object", "null"] } },
+                        ]
+                    },
+                ]
+            },
+            "result": { "type": "string" },
+        })
+    );
+}
+
+#[test]
+pub fn records_schema() {
+    let faas = create_faas("records");
+
+    let schema = faas
+        .json_schema("records_effector")
+        .unwrap_or_else(|e| panic!("can't build schema: {:?}", e));
+
+    let function = &schema["definitions"]["functions"]["mutate_struct"]["properties"];
+    let arguments = &function["arguments"]["oneOf"];
+    assert_eq!(
+        arguments[0]["properties"]["test_record"],
+        json!({ "$ref": "#/definitions/arguments/TestRecord" })
+    );
+    assert_eq!(
+        function["result"],
+        json!({ "$ref": "#/definitions/results/TestRecord" })
+    );
+
+    let record_fields = &schema["definitions"]["results"]["TestRecord"]["properties"];
+    assert_eq!(
+        record_fields["field_8"],
+        json!({ "type": "string", "pattern": "^-?[0-9]+$" })
+    );
+    assert_eq!(
+        record_fields["field_5"],
+        json!({ "type": "integer", "minimum": 0, "maximum": 255 })
+    );
+
+    let result = faas.json_schema("missing_module");
+    assert!(result.is_err());
+}
+
+#[test]
+pub fn byte_array_schema() {
+    let mut config =
+        fluence_faas::TomlFaaSConfig::load("./tests/wasm_tests/arrays_passing/Config.toml")
+            .expect("toml faas config should be created");
+    config.modules_dir = Some(String::from("./tests/wasm_tests/arrays_passing/artifacts"));
+    config.json_byte_array_encoding = Some(fluence_faas::ByteArrayEncoding::Hex);
+    let faas = common::create_faas_with_config(config);
+
+    let schema = faas
+        .json_schema("arrays_passing_pure")
+        .unwrap_or_else(|e| panic!("can't build schema: {:?}", e));
+
+    // byte arrays are accepted in any encoding, but returned only in the chosen one
+    let function = &schema["definitions"]["functions"]["byte_type"]["properties"];
+    assert_eq!(
+        function["arguments"]["oneOf"][0]["properties"]["arg"],
+        json!({
+            "anyOf": [
+                { "type": "array", "items": { "type": "integer", "minimum": 0, "maximum": 255 } },
+                { "type": "string", "contentEncoding": "base64" },
+                { "type": "string", "pattern": "^0x([0-9a-fA-F]{2})*$" },
+            ]
+        })
+    );
+    assert_eq!(
+        function["result"],
+        json!({ "type": "string", "pattern": "^0x([0-9a-fA-F]{2})*$" })
+    );
+}
@@ -0,0 +1,141 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+mod common;
+
+use fluence_faas::CallContext;
+use fluence_faas::CallInterceptor;
+use fluence_faas::FCEError;
+use fluence_faas::FaaSError;
+use fluence_faas::FluenceFaaS;
+use fluence_faas::IValue;
+use fluence_faas::MemoizationStats;
+
+use pretty_assertions::assert_eq;
+
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+/// Records results of calls and rejects them while reject is set.
+#[derive(Default)]
+struct RecordingInterceptor {
+    results: Mutex<Vec<Result<Vec<IValue>, String>>>,
+    reject: AtomicBool,
+}
+
+impl CallInterceptor for RecordingInterceptor {
+    fn before_call(&self, _context: &CallContext<'_>) -> Result<(), String> {
+        if self.reject.load(Ordering::SeqCst) {
+            return Err(String::from("rejected by test"));
+        }
+
+        Ok(())
+    }
+
+    fn after_call(&self, _context: &CallContext<'_>, result: Result<&[IValue], &FCEError>) {
+        let result = result.map(<[IValue]>::to_vec).map_err(|e| e.to_string());
+        self.results.lock().unwrap().push(result);
+    }
+}
+
+fn create_pure_faas(example_name: &str, cache_size: usize) -> FluenceFaaS {
+    let mut config = common::example_config(example_name);
+    config.memoization_cache_size = Some(cache_size);
+    for module in config.module.iter_mut() {
+        module.config.pure = Some(true);
+    }
+
+    common::create_faas_with_config(config)
+}
+
+fn call_greeting(faas: &mut FluenceFaaS, name: &str) -> Vec<IValue> {
+    faas.call_with_ivalues(
+        "greeting",
+        "greeting",
+        &[IValue::String(name.to_string())],
+        <_>::default(),
+    )
+    .unwrap_or_else(|e| panic!("can't invoke greeting: {:?}", e))
+}
+
+#[test]
+pub fn pure_calls_are_memoized() {
+    let mut faas = create_pure_faas("greeting", 1);
+
+    let first_result = call_greeting(&mut faas, "Fluence");
+    let second_result = call_greeting(&mut faas, "Fluence");
+    call_greeting(&mut faas, "FCE");
+
+    assert_eq!(first_result, second_result);
+    assert_eq!(
+        faas.memoization_stats(),
+        MemoizationStats {
+            hits: 1,
+            misses: 2,
+            evictions: 1,
+            bypassed: 0,
+            cached_results: 1,
+        }
+    );
+}
+
+#[test]
+pub fn modules_with_host_imports_are_bypassed() {
+    let mut faas = create_pure_faas("call_parameters", 16);
+
+    for _ in 0..2 {
+        faas.call_with_ivalues("call_parameters", "call_parameters", &[], <_>::default())
+            .unwrap_or_else(|e| panic!("can't invoke call_parameters: {:?}", e));
+    }
+
+    assert_eq!(
+        faas.memoization_stats(),
+        MemoizationStats {
+            bypassed: 2,
+            ..<_>::default()
+        }
+    );
+}
+
+#[test]
+pub fn memoized_calls_are_intercepted() {
+    let mut faas = create_pure_faas("greeting", 16);
+    let interceptor = Arc::new(RecordingInterceptor::default());
+    faas.add_interceptor(interceptor.clone());
+
+    let result = call_greeting(&mut faas, "Fluence");
+    call_greeting(&mut faas, "Fluence");
+    assert_eq!(faas.memoization_stats().hits, 1);
+    assert_eq!(
+        *interceptor.results.lock().unwrap(),
+        vec![Ok(result.clone()), Ok(result)]
+    );
+
+    interceptor.reject.store(true, Ordering::SeqCst);
+    let rejected = faas.call_with_ivalues(
+        "greeting",
+        "greeting",
+        &[IValue::String(String::from("Fluence"))],
+        <_>::default(),
+    );
+    assert!(matches!(
+        rejected,
+        Err(FaaSError::EngineError(FCEError::CallRejected { .. }))
+    ));
+    assert_eq!(faas.memoization_stats().hits, 2);
+}
@@ -0,0 +1,70 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+mod common;
+
+use common::create_faas;
+
+use serde_json::json;
+
+#[test]
+pub fn calls_and_errors_are_counted() {
+    let mut faas = create_faas("greeting");
+
+    for _ in 0..3 {
+        faas.call_with_json("greeting", "greeting", json!(["Fluence"]), <_>::default())
+            .unwrap_or_else(|e| panic!("can't invoke greeting: {:?}", e));
+    }
+    let result = faas.call_with_json("greeting", "greeting", json!([1, 2]), <_>::default());
+    assert!(result.is_err());
+
+    let metrics = faas.prometheus_metrics();
+
+    assert!(metrics.contains("# TYPE fce_function_calls_total counter"));
+    assert!(
+        metrics.contains(r#"fce_function_calls_total{module="greeting",function="greeting"} 3"#)
+    );
+    assert!(metrics.contains(
+        r#"fce_function_errors_total{module="greeting",function="greeting",kind="JsonArgumentsDeserializationError"} 1"#
+    ));
+    assert!(metrics.contains(
+        r#"fce_function_call_duration_seconds_bucket{module="greeting",function="greeting",le="+Inf"} 3"#
+    ));
+    assert!(metrics.contains(
+        r#"fce_function_call_duration_seconds_count{module="greeting",function="greeting"} 3"#
+    ));
+    assert!(metrics.contains(r#"fce_module_memory_pages{module="greeting"} "#));
+    assert!(metrics.contains(r#"fce_host_imports_lifted_bytes_total{module="greeting"} 0"#));
+}
+
+#[test]
+pub fn errors_of_missing_functions_are_counted() {
+    let mut faas = create_faas("greeting");
+
+    let result = faas.call_with_ivalues("greeting", "missing", &[], <_>::default());
+    assert!(result.is_err());
+    let result = faas.call_with_ivalues("missing", "greeting", &[], <_>::default());
+    assert!(result.is_err());
+
+    let metrics = faas.prometheus_metrics();
+
+    assert!(metrics.contains(
+        r#"fce_function_errors_total{module="greeting",function="missing",kind="EngineError"} 1"#
+    ));
+    assert!(metrics.contains(
+        r#"fce_function_errors_total{module="missing",function="greeting",kind="EngineError"} 1"#
+    ));
+}
@@ -0,0 +1,150 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use fluence_faas::FaaSError;
+use fluence_faas::FluenceFaaS;
+use fluence_faas::IValue;
+use fluence_faas::ModuleStore;
+use fluence_faas::TomlFaaSConfig;
+use fluence_faas::TomlFaaSModuleConfig;
+use fluence_faas::TomlFaaSNamedModuleConfig;
+
+use std::path::PathBuf;
+
+fn store_dir(test_name: &str) -> PathBuf {
+    let store_dir = std::env::temp_dir().join(format!("module_store_{}", test_name));
+    let _ = std::fs::remove_dir_all(&store_dir);
+
+    store_dir
+}
+
+fn greeting_wasm() -> Vec<u8> {
+    std::fs::read("../examples/greeting/artifacts/greeting.wasm")
+        .expect("greeting.wasm should presence")
+}
+
+#[test]
+pub fn modules_are_deduplicated() {
+    let store = ModuleStore::new(store_dir("modules_are_deduplicated"));
+    let wasm = greeting_wasm();
+
+    let config = TomlFaaSModuleConfig {
+        mem_pages_count: Some(1),
+        ..<_>::default()
+    };
+    let hash = store
+        .put(&wasm, &<_>::default())
+        .unwrap_or_else(|e| panic!("can't put module: {:?}", e));
+    let same_hash = store
+        .put(&wasm, &config)
+        .unwrap_or_else(|e| panic!("can't put module: {:?}", e));
+    assert_eq!(hash, same_hash);
+    assert_eq!(hash, ModuleStore::hash(&wasm));
+
+    let hashes = store
+        .list()
+        .unwrap_or_else(|e| panic!("can't list modules: {:?}", e));
+    assert_eq!(hashes, vec![hash.clone()]);
+
+    let stored_module = store
+        .load(&hash)
+        .unwrap_or_else(|e| panic!("can't load module: {:?}", e));
+    assert_eq!(stored_module.wasm, wasm);
+    assert_eq!(stored_module.config.mem_pages_count, Some(1));
+}
+
+#[test]
+pub fn corrupted_module() {
+    let store_dir = store_dir("corrupted_module");
+    let store = ModuleStore::new(&store_dir);
+    let mut wasm = greeting_wasm();
+
+    let hash = store
+        .put(&wasm, &<_>::default())
+        .unwrap_or_else(|e| panic!("can't put module: {:?}", e));
+
+    let last_byte = wasm.len() - 1;
+    wasm[last_byte] ^= 0xFF;
+    std::fs::write(store_dir.join(format!("{}.wasm", hash)), &wasm)
+        .expect("module should be rewritten");
+
+    let result = store.load(&hash);
+    assert!(matches!(
+        result,
+        Err(FaaSError::ModuleIntegrityError { .. })
+    ));
+
+    let result = store.load("../greeting");
+    assert!(matches!(result, Err(FaaSError::InvalidModuleHash(_))));
+
+    // putting the module again restores the corrupted file
+    wasm[last_byte] ^= 0xFF;
+    store
+        .put(&wasm, &<_>::default())
+        .unwrap_or_else(|e| panic!("can't put module again: {:?}", e));
+    let stored_module = store
+        .load(&hash)
+        .unwrap_or_else(|e| panic!("can't load restored module: {:?}", e));
+    assert_eq!(stored_module.wasm, wasm);
+
+    let mut entries = std::fs::read_dir(&store_dir)
+        .expect("store dir should be readable")
+        .map(|entry| {
+            let entry = entry.expect("entry should be readable");
+            entry.file_name().to_string_lossy().to_string()
+        })
+        .collect::<Vec<_>>();
+    entries.sort();
+    assert_eq!(
+        entries,
+        vec![format!("{}.json", hash), format!("{}.wasm", hash)]
+    );
+}
+
+#[test]
+pub fn load_by_hash() {
+    let store_dir = store_dir("load_by_hash");
+    let hash = ModuleStore::new(&store_dir)
+        .put(&greeting_wasm(), &<_>::default())
+        .unwrap_or_else(|e| panic!("can't put module: {:?}", e));
+
+    let mut module = TomlFaaSNamedModuleConfig::new("greeting_v1");
+    module.hash = Some(hash);
+    let config = TomlFaaSConfig {
+        module_store_dir: Some(store_dir.to_string_lossy().to_string()),
+        module: vec![module.clone()],
+        ..<_>::default()
+    };
+
+    let mut faas = FluenceFaaS::with_raw_config(config)
+        .unwrap_or_else(|e| panic!("can't create Fluence FaaS instance: {:?}", e));
+    let result = faas
+        .call_with_ivalues(
+            "greeting_v1",
+            "greeting",
+            &[IValue::String(String::from("Fluence"))],
+            <_>::default(),
+        )
+        .unwrap_or_else(|e| panic!("can't invoke greeting: {:?}", e));
+    assert_eq!(result, vec![IValue::String(String::from("Hi, Fluence"))]);
+
+    let config = TomlFaaSConfig {
+        module: vec![module],
+        ..<_>::default()
+    };
+    let result = FluenceFaaS::with_raw_config(config);
+    assert!(matches!(result, Err(FaaSError::ConfigParseError(_))));
+}
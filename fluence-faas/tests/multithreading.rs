@@ -0,0 +1,117 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+mod common;
+
+use common::create_faas;
+use fluence_faas::FluenceFaaS;
+use fluence_faas::IValue;
+
+use pretty_assertions::assert_eq;
+
+use std::sync::Arc;
+use std::sync::Mutex;
+
+fn assert_send<T: Send>() {}
+
+#[test]
+pub fn faas_is_send() {
+    assert_send::<FluenceFaaS>();
+    assert_send::<fluence_faas::FaaSFunctionSignature>();
+    assert_send::<fluence_faas::RecordTypes>();
+}
+
+#[tokio::test(threaded_scheduler)]
+pub async fn move_faas_between_tasks() {
+    let mut faas = create_faas("greeting");
+
+    let result = tokio::spawn(async move {
+        faas.call_with_ivalues(
+            "greeting",
+            "greeting",
+            &[IValue::String(String::from("Fluence"))],
+            <_>::default(),
+        )
+    })
+    .await
+    .expect("task shouldn't panic")
+    .unwrap_or_else(|e| panic!("can't invoke greeting: {:?}", e));
+
+    assert_eq!(result, vec![IValue::String(String::from("Hi, Fluence"))]);
+}
+
+#[tokio::test(threaded_scheduler)]
+pub async fn share_faas_between_tasks() {
+    let faas = Arc::new(Mutex::new(create_faas("greeting")));
+
+    let tasks = (0..16)
+        .map(|task_id| {
+            let faas = faas.clone();
+            tokio::spawn(async move {
+                let name = format!("Fluence {}", task_id);
+                let result = faas
+                    .lock()
+                    .unwrap()
+                    .call_with_ivalues(
+                        "greeting",
+                        "greeting",
+                        &[IValue::String(name.clone())],
+                        <_>::default(),
+                    )
+                    .unwrap_or_else(|e| panic!("can't invoke greeting: {:?}", e));
+
+                (name, result)
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for task in tasks {
+        let (name, result) = task.await.expect("task shouldn't panic");
+        assert_eq!(result, vec![IValue::String(format!("Hi, {}", name))]);
+    }
+}
+
+#[test]
+pub fn call_faas_from_other_thread() {
+    let mut faas = create_faas("greeting");
+
+    let handle = std::thread::spawn(move || {
+        let result = faas
+            .call_with_json(
+                "greeting",
+                "greeting",
+                serde_json::json!(["Fluence"]),
+                <_>::default(),
+            )
+            .unwrap_or_else(|e| panic!("can't invoke greeting: {:?}", e));
+
+        (faas, result)
+    });
+
+    let (mut faas, result) = handle.join().expect("thread shouldn't panic");
+    assert_eq!(result, serde_json::json!("Hi, Fluence"));
+
+    // FaaS should stay usable after it has been moved back
+    let result = faas
+        .call_with_ivalues(
+            "greeting",
+            "greeting",
+            &[IValue::String(String::from(""))],
+            <_>::default(),
+        )
+        .unwrap_or_else(|e| panic!("can't invoke greeting: {:?}", e));
+    assert_eq!(result, vec![IValue::String(String::from("Hi, "))]);
+}
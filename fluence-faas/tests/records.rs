@@ -210,3 +210,67 @@ fn inner_records() {
 
     assert_eq!(result, right_result);
 }
+
+#[test]
+fn inner_records_argument_errors() {
+    use fluence_faas::FaaSError;
+    use fluence_faas::JsonArgumentError;
+    use fluence_faas::JsonArgumentErrorKind;
+    use fluence_faas::JsonKind;
+
+    let inner_records_config_raw = std::fs::read("./tests/wasm_tests/inner_records/Config.toml")
+        .expect("./tests/wasm_tests/inner_records/Config.toml should presence");
+
+    let mut inner_records_config: fluence_faas::TomlFaaSConfig =
+        toml::from_slice(&inner_records_config_raw)
+            .expect("argument passing test config should be well-formed");
+
+    inner_records_config.modules_dir =
+        Some(String::from("./tests/wasm_tests/inner_records/artifacts"));
+
+    let mut faas = FluenceFaaS::with_raw_config(inner_records_config)
+        .unwrap_or_else(|e| panic!("can't create Fluence FaaS instance: {:?}", e));
+
+    let mut call = |arguments| match faas.call_with_json(
+        "inner_records_pure",
+        "test_record",
+        arguments,
+        <_>::default(),
+    ) {
+        Err(FaaSError::JsonArgumentsDeserializationError(error)) => error,
+        result => panic!("expected an arguments error, got {:?}", result),
+    };
+
+    let error = call(json!({
+        "test_record": {
+            "test_record_0": { "field_0": 0 },
+            "test_record_1": {
+                "field_0": 1,
+                "field_1": "",
+                "field_2": [1, "2"],
+                "test_record_0": { "field_0": 1 }
+            }
+        }
+    }));
+    assert_eq!(
+        error,
+        JsonArgumentError {
+            path: String::from("args.test_record.test_record_1.field_2[1]"),
+            kind: JsonArgumentErrorKind::TypeMismatch {
+                expected: String::from("U8"),
+                received: JsonKind::String,
+            },
+        }
+    );
+
+    let error = call(json!([{ "test_record_0": { "field_0": 0 } }]));
+    assert_eq!(
+        error,
+        JsonArgumentError {
+            path: String::from("args[0].test_record_1"),
+            kind: JsonArgumentErrorKind::MissingValue {
+                expected: String::from("TestRecord1"),
+            },
+        }
+    );
+}
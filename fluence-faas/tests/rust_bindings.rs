@@ -0,0 +1,188 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+mod common;
+
+// generated bindings are compiled as modules of this test, so they are checked by rustc
+#[rustfmt::skip]
+#[path = "rust_bindings/greeting.rs"]
+mod greeting_bindings;
+
+#[rustfmt::skip]
+#[path = "rust_bindings/records_effector.rs"]
+mod records_effector_bindings;
+
+#[rustfmt::skip]
+#[path = "rust_bindings/reserved_names.rs"]
+mod reserved_names_bindings;
+
+use common::create_faas;
+use fluence_faas::FaaSFunctionSignature;
+use fluence_faas::FaaSModuleInterface;
+use fluence_faas::IFunctionArg;
+use fluence_faas::IRecordType;
+use fluence_faas::IType;
+use fluence_faas::RecordTypes;
+
+use pretty_assertions::assert_eq;
+use wasmer_wit::IRecordFieldType;
+
+use std::path::Path;
+use std::sync::Arc;
+
+/// Compares bindings with the compiled ones, UPDATE_BINDINGS env rewrites the compiled ones.
+fn check_bindings(file_name: &str, bindings: &str) {
+    let path = Path::new("tests/rust_bindings").join(file_name);
+    if std::env::var_os("UPDATE_BINDINGS").is_some() {
+        std::fs::write(&path, bindings)
+            .unwrap_or_else(|e| panic!("can't write bindings to {:?}: {}", path, e));
+        return;
+    }
+
+    let compiled_bindings = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("can't read bindings from {:?}: {}", path, e));
+    assert_eq!(
+        bindings, compiled_bindings,
+        "bindings in {:?} are outdated, rerun the test with UPDATE_BINDINGS=1",
+        path
+    );
+}
+
+#[test]
+pub fn greeting_bindings() {
+    use greeting_bindings::GreetingClient;
+
+    let mut faas = create_faas("greeting");
+
+    let bindings = faas
+        .rust_bindings("greeting")
+        .unwrap_or_else(|e| panic!("can't generate bindings: {:?}", e));
+    check_bindings("greeting.rs", &bindings);
+
+    let result = GreetingClient::new(&mut faas)
+        .greeting(String::from("Fluence"))
+        .unwrap_or_else(|e| panic!("can't invoke greeting: {:?}", e));
+    assert_eq!(result, "Hi, Fluence");
+}
+
+#[test]
+pub fn records_bindings() {
+    use records_effector_bindings::RecordsEffectorClient;
+    use records_effector_bindings::TestRecord;
+
+    let mut faas = create_faas("records");
+
+    let bindings = faas
+        .rust_bindings("records_effector")
+        .unwrap_or_else(|e| panic!("can't generate bindings: {:?}", e));
+    check_bindings("records_effector.rs", &bindings);
+
+    let record = TestRecord {
+        field_0: 0,
+        field_1: 0,
+        field_2: 0,
+        field_3: 0,
+        field_4: 0,
+        field_5: 0,
+        field_6: 0,
+        field_7: 0,
+        field_8: 0,
+        field_9: 0.0,
+        field_10: 0.0,
+        field_11: String::new(),
+        field_12: vec![],
+    };
+    let result = RecordsEffectorClient::new(&mut faas)
+        .mutate_struct(record)
+        .unwrap_or_else(|e| panic!("can't invoke mutate_struct: {:?}", e));
+    assert_eq!(result.field_8, 8);
+    assert_eq!(result.field_11, "field_11");
+    assert_eq!(result.field_12, vec![0x13, 0x37]);
+
+    let result = faas.rust_bindings("unknown_module");
+    assert!(result.is_err());
+}
+
+fn signature(
+    name: &str,
+    arguments: &[(&str, IType)],
+    outputs: Vec<IType>,
+) -> FaaSFunctionSignature {
+    let arguments = arguments
+        .iter()
+        .map(|(name, ty)| IFunctionArg {
+            name: name.to_string(),
+            ty: ty.clone(),
+        })
+        .collect();
+
+    FaaSFunctionSignature {
+        name: Arc::new(name.to_string()),
+        arguments: Arc::new(arguments),
+        outputs: Arc::new(outputs),
+    }
+}
+
+#[test]
+pub fn reserved_names_bindings() {
+    let fields = ["type", "self", "fn"]
+        .iter()
+        .map(|name| IRecordFieldType {
+            name: name.to_string(),
+            ty: IType::S32,
+        })
+        .collect();
+    let record_type = IRecordType {
+        name: String::from("Item"),
+        fields: fluence_faas::ne_vec::NEVec::new(fields).expect("record has fields"),
+    };
+    let mut record_types = RecordTypes::new();
+    record_types.insert(0, Arc::new(record_type));
+
+    let module_interface = FaaSModuleInterface {
+        record_types: &record_types,
+        function_signatures: vec![
+            signature("new", &[("type", IType::S32)], vec![IType::S32]),
+            signature("new_", &[], vec![]),
+            signature("with_call_parameters", &[("crate", IType::String)], vec![]),
+            signature(
+                "self",
+                &[("item", IType::Record(0))],
+                vec![IType::Record(0)],
+            ),
+            signature("match", &[("super", IType::U8)], vec![IType::U8]),
+        ],
+    };
+
+    let bindings = fluence_faas::module_rust_bindings("reserved_names", &module_interface);
+    check_bindings("reserved_names.rs", &bindings);
+
+    // the client keeps its own methods, functions with the same names get a suffix
+    assert!(bindings.contains("    pub fn new(faas: &'f mut FluenceFaaS) -> Self {"));
+    assert!(
+        bindings.contains("    pub fn new__(&mut self, r#type: i32) -> Result<i32, FaaSError> {")
+    );
+    assert!(bindings.contains("    pub fn new_(&mut self) -> Result<(), FaaSError> {"));
+    assert!(bindings.contains(
+        "    pub fn with_call_parameters_(&mut self, crate_: String) -> Result<(), FaaSError> {"
+    ));
+    assert!(
+        bindings.contains("    pub fn self_(&mut self, item: Item) -> Result<Item, FaaSError> {")
+    );
+    assert!(
+        bindings.contains("    pub fn r#match(&mut self, super_: u8) -> Result<u8, FaaSError> {")
+    );
+}
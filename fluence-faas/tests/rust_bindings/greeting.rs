@@ -0,0 +1,73 @@
+// Generated from the interface of the `greeting` module, don't edit it manually.
+
+#![allow(dead_code, unused_imports, clippy::all)]
+
+use fluence_faas::CallParameters;
+use fluence_faas::FaaSError;
+use fluence_faas::FluenceFaaS;
+use fluence_faas::IValue;
+
+pub const MODULE_NAME: &str = "greeting";
+
+/// Calls export functions of the module with typed arguments.
+pub struct GreetingClient<'f> {
+    faas: &'f mut FluenceFaaS,
+    call_parameters: CallParameters,
+}
+
+impl<'f> GreetingClient<'f> {
+    pub fn new(faas: &'f mut FluenceFaaS) -> Self {
+        Self {
+            faas,
+            call_parameters: <_>::default(),
+        }
+    }
+
+    pub fn with_call_parameters(mut self, call_parameters: CallParameters) -> Self {
+        self.call_parameters = call_parameters;
+        self
+    }
+
+    pub fn greeting(&mut self, name: String) -> Result<String, FaaSError> {
+        let arguments = vec![
+            IValue::String(name),
+        ];
+
+        let outputs = self.faas.call_with_ivalues(
+            MODULE_NAME,
+            "greeting",
+            &arguments,
+            self.call_parameters.clone(),
+        )?;
+
+        let value = single_output(outputs)?;
+        match value { IValue::String(value) => Ok(value), value => Err(unexpected_value("String", value)) }
+    }
+}
+
+fn unexpected_value(expected: &str, value: IValue) -> FaaSError {
+    FaaSError::TypedResultError(format!("expected {}, got {:?}", expected, value))
+}
+
+fn next_field(
+    fields: &mut impl Iterator<Item = IValue>,
+    record_name: &str,
+    field_name: &str,
+) -> Result<IValue, FaaSError> {
+    fields.next().ok_or_else(|| {
+        FaaSError::TypedResultError(format!(
+            "field {} of record {} is missing",
+            field_name, record_name
+        ))
+    })
+}
+
+fn single_output(mut outputs: Vec<IValue>) -> Result<IValue, FaaSError> {
+    match outputs.len() {
+        1 => Ok(outputs.remove(0)),
+        _ => Err(FaaSError::TypedResultError(format!(
+            "expected exactly one output, got {:?}",
+            outputs
+        ))),
+    }
+}
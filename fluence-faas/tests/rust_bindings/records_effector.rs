@@ -0,0 +1,153 @@
+// Generated from the interface of the `records_effector` module, don't edit it manually.
+
+#![allow(dead_code, unused_imports, clippy::all)]
+
+use fluence_faas::CallParameters;
+use fluence_faas::FaaSError;
+use fluence_faas::FluenceFaaS;
+use fluence_faas::IValue;
+
+pub const MODULE_NAME: &str = "records_effector";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestRecord {
+    pub field_0: i32,
+    pub field_1: i8,
+    pub field_2: i16,
+    pub field_3: i32,
+    pub field_4: i64,
+    pub field_5: u8,
+    pub field_6: u16,
+    pub field_7: u32,
+    pub field_8: u64,
+    pub field_9: f32,
+    pub field_10: f64,
+    pub field_11: String,
+    pub field_12: Vec<u8>,
+}
+
+impl TestRecord {
+    pub fn into_ivalue(self) -> IValue {
+        let fields = vec![
+            IValue::I32(self.field_0),
+            IValue::S8(self.field_1),
+            IValue::S16(self.field_2),
+            IValue::S32(self.field_3),
+            IValue::S64(self.field_4),
+            IValue::U8(self.field_5),
+            IValue::U16(self.field_6),
+            IValue::U32(self.field_7),
+            IValue::U64(self.field_8),
+            IValue::F32(self.field_9),
+            IValue::F64(self.field_10),
+            IValue::String(self.field_11),
+            IValue::Array(self.field_12.into_iter().map(|value| IValue::U8(value)).collect()),
+        ];
+
+        IValue::Record(
+            fluence_faas::ne_vec::NEVec::new(fields)
+                .expect("records always have at least one field"),
+        )
+    }
+
+    pub fn from_ivalue(value: IValue) -> Result<Self, FaaSError> {
+        let mut fields = match value {
+            IValue::Record(fields) => fields.into_vec().into_iter(),
+            value => return Err(unexpected_value("TestRecord", value)),
+        };
+
+        let record = Self {
+            field_0: next_field(&mut fields, "TestRecord", "field_0")
+                .and_then(|value| match value { IValue::I32(value) => Ok(value), value => Err(unexpected_value("I32", value)) })?,
+            field_1: next_field(&mut fields, "TestRecord", "field_1")
+                .and_then(|value| match value { IValue::S8(value) => Ok(value), value => Err(unexpected_value("S8", value)) })?,
+            field_2: next_field(&mut fields, "TestRecord", "field_2")
+                .and_then(|value| match value { IValue::S16(value) => Ok(value), value => Err(unexpected_value("S16", value)) })?,
+            field_3: next_field(&mut fields, "TestRecord", "field_3")
+                .and_then(|value| match value { IValue::S32(value) => Ok(value), value => Err(unexpected_value("S32", value)) })?,
+            field_4: next_field(&mut fields, "TestRecord", "field_4")
+                .and_then(|value| match value { IValue::S64(value) => Ok(value), value => Err(unexpected_value("S64", value)) })?,
+            field_5: next_field(&mut fields, "TestRecord", "field_5")
+                .and_then(|value| match value { IValue::U8(value) => Ok(value), value => Err(unexpected_value("U8", value)) })?,
+            field_6: next_field(&mut fields, "TestRecord", "field_6")
+                .and_then(|value| match value { IValue::U16(value) => Ok(value), value => Err(unexpected_value("U16", value)) })?,
+            field_7: next_field(&mut fields, "TestRecord", "field_7")
+                .and_then(|value| match value { IValue::U32(value) => Ok(value), value => Err(unexpected_value("U32", value)) })?,
+            field_8: next_field(&mut fields, "TestRecord", "field_8")
+                .and_then(|value| match value { IValue::U64(value) => Ok(value), value => Err(unexpected_value("U64", value)) })?,
+            field_9: next_field(&mut fields, "TestRecord", "field_9")
+                .and_then(|value| match value { IValue::F32(value) => Ok(value), value => Err(unexpected_value("F32", value)) })?,
+            field_10: next_field(&mut fields, "TestRecord", "field_10")
+                .and_then(|value| match value { IValue::F64(value) => Ok(value), value => Err(unexpected_value("F64", value)) })?,
+            field_11: next_field(&mut fields, "TestRecord", "field_11")
+                .and_then(|value| match value { IValue::String(value) => Ok(value), value => Err(unexpected_value("String", value)) })?,
+            field_12: next_field(&mut fields, "TestRecord", "field_12")
+                .and_then(|value| match value { IValue::Array(values) => values.into_iter().map(|value| match value { IValue::U8(value) => Ok(value), value => Err(unexpected_value("U8", value)) }).collect::<Result<Vec<_>, FaaSError>>(), value => Err(unexpected_value("Array<U8>", value)) })?,
+        };
+
+        Ok(record)
+    }
+}
+
+/// Calls export functions of the module with typed arguments.
+pub struct RecordsEffectorClient<'f> {
+    faas: &'f mut FluenceFaaS,
+    call_parameters: CallParameters,
+}
+
+impl<'f> RecordsEffectorClient<'f> {
+    pub fn new(faas: &'f mut FluenceFaaS) -> Self {
+        Self {
+            faas,
+            call_parameters: <_>::default(),
+        }
+    }
+
+    pub fn with_call_parameters(mut self, call_parameters: CallParameters) -> Self {
+        self.call_parameters = call_parameters;
+        self
+    }
+
+    pub fn mutate_struct(&mut self, test_record: TestRecord) -> Result<TestRecord, FaaSError> {
+        let arguments = vec![
+            test_record.into_ivalue(),
+        ];
+
+        let outputs = self.faas.call_with_ivalues(
+            MODULE_NAME,
+            "mutate_struct",
+            &arguments,
+            self.call_parameters.clone(),
+        )?;
+
+        let value = single_output(outputs)?;
+        TestRecord::from_ivalue(value)
+    }
+}
+
+fn unexpected_value(expected: &str, value: IValue) -> FaaSError {
+    FaaSError::TypedResultError(format!("expected {}, got {:?}", expected, value))
+}
+
+fn next_field(
+    fields: &mut impl Iterator<Item = IValue>,
+    record_name: &str,
+    field_name: &str,
+) -> Result<IValue, FaaSError> {
+    fields.next().ok_or_else(|| {
+        FaaSError::TypedResultError(format!(
+            "field {} of record {} is missing",
+            field_name, record_name
+        ))
+    })
+}
+
+fn single_output(mut outputs: Vec<IValue>) -> Result<IValue, FaaSError> {
+    match outputs.len() {
+        1 => Ok(outputs.remove(0)),
+        _ => Err(FaaSError::TypedResultError(format!(
+            "expected exactly one output, got {:?}",
+            outputs
+        ))),
+    }
+}
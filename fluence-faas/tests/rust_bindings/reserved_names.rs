@@ -0,0 +1,176 @@
+// Generated from the interface of the `reserved_names` module, don't edit it manually.
+
+#![allow(dead_code, unused_imports, clippy::all)]
+
+use fluence_faas::CallParameters;
+use fluence_faas::FaaSError;
+use fluence_faas::FluenceFaaS;
+use fluence_faas::IValue;
+
+pub const MODULE_NAME: &str = "reserved_names";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Item {
+    pub r#type: i32,
+    pub self_: i32,
+    pub r#fn: i32,
+}
+
+impl Item {
+    pub fn into_ivalue(self) -> IValue {
+        let fields = vec![
+            IValue::S32(self.r#type),
+            IValue::S32(self.self_),
+            IValue::S32(self.r#fn),
+        ];
+
+        IValue::Record(
+            fluence_faas::ne_vec::NEVec::new(fields)
+                .expect("records always have at least one field"),
+        )
+    }
+
+    pub fn from_ivalue(value: IValue) -> Result<Self, FaaSError> {
+        let mut fields = match value {
+            IValue::Record(fields) => fields.into_vec().into_iter(),
+            value => return Err(unexpected_value("Item", value)),
+        };
+
+        let record = Self {
+            r#type: next_field(&mut fields, "Item", "type")
+                .and_then(|value| match value { IValue::S32(value) => Ok(value), value => Err(unexpected_value("S32", value)) })?,
+            self_: next_field(&mut fields, "Item", "self")
+                .and_then(|value| match value { IValue::S32(value) => Ok(value), value => Err(unexpected_value("S32", value)) })?,
+            r#fn: next_field(&mut fields, "Item", "fn")
+                .and_then(|value| match value { IValue::S32(value) => Ok(value), value => Err(unexpected_value("S32", value)) })?,
+        };
+
+        Ok(record)
+    }
+}
+
+/// Calls export functions of the module with typed arguments.
+pub struct ReservedNamesClient<'f> {
+    faas: &'f mut FluenceFaaS,
+    call_parameters: CallParameters,
+}
+
+impl<'f> ReservedNamesClient<'f> {
+    pub fn new(faas: &'f mut FluenceFaaS) -> Self {
+        Self {
+            faas,
+            call_parameters: <_>::default(),
+        }
+    }
+
+    pub fn with_call_parameters(mut self, call_parameters: CallParameters) -> Self {
+        self.call_parameters = call_parameters;
+        self
+    }
+
+    pub fn r#match(&mut self, super_: u8) -> Result<u8, FaaSError> {
+        let arguments = vec![
+            IValue::U8(super_),
+        ];
+
+        let outputs = self.faas.call_with_ivalues(
+            MODULE_NAME,
+            "match",
+            &arguments,
+            self.call_parameters.clone(),
+        )?;
+
+        let value = single_output(outputs)?;
+        match value { IValue::U8(value) => Ok(value), value => Err(unexpected_value("U8", value)) }
+    }
+
+    /// Calls the export function `new`.
+    pub fn new__(&mut self, r#type: i32) -> Result<i32, FaaSError> {
+        let arguments = vec![
+            IValue::S32(r#type),
+        ];
+
+        let outputs = self.faas.call_with_ivalues(
+            MODULE_NAME,
+            "new",
+            &arguments,
+            self.call_parameters.clone(),
+        )?;
+
+        let value = single_output(outputs)?;
+        match value { IValue::S32(value) => Ok(value), value => Err(unexpected_value("S32", value)) }
+    }
+
+    pub fn new_(&mut self) -> Result<(), FaaSError> {
+        let arguments = vec![
+        ];
+
+        self.faas.call_with_ivalues(
+            MODULE_NAME,
+            "new_",
+            &arguments,
+            self.call_parameters.clone(),
+        )?;
+
+        Ok(())
+    }
+
+    pub fn self_(&mut self, item: Item) -> Result<Item, FaaSError> {
+        let arguments = vec![
+            item.into_ivalue(),
+        ];
+
+        let outputs = self.faas.call_with_ivalues(
+            MODULE_NAME,
+            "self",
+            &arguments,
+            self.call_parameters.clone(),
+        )?;
+
+        let value = single_output(outputs)?;
+        Item::from_ivalue(value)
+    }
+
+    /// Calls the export function `with_call_parameters`.
+    pub fn with_call_parameters_(&mut self, crate_: String) -> Result<(), FaaSError> {
+        let arguments = vec![
+            IValue::String(crate_),
+        ];
+
+        self.faas.call_with_ivalues(
+            MODULE_NAME,
+            "with_call_parameters",
+            &arguments,
+            self.call_parameters.clone(),
+        )?;
+
+        Ok(())
+    }
+}
+
+fn unexpected_value(expected: &str, value: IValue) -> FaaSError {
+    FaaSError::TypedResultError(format!("expected {}, got {:?}", expected, value))
+}
+
+fn next_field(
+    fields: &mut impl Iterator<Item = IValue>,
+    record_name: &str,
+    field_name: &str,
+) -> Result<IValue, FaaSError> {
+    fields.next().ok_or_else(|| {
+        FaaSError::TypedResultError(format!(
+            "field {} of record {} is missing",
+            field_name, record_name
+        ))
+    })
+}
+
+fn single_output(mut outputs: Vec<IValue>) -> Result<IValue, FaaSError> {
+    match outputs.len() {
+        1 => Ok(outputs.remove(0)),
+        _ => Err(FaaSError::TypedResultError(format!(
+            "expected exactly one output, got {:?}",
+            outputs
+        ))),
+    }
+}
@@ -0,0 +1,208 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+mod common;
+
+use common::create_faas;
+
+use pretty_assertions::assert_eq;
+use tracing::field::Field;
+use tracing::field::Visit;
+use tracing::span::Attributes;
+use tracing_core::span::Current;
+use tracing::span::Id;
+use tracing::span::Record;
+use tracing::Event;
+use tracing::Metadata;
+use tracing::Subscriber;
+
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct RecordedSpan {
+    name: &'static str,
+    fields: Vec<String>,
+    parent: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct RecordedEvent {
+    target: String,
+    level: tracing::Level,
+    fields: Vec<String>,
+}
+
+#[derive(Default)]
+struct RecorderState {
+    spans: Vec<RecordedSpan>,
+    spans_metadata: Vec<&'static Metadata<'static>>,
+    events: Vec<RecordedEvent>,
+    entered: Vec<u64>,
+}
+
+/// Records created spans with their parents and events,
+/// ids are indices in spans starting from 1.
+#[derive(Default, Clone)]
+struct SpansRecorder(Arc<Mutex<RecorderState>>);
+
+struct FieldsVisitor<'v>(&'v mut Vec<String>);
+
+impl Visit for FieldsVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.push(format!("{}={}", field.name(), value));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0.push(format!("{}={:?}", field.name(), value));
+    }
+}
+
+impl Subscriber for SpansRecorder {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut state = self.0.lock().unwrap();
+
+        let parent = match span.parent() {
+            Some(parent) => Some(parent.into_u64()),
+            None if span.is_contextual() => state.entered.last().cloned(),
+            None => None,
+        };
+        let mut fields = Vec::new();
+        span.record(&mut FieldsVisitor(&mut fields));
+
+        state.spans_metadata.push(span.metadata());
+        state.spans.push(RecordedSpan {
+            name: span.metadata().name(),
+            fields,
+            parent,
+        });
+
+        Id::from_u64(state.spans.len() as u64)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Vec::new();
+        event.record(&mut FieldsVisitor(&mut fields));
+
+        self.0.lock().unwrap().events.push(RecordedEvent {
+            target: event.metadata().target().to_string(),
+            level: *event.metadata().level(),
+            fields,
+        });
+    }
+
+    fn enter(&self, span: &Id) {
+        self.0.lock().unwrap().entered.push(span.into_u64());
+    }
+
+    fn exit(&self, _span: &Id) {
+        self.0.lock().unwrap().entered.pop();
+    }
+
+    fn current_span(&self) -> Current {
+        let state = self.0.lock().unwrap();
+        match state.entered.last() {
+            Some(&id) => Current::new(Id::from_u64(id), state.spans_metadata[id as usize - 1]),
+            None => Current::none(),
+        }
+    }
+}
+
+#[test]
+pub fn nested_calls_have_parent_spans() {
+    let mut faas = create_faas("records");
+
+    let call_parameters = fluence_faas::CallParameters {
+        particle_id: String::from("particle_id"),
+        init_peer_id: String::from("init_peer_id"),
+        ..<_>::default()
+    };
+
+    let recorder = SpansRecorder::default();
+    tracing::subscriber::with_default(recorder.clone(), || {
+        faas.call_with_ivalues("records_pure", "invoke", &[], call_parameters)
+            .unwrap_or_else(|e| panic!("can't invoke pure: {:?}", e));
+    });
+
+    let spans = recorder.0.lock().unwrap().spans.clone();
+    assert_eq!(
+        spans,
+        vec![
+            RecordedSpan {
+                name: "faas_call",
+                fields: vec![
+                    String::from("module=records_pure"),
+                    String::from("function=invoke"),
+                    String::from("particle_id=particle_id"),
+                    String::from("init_peer_id=init_peer_id"),
+                    String::from("service_id="),
+                ],
+                parent: None,
+            },
+            RecordedSpan {
+                name: "import_call",
+                fields: vec![
+                    String::from("module=records_effector"),
+                    String::from("function=mutate_struct"),
+                ],
+                parent: Some(1),
+            },
+        ]
+    );
+}
+
+#[test]
+pub fn module_logs_are_events_of_module() {
+    let mut module = fluence_faas::TomlFaaSNamedModuleConfig::new("local_storage");
+    module.config.logger_enabled = Some(true);
+    let config = fluence_faas::TomlFaaSConfig {
+        modules_dir: Some(String::from("../examples/url-downloader/artifacts/")),
+        module: vec![module],
+        ..<_>::default()
+    };
+
+    let mut faas = common::create_faas_with_config(config);
+
+    let recorder = SpansRecorder::default();
+    tracing::subscriber::with_default(recorder.clone(), || {
+        faas.call_with_ivalues(
+            "local_storage",
+            "get",
+            &[fluence_faas::IValue::String(String::from("file"))],
+            <_>::default(),
+        )
+        .unwrap_or_else(|e| panic!("can't invoke get: {:?}", e));
+    });
+
+    let events = recorder.0.lock().unwrap().events.clone();
+    assert_eq!(
+        events,
+        vec![RecordedEvent {
+            target: String::from("local_storage"),
+            level: tracing::Level::INFO,
+            fields: vec![String::from("message=get called with file name: file")],
+        }]
+    );
+}
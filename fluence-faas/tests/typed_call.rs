@@ -0,0 +1,160 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use fluence_faas::FaaSError;
+use fluence_faas::FluenceFaaS;
+
+use pretty_assertions::assert_eq;
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+struct TestRecord {
+    field_0: bool,
+    field_1: i8,
+    field_2: i16,
+    field_3: i32,
+    field_4: i64,
+    field_5: u8,
+    field_6: u16,
+    field_7: u32,
+    field_8: u64,
+    field_9: f32,
+    field_10: f64,
+    field_11: String,
+    field_12: Vec<u8>,
+}
+
+#[test]
+pub fn greeting_typed() {
+    let greeting_config_path = "../examples/greeting/Config.toml";
+
+    let greeting_config_raw = std::fs::read(greeting_config_path)
+        .expect("../examples/greeting/Config.toml should presence");
+
+    let mut greeting_config: fluence_faas::TomlFaaSConfig =
+        toml::from_slice(&greeting_config_raw).expect("greeting config should be well-formed");
+    greeting_config.modules_dir = Some(String::from("../examples/greeting/artifacts"));
+
+    let mut faas = FluenceFaaS::with_raw_config(greeting_config)
+        .unwrap_or_else(|e| panic!("can't create Fluence FaaS instance: {:?}", e));
+
+    let result: String = faas
+        .call_typed("greeting", "greeting", &("Fluence",), <_>::default())
+        .unwrap_or_else(|e| panic!("can't invoke greeting: {:?}", e));
+    assert_eq!(result, "Hi, Fluence");
+
+    let result: Option<String> = faas
+        .call_typed("greeting", "greeting", &[""], <_>::default())
+        .unwrap_or_else(|e| panic!("can't invoke greeting: {:?}", e));
+    assert_eq!(result, Some(String::from("Hi, ")));
+
+    let result = faas.call_typed::<_, String, _, _>("greeting", "greeting", &(1,), <_>::default());
+    match result {
+        Err(FaaSError::TypedArgumentsError(err_msg)) => {
+            assert!(err_msg.starts_with("args[0]"), "{}", err_msg)
+        }
+        result => panic!("expected an arguments error, got {:?}", result),
+    }
+}
+
+#[test]
+pub fn records_typed() {
+    let records_config_path = "../examples/records/Config.toml";
+
+    let records_config_raw = std::fs::read(records_config_path)
+        .expect("../examples/records/Config.toml should presence");
+
+    let mut records_config: fluence_faas::TomlFaaSConfig =
+        toml::from_slice(&records_config_raw).expect("records config should be well-formed");
+    records_config.modules_dir = Some(String::from("../examples/records/artifacts/"));
+
+    let mut faas = FluenceFaaS::with_raw_config(records_config)
+        .unwrap_or_else(|e| panic!("can't create Fluence FaaS instance: {:?}", e));
+
+    #[derive(Serialize)]
+    struct MutateStructArgs {
+        test_record: TestRecord,
+    }
+
+    let args = MutateStructArgs {
+        test_record: TestRecord::default(),
+    };
+    let result: TestRecord = faas
+        .call_typed("records_effector", "mutate_struct", &args, <_>::default())
+        .unwrap_or_else(|e| panic!("can't invoke mutate_struct: {:?}", e));
+
+    let right_result = TestRecord {
+        field_0: true,
+        field_1: 1,
+        field_2: 2,
+        field_3: 3,
+        field_4: 4,
+        field_5: 5,
+        field_6: 6,
+        field_7: 7,
+        field_8: 8,
+        field_9: 9.0,
+        field_10: 10.0,
+        field_11: String::from("field_11"),
+        field_12: vec![0x13, 0x37],
+    };
+    assert_eq!(result, right_result);
+
+    // records could be also received as tuples in the order of their fields
+    type TupleRecord = (
+        bool,
+        i8,
+        i16,
+        i32,
+        i64,
+        u8,
+        u16,
+        u32,
+        u64,
+        f32,
+        f64,
+        String,
+        Vec<u8>,
+    );
+    let result: TupleRecord = faas
+        .call_typed(
+            "records_effector",
+            "mutate_struct",
+            &(TestRecord::default(),),
+            <_>::default(),
+        )
+        .unwrap_or_else(|e| panic!("can't invoke mutate_struct: {:?}", e));
+    assert_eq!(result.11, "field_11");
+
+    #[derive(Serialize)]
+    struct PartialRecord {
+        field_0: bool,
+    }
+
+    let result = faas.call_typed::<_, TestRecord, _, _>(
+        "records_effector",
+        "mutate_struct",
+        &(PartialRecord { field_0: true },),
+        <_>::default(),
+    );
+    match result {
+        Err(FaaSError::TypedArgumentsError(err_msg)) => {
+            assert!(err_msg.starts_with("args[0]"), "{}", err_msg)
+        }
+        result => panic!("expected an arguments error, got {:?}", result),
+    }
+}
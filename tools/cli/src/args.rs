@@ -23,6 +23,16 @@ pub const DESCRIPTION: &str = env!("CARGO_PKG_DESCRIPTION");
 pub const IN_WASM_PATH: &str = "in-wasm-path";
 pub const WIT_PATH: &str = "wit-path";
 pub const OUT_WASM_PATH: &str = "out-wasm-path";
+pub const BYTE_ARRAY_ENCODING: &str = "byte-array-encoding";
+pub const INT64_AS_STRING: &str = "int64-as-string";
+pub const MODULE_NAME: &str = "module-name";
+pub const SERVICE_ID: &str = "service-id";
+pub const OUT_PATH: &str = "out-path";
+pub const FORMAT: &str = "format";
+pub const SECRET_KEY_PATH: &str = "secret-key-path";
+pub const DETACHED: &str = "detached";
+pub const TRUSTED_KEY: &str = "trusted-key";
+pub const SIGNATURE_PATH: &str = "signature-path";
 
 pub fn build<'a, 'b>() -> App<'a, 'b> {
     SubCommand::with_name("build")
@@ -64,6 +74,134 @@ pub fn show_wit<'a, 'b>() -> App<'a, 'b> {
             .help("path to the Wasm file")])
 }
 
+pub fn schema<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("schema")
+        .about("Show JSON Schema of arguments and results of functions of provided Wasm file")
+        .setting(clap::AppSettings::ArgRequiredElseHelp)
+        .args(&[
+            Arg::with_name(IN_WASM_PATH)
+                .required(true)
+                .takes_value(true)
+                .short("i")
+                .help("path to the Wasm file"),
+            Arg::with_name(BYTE_ARRAY_ENCODING)
+                .takes_value(true)
+                .long(BYTE_ARRAY_ENCODING)
+                .possible_values(&["array", "base64", "hex"])
+                .help("encoding of byte arrays in results"),
+            Arg::with_name(INT64_AS_STRING)
+                .long(INT64_AS_STRING)
+                .help("64-bit integers are passed as decimal strings"),
+        ])
+}
+
+pub fn bindings<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("bindings")
+        .about("Generate typed Rust client bindings for functions of provided Wasm file")
+        .setting(clap::AppSettings::ArgRequiredElseHelp)
+        .args(&[
+            Arg::with_name(IN_WASM_PATH)
+                .required(true)
+                .takes_value(true)
+                .short("i")
+                .help("path to the Wasm file"),
+            Arg::with_name(MODULE_NAME)
+                .takes_value(true)
+                .long(MODULE_NAME)
+                .help("name of the module in a service config, the file stem by default"),
+            Arg::with_name(OUT_PATH)
+                .takes_value(true)
+                .short("o")
+                .help("path to result file with bindings, they are printed if it isn't specified"),
+        ])
+}
+
+pub fn declarations<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("declarations")
+        .about(
+            "Show TypeScript or Aqua declarations of functions and records of provided Wasm file",
+        )
+        .setting(clap::AppSettings::ArgRequiredElseHelp)
+        .args(&[
+            Arg::with_name(IN_WASM_PATH)
+                .required(true)
+                .takes_value(true)
+                .short("i")
+                .help("path to the Wasm file"),
+            Arg::with_name(FORMAT)
+                .required(true)
+                .takes_value(true)
+                .long(FORMAT)
+                .possible_values(&["typescript", "aqua"])
+                .help("language of declarations"),
+            Arg::with_name(MODULE_NAME)
+                .takes_value(true)
+                .long(MODULE_NAME)
+                .help("name of the service, the file stem by default"),
+            Arg::with_name(SERVICE_ID)
+                .takes_value(true)
+                .long(SERVICE_ID)
+                .help("default id of the Aqua service, the service name by default"),
+            Arg::with_name(BYTE_ARRAY_ENCODING)
+                .takes_value(true)
+                .long(BYTE_ARRAY_ENCODING)
+                .possible_values(&["array", "base64", "hex"])
+                .help("encoding of byte arrays in TypeScript declarations"),
+            Arg::with_name(INT64_AS_STRING)
+                .long(INT64_AS_STRING)
+                .help("64-bit integers are passed as decimal strings"),
+        ])
+}
+
+pub fn sign<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("sign")
+        .about("Sign provided Wasm file with an ed25519 key")
+        .setting(clap::AppSettings::ArgRequiredElseHelp)
+        .args(&[
+            Arg::with_name(IN_WASM_PATH)
+                .required(true)
+                .takes_value(true)
+                .short("i")
+                .help("path to the Wasm file"),
+            Arg::with_name(SECRET_KEY_PATH)
+                .required(true)
+                .takes_value(true)
+                .short("k")
+                .help("path to file with a hex ed25519 secret key"),
+            Arg::with_name(DETACHED)
+                .long(DETACHED)
+                .help("make a detached hex signature instead of embedding it to the Wasm file"),
+            Arg::with_name(OUT_PATH)
+                .takes_value(true)
+                .short("o")
+                .help("path to result file with signed Wasm or a detached signature"),
+        ])
+}
+
+pub fn verify<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("verify")
+        .about("Verify that provided Wasm file is signed by one of trusted keys")
+        .setting(clap::AppSettings::ArgRequiredElseHelp)
+        .args(&[
+            Arg::with_name(IN_WASM_PATH)
+                .required(true)
+                .takes_value(true)
+                .short("i")
+                .help("path to the Wasm file"),
+            Arg::with_name(TRUSTED_KEY)
+                .required(true)
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1)
+                .short("k")
+                .help("hex ed25519 public key trusted to sign modules"),
+            Arg::with_name(SIGNATURE_PATH)
+                .takes_value(true)
+                .short("s")
+                .help("path to file with a detached hex signature, the embedded one is checked by default"),
+        ])
+}
+
 pub fn repl<'a, 'b>() -> App<'a, 'b> {
     SubCommand::with_name("repl")
         .about("Start Fluence application service REPL")
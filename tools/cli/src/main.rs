@@ -28,6 +28,7 @@
 mod args;
 mod build;
 mod errors;
+mod module_interface;
 
 pub(crate) type Result<T> = std::result::Result<T, crate::errors::CLIError>;
 
@@ -39,6 +40,11 @@ pub fn main() -> std::result::Result<(), anyhow::Error> {
         .subcommand(args::build())
         .subcommand(args::embed_wit())
         .subcommand(args::show_wit())
+        .subcommand(args::schema())
+        .subcommand(args::bindings())
+        .subcommand(args::declarations())
+        .subcommand(args::sign())
+        .subcommand(args::verify())
         .subcommand(args::repl());
     let arg_matches = app.get_matches();
 
@@ -77,6 +83,135 @@ pub fn main() -> std::result::Result<(), anyhow::Error> {
 
             Ok(())
         }
+        ("schema", Some(arg)) => {
+            let wasm_path = arg.value_of(args::IN_WASM_PATH).unwrap();
+            let wasm_path = std::path::PathBuf::from(wasm_path);
+            let json_options = json_options(arg);
+
+            let module_name = wasm_path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let module_interface = module_interface::ModuleInterface::load(wasm_path)?;
+            let schema = fluence_faas::module_json_schema(
+                &module_name,
+                &module_interface.as_faas_interface(),
+                &json_options,
+            );
+            println!("{}", serde_json::to_string_pretty(&schema)?);
+
+            Ok(())
+        }
+        ("bindings", Some(arg)) => {
+            let wasm_path = arg.value_of(args::IN_WASM_PATH).unwrap();
+            let wasm_path = std::path::PathBuf::from(wasm_path);
+
+            let module_name = match arg.value_of(args::MODULE_NAME) {
+                Some(module_name) => module_name.to_string(),
+                None => wasm_path
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+            };
+            let module_interface = module_interface::ModuleInterface::load(wasm_path)?;
+            let bindings = fluence_faas::module_rust_bindings(
+                &module_name,
+                &module_interface.as_faas_interface(),
+            );
+
+            match arg.value_of(args::OUT_PATH) {
+                Some(out_path) => std::fs::write(out_path, bindings)?,
+                None => print!("{}", bindings),
+            }
+
+            Ok(())
+        }
+        ("declarations", Some(arg)) => {
+            let wasm_path = arg.value_of(args::IN_WASM_PATH).unwrap();
+            let wasm_path = std::path::PathBuf::from(wasm_path);
+
+            let service_name = match arg.value_of(args::MODULE_NAME) {
+                Some(service_name) => service_name.to_string(),
+                None => wasm_path
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+            };
+            let module_interface = module_interface::ModuleInterface::load(wasm_path)?;
+            let faas_interface = module_interface.as_faas_interface();
+
+            let declarations = match arg.value_of(args::FORMAT) {
+                Some("aqua") => fluence_app_service::service_aqua_declarations(
+                    &service_name,
+                    arg.value_of(args::SERVICE_ID).unwrap_or(&service_name),
+                    &faas_interface,
+                )?,
+                // other values are rejected by clap
+                _ => fluence_app_service::service_typescript_declarations(
+                    &service_name,
+                    &faas_interface,
+                    &json_options(arg),
+                ),
+            };
+            print!("{}", declarations);
+
+            Ok(())
+        }
+        ("sign", Some(arg)) => {
+            let in_wasm_path = arg.value_of(args::IN_WASM_PATH).unwrap();
+            let secret_key_path = arg.value_of(args::SECRET_KEY_PATH).unwrap();
+
+            let wasm = std::fs::read(in_wasm_path)?;
+            let secret_key = read_hex(secret_key_path)?;
+            let public_key = fluence_faas::signing_public_key(&secret_key)?;
+
+            if arg.is_present(args::DETACHED) {
+                let signature = fluence_faas::module_signature(&wasm, &secret_key)?;
+                let signature = hex::encode(signature);
+
+                match arg.value_of(args::OUT_PATH) {
+                    Some(out_path) => std::fs::write(out_path, signature)?,
+                    None => println!("{}", signature),
+                }
+            } else {
+                let signed_wasm = fluence_faas::sign_module(&wasm, &secret_key)?;
+                let out_wasm_path = arg.value_of(args::OUT_PATH).unwrap_or(in_wasm_path);
+                std::fs::write(out_wasm_path, signed_wasm)?;
+            }
+            eprintln!("signed by {}", hex::encode(public_key));
+
+            Ok(())
+        }
+        ("verify", Some(arg)) => {
+            use fluence_faas::ModuleSignatureConfig;
+
+            let wasm_path = arg.value_of(args::IN_WASM_PATH).unwrap();
+            let wasm = std::fs::read(wasm_path)?;
+
+            let trusted_keys = arg
+                .values_of(args::TRUSTED_KEY)
+                .unwrap_or_default()
+                .map(|key| {
+                    let mut key_bytes = [0u8; 32];
+                    hex::decode_to_slice(key, &mut key_bytes)
+                        .map_err(|e| anyhow::anyhow!("trusted key {} is malformed: {}", key, e))?;
+                    Ok(key_bytes)
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            let detached_signature = arg
+                .value_of(args::SIGNATURE_PATH)
+                .map(read_hex)
+                .transpose()?;
+
+            let config = ModuleSignatureConfig {
+                trusted_keys,
+                detached_signature,
+            };
+            fluence_faas::verify_module_signature(&wasm, &config)?;
+            println!("signature is valid");
+
+            Ok(())
+        }
         ("repl", Some(args)) => {
             use std::process::Command;
             // use UNIX-specific API for replacing process image
@@ -99,3 +234,26 @@ pub fn main() -> std::result::Result<(), anyhow::Error> {
         c => Err(crate::errors::CLIError::NoSuchCommand(c.0.to_string()).into()),
     }
 }
+
+/// Reads a hex encoded value from a file, surrounding whitespaces are ignored.
+fn read_hex(path: &str) -> std::result::Result<Vec<u8>, anyhow::Error> {
+    let content = std::fs::read_to_string(path)?;
+    hex::decode(content.trim()).map_err(|e| anyhow::anyhow!("{} isn't a hex file: {}", path, e))
+}
+
+/// Builds JSON options from the byte array encoding and int64 arguments of a subcommand.
+fn json_options(arg: &clap::ArgMatches<'_>) -> fluence_faas::JsonOptions {
+    use fluence_faas::ByteArrayEncoding;
+
+    let byte_array_encoding = match arg.value_of(args::BYTE_ARRAY_ENCODING) {
+        Some("base64") => ByteArrayEncoding::Base64,
+        Some("hex") => ByteArrayEncoding::Hex,
+        // other values are rejected by clap
+        _ => ByteArrayEncoding::Array,
+    };
+
+    fluence_faas::JsonOptions {
+        byte_array_encoding,
+        int64_as_string: arg.is_present(args::INT64_AS_STRING),
+    }
+}
@@ -0,0 +1,64 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::Result;
+
+use fluence_faas::FaaSFunctionSignature;
+use fluence_faas::FaaSModuleInterface;
+use fluence_faas::RecordTypes;
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Interface of a Wasm module converted to the representation used by FaaS.
+pub(crate) struct ModuleInterface {
+    record_types: RecordTypes,
+    function_signatures: Vec<FaaSFunctionSignature>,
+}
+
+impl ModuleInterface {
+    pub(crate) fn load(wasm_path: PathBuf) -> Result<Self> {
+        let raw_interface = fce_wit_parser::module_raw_interface(wasm_path)?;
+
+        let record_types = raw_interface
+            .record_types
+            .iter()
+            .map(|(id, record_type)| (*id, Arc::new(record_type.as_ref().clone())))
+            .collect();
+
+        let function_signatures = raw_interface
+            .function_signatures
+            .iter()
+            .map(|signature| FaaSFunctionSignature {
+                name: Arc::new(signature.name.as_ref().clone()),
+                arguments: Arc::new(signature.arguments.as_ref().clone()),
+                outputs: Arc::new(signature.outputs.as_ref().clone()),
+            })
+            .collect();
+
+        Ok(Self {
+            record_types,
+            function_signatures,
+        })
+    }
+
+    pub(crate) fn as_faas_interface(&self) -> FaaSModuleInterface<'_> {
+        FaaSModuleInterface {
+            record_types: &self.record_types,
+            function_signatures: self.function_signatures.clone(),
+        }
+    }
+}
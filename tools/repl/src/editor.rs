@@ -161,6 +161,8 @@ fn commands_hints() -> HashSet<String> {
     set.insert(String::from("envs"));
     set.insert(String::from("fs"));
     set.insert(String::from("interface"));
+    set.insert(String::from("journal"));
+    set.insert(String::from("replay"));
     set.insert(String::from("help"));
     set
 }
@@ -60,6 +60,8 @@ impl REPL {
             Some("e") | Some("envs") => self.show_envs(args),
             Some("f") | Some("fs") => self.show_fs(args),
             Some("i") | Some("interface") => self.show_interface(),
+            Some("j") | Some("journal") => self.journal(args),
+            Some("r") | Some("replay") => self.replay_journal(args),
             Some("q") | Some("quit") => {
                 return false;
             }
@@ -183,6 +185,54 @@ impl REPL {
         print!("Loaded modules interface:\n{}", interface);
     }
 
+    fn journal<'args>(&mut self, mut args: impl Iterator<Item = &'args str>) {
+        next_argument!(journal_path, args, "Journal path should be specified");
+
+        if journal_path == "off" {
+            self.app_service.stop_journal();
+            println!("journal recording stopped");
+            return;
+        }
+
+        match self.app_service.start_journal(journal_path) {
+            Ok(_) => println!("calls are being recorded to {}", journal_path),
+            Err(e) => println!("journal recording failed with: {}", e),
+        }
+    }
+
+    fn replay_journal<'args>(&mut self, mut args: impl Iterator<Item = &'args str>) {
+        next_argument!(journal_path, args, "Journal path should be specified");
+
+        // journal is replayed against a fresh service to have modules in the recorded state
+        match Self::create_app_service(args.next()) {
+            Ok(service) => self.app_service = service,
+            Err(e) => {
+                println!("failed to create a new application service: {}", e);
+                return;
+            }
+        };
+
+        let start = Instant::now();
+        let report = match self.app_service.replay_journal(journal_path) {
+            Ok(report) => report,
+            Err(e) => {
+                println!("replay failed with: {}", e);
+                return;
+            }
+        };
+        let elapsed_time = start.elapsed();
+
+        for divergence in &report.divergences {
+            println!("divergence in {}", divergence);
+        }
+        println!(
+            "{} calls replayed, {} divergences found\nelapsed time: {:?}",
+            report.replayed_calls,
+            report.divergences.len(),
+            elapsed_time
+        );
+    }
+
     fn create_app_service<S: Into<PathBuf>>(config_file_path: Option<S>) -> ReplResult<AppService> {
         let tmp_path: String = std::env::temp_dir().to_string_lossy().into();
         let service_id = uuid::Uuid::new_v4().to_string();
@@ -216,6 +266,8 @@ fn print_help() {
             u/unload <module_name>                    unload a Wasm module\n\
             c/call <module_name> <func_name> [args]   call function with given name from given module\n\
             i/interface                               print public interface of all loaded modules\n\
+            j/journal <journal_path>|off              start or stop recording calls to a journal\n\
+            r/replay <journal_path> [config_path]     replay a journal against a new service\n\
             e/envs <module_name>                      print environment variables of a module\n\
             f/fs <module_name>                        print filesystem state of a module\n\
             h/help                                    print this message\n\